@@ -0,0 +1,194 @@
+use crate::segments::core::Segment;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// One unit of work of a batch: a channel analysed over a segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkUnit {
+    pub channel: String,
+    pub segment: Segment,
+}
+
+/// The result of running the analysis on one [`WorkUnit`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitOutcome<T, E> {
+    pub unit: WorkUnit,
+    // Number of times the analysis was run, one more than the retries used
+    pub attempts: usize,
+    // Result of the last attempt
+    pub result: Result<T, E>,
+}
+
+impl<T, E> UnitOutcome<T, E> {
+    /// Returns true if the analysis of this unit eventually succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Options controlling how [`run_batch`] schedules work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BatchOptions {
+    // Largest number of units analysed at once; zero uses the available parallelism
+    pub max_parallel: usize,
+
+    // Number of times a failed unit is re-run before its error is kept
+    pub retries: usize,
+}
+
+/// Runs `analysis` over every combination of `channels` and `segments` on a bounded pool of
+/// threads.
+///
+/// Units are dispatched channel by channel, segment by segment, and a unit whose analysis
+/// fails is retried up to `options.retries` times. Failures never stop the batch: every
+/// unit gets an outcome, returned in dispatch order, holding either its result or the
+/// error of its last attempt.
+pub fn run_batch<T, E, F>(
+    channels: &[&str],
+    segments: &[Segment],
+    options: BatchOptions,
+    analysis: F,
+) -> Vec<UnitOutcome<T, E>>
+where
+    T: Send,
+    E: Send,
+    F: Fn(&WorkUnit) -> Result<T, E> + Sync,
+{
+    let units: Vec<WorkUnit> = channels
+        .iter()
+        .flat_map(|channel| {
+            segments.iter().map(|segment| WorkUnit {
+                channel: channel.to_string(),
+                segment: *segment,
+            })
+        })
+        .collect();
+    let workers = match options.max_parallel {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+    .min(units.len());
+
+    let next = AtomicUsize::new(0);
+    let outcomes: Mutex<Vec<Option<UnitOutcome<T, E>>>> =
+        Mutex::new((0..units.len()).map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(unit) = units.get(index) else {
+                        break;
+                    };
+                    let mut attempts = 1;
+                    let mut result = analysis(unit);
+                    while result.is_err() && attempts <= options.retries {
+                        attempts += 1;
+                        result = analysis(unit);
+                    }
+                    outcomes.lock().unwrap()[index] = Some(UnitOutcome {
+                        unit: unit.clone(),
+                        attempts,
+                        result,
+                    });
+                }
+            });
+        }
+    });
+
+    outcomes
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|outcome| outcome.expect("every work unit is run"))
+        .collect()
+}
+
+// --- Tests for the batch executor ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    #[test]
+    fn test_run_batch_collects_every_unit_in_order() {
+        let segments = [Segment::new(0.0, 10.0), Segment::new(20.0, 25.0)];
+        let outcomes = run_batch(
+            &["X1:A", "X1:B"],
+            &segments,
+            BatchOptions::default(),
+            |unit| {
+                if unit.channel == "X1:B" && unit.segment.start() == 20.0 {
+                    Err("no data")
+                } else {
+                    Ok(unit.segment.end() - unit.segment.start())
+                }
+            },
+        );
+        let summary: Vec<_> = outcomes
+            .iter()
+            .map(|o| (o.unit.channel.as_str(), o.unit.segment.start(), o.result))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("X1:A", 0.0, Ok(10.0)),
+                ("X1:A", 20.0, Ok(5.0)),
+                ("X1:B", 0.0, Ok(10.0)),
+                ("X1:B", 20.0, Err("no data")),
+            ]
+        );
+        assert!(outcomes.iter().all(|o| o.attempts == 1));
+    }
+
+    #[test]
+    fn test_run_batch_retries_failures() {
+        // Every unit fails on its first attempt only
+        let failed_once = Mutex::new(HashSet::new());
+        let options = BatchOptions {
+            max_parallel: 2,
+            retries: 2,
+        };
+        let segments = [Segment::new(0.0, 1.0), Segment::new(1.0, 2.0)];
+        let outcomes = run_batch(&["X1:A"], &segments, options, |unit| {
+            let key = unit.segment.start().to_bits();
+            if failed_once.lock().unwrap().insert(key) {
+                Err(())
+            } else {
+                Ok(())
+            }
+        });
+        assert!(outcomes.iter().all(|o| o.is_ok() && o.attempts == 2));
+
+        let options = BatchOptions {
+            max_parallel: 1,
+            retries: 3,
+        };
+        let outcomes = run_batch(&["X1:A"], &segments[..1], options, |_| Err::<(), _>(()));
+        assert_eq!(outcomes[0].attempts, 4);
+    }
+
+    #[test]
+    fn test_run_batch_bounds_parallelism() {
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let segments: Vec<_> = (0..12)
+            .map(|k| Segment::new(k as f64, k as f64 + 1.0))
+            .collect();
+        let options = BatchOptions {
+            max_parallel: 3,
+            retries: 0,
+        };
+        let outcomes = run_batch(&["X1:A"], &segments, options, |_| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+            Ok::<_, ()>(())
+        });
+        assert_eq!(outcomes.len(), 12);
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+    }
+}
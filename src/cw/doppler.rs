@@ -0,0 +1,225 @@
+use crate::detector::geometry::{Detector, SPEED_OF_LIGHT, greenwich_mean_sidereal_time};
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use astronomy::time::Time;
+use astronomy::units::{HERTZ, Quantity, QuantityError, SECOND};
+use ndarray::Array1;
+
+// Astronomical unit in metres
+const ASTRONOMICAL_UNIT: f64 = 1.495_978_707e11;
+
+// Sidereal rotation rate of the Earth in rad/s
+const EARTH_ROTATION_RATE: f64 = 7.292_115_0e-5;
+
+// Half-width of the central difference used to differentiate the orbit, in days
+const ORBIT_DIFFERENCE_STEP: f64 = 0.5;
+
+// Days since J2000.0 (TT is approximated by GPS + 51.184 s)
+fn days_since_j2000(time: Time) -> f64 {
+    // The GPS epoch is Julian date 2444244.5 and TT - GPS = 51.184 s
+    let julian_date = 2444244.5 + (time.as_gps_seconds_f64() + 51.184) / 86400.0;
+    julian_date - 2451545.0
+}
+
+// Heliocentric position of the Earth in equatorial coordinates (metres), using the
+// low-precision solar coordinates of the Astronomical Almanac.
+fn earth_position(days: f64) -> [f64; 3] {
+    let mean_longitude = (280.460 + 0.985_647_4 * days).to_radians();
+    let mean_anomaly = (357.528 + 0.985_600_3 * days).to_radians();
+    let ecliptic_longitude = mean_longitude
+        + 1.915_f64.to_radians() * mean_anomaly.sin()
+        + 0.020_f64.to_radians() * (2.0 * mean_anomaly).sin();
+    let distance = 1.000_14 - 0.016_71 * mean_anomaly.cos() - 0.000_14 * (2.0 * mean_anomaly).cos();
+    let obliquity = (23.439 - 0.000_000_4 * days).to_radians();
+
+    // The Sun as seen from the Earth, mirrored to give the Earth as seen from the Sun
+    let x = -distance * ecliptic_longitude.cos() * ASTRONOMICAL_UNIT;
+    let y = -distance * ecliptic_longitude.sin() * ASTRONOMICAL_UNIT;
+    [x, y * obliquity.cos(), y * obliquity.sin()]
+}
+
+/// Returns the orbital velocity of the Earth around the Sun in equatorial
+/// coordinates, in m/s.
+pub fn earth_orbital_velocity(time: Time) -> [f64; 3] {
+    let days = days_since_j2000(time);
+    let ahead = earth_position(days + ORBIT_DIFFERENCE_STEP);
+    let behind = earth_position(days - ORBIT_DIFFERENCE_STEP);
+    let interval = 2.0 * ORBIT_DIFFERENCE_STEP * 86400.0;
+    [
+        (ahead[0] - behind[0]) / interval,
+        (ahead[1] - behind[1]) / interval,
+        (ahead[2] - behind[2]) / interval,
+    ]
+}
+
+/// Returns the position of the detector vertex relative to the Sun in equatorial
+/// coordinates, in metres.
+pub fn detector_position(detector: &Detector, time: Time) -> [f64; 3] {
+    let inertial = inertial_vertex(detector, time);
+    let orbital = earth_position(days_since_j2000(time));
+    [
+        orbital[0] + inertial[0],
+        orbital[1] + inertial[1],
+        orbital[2] + inertial[2],
+    ]
+}
+
+/// Returns the Roemer delay, in seconds, to be added to a detector arrival time to obtain
+/// the arrival time at the Sun for a source at right ascension `ra` and declination `dec`.
+pub fn roemer_delay(detector: &Detector, ra: f64, dec: f64, time: Time) -> f64 {
+    let position = detector_position(detector, time);
+    let source = [dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin()];
+    let projection: f64 = position.iter().zip(source.iter()).map(|(r, n)| r * n).sum();
+    projection / SPEED_OF_LIGHT
+}
+
+// Detector vertex rotated from the Earth-fixed frame into the inertial equatorial frame
+fn inertial_vertex(detector: &Detector, time: Time) -> [f64; 3] {
+    let (sin_gmst, cos_gmst) = greenwich_mean_sidereal_time(time).sin_cos();
+    let [x, y, z] = detector.vertex;
+    [x * cos_gmst - y * sin_gmst, x * sin_gmst + y * cos_gmst, z]
+}
+
+/// Returns the velocity of the detector vertex relative to the solar system in
+/// equatorial coordinates, in m/s, combining the orbital and rotational motion of the Earth.
+pub fn detector_velocity(detector: &Detector, time: Time) -> [f64; 3] {
+    let inertial = inertial_vertex(detector, time);
+    let orbital = earth_orbital_velocity(time);
+    [
+        orbital[0] - EARTH_ROTATION_RATE * inertial[1],
+        orbital[1] + EARTH_ROTATION_RATE * inertial[0],
+        orbital[2],
+    ]
+}
+
+/// Computes the Doppler-shifted frequency seen by a detector for a continuous-wave
+/// source of constant intrinsic `frequency` at right ascension `ra` and declination `dec`
+/// (radians).
+///
+/// The result is a `TimeSeriesBase` in Hz with `length` samples starting at `t0` and spaced
+/// by `dt`, following `f(t) = f0 (1 + v(t)·n / c)` where `v` is the detector velocity and `n`
+/// the unit vector towards the source. The solar motion around the barycentre is neglected,
+/// which limits the accuracy to a few parts in 10⁷ of the frequency.
+pub fn doppler_modulated_frequency(
+    detector: &Detector,
+    ra: f64,
+    dec: f64,
+    frequency: &Quantity,
+    t0: Time,
+    dt: &Quantity,
+    length: usize,
+) -> Result<TimeSeriesBase, QuantityError> {
+    if frequency.value.len() != 1 || dt.value.len() != 1 {
+        return Err(QuantityError::InvalidQuantity(
+            "Frequency and dt must be scalar quantities.".to_string(),
+        ));
+    }
+    let f0 = frequency.to(&HERTZ)?.value[0];
+    let step = dt.to(&SECOND)?.value[0];
+    let source = [dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin()];
+    let start = t0.as_gps_seconds_f64();
+
+    let values = Array1::from_shape_fn(length, |i| {
+        let time = Time::from_gps_seconds(start + i as f64 * step);
+        let velocity = detector_velocity(detector, time);
+        let projection: f64 = velocity.iter().zip(source.iter()).map(|(v, n)| v * n).sum();
+        f0 * (1.0 + projection / SPEED_OF_LIGHT)
+    });
+
+    TimeSeriesBaseBuilder::new()
+        .value(values)
+        .unit(HERTZ)
+        .epoch(t0)
+        .dt(dt.clone())
+        .name(format!("{} Doppler frequency", detector.get_name()))
+        .build()
+}
+
+// --- Tests for Doppler modulation ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    fn norm(v: [f64; 3]) -> f64 {
+        (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+    }
+
+    #[test]
+    fn test_earth_orbital_speed() {
+        // Orbital speed ranges between about 29.3 km/s (aphelion) and 30.3 km/s (perihelion)
+        for day in 0..12 {
+            let time = Time::from_gps_seconds(1.2e9 + day as f64 * 30.0 * 86400.0);
+            let speed = norm(earth_orbital_velocity(time));
+            assert!((29_200.0..30_400.0).contains(&speed), "speed = {speed}");
+        }
+    }
+
+    #[test]
+    fn test_detector_rotational_speed() {
+        let detector = Detector::from_prefix("H1").unwrap();
+        let time = Time::from_gps_seconds(1.2e9);
+        let orbital = earth_orbital_velocity(time);
+        let total = detector_velocity(&detector, time);
+        let rotational = norm([
+            total[0] - orbital[0],
+            total[1] - orbital[1],
+            total[2] - orbital[2],
+        ]);
+        // Hanford sits at a latitude of about 46.5 degrees
+        assert!(
+            (rotational - 320.0).abs() < 10.0,
+            "rotational = {rotational}"
+        );
+    }
+
+    #[test]
+    fn test_roemer_delay_bounded_by_light_travel_time() {
+        let detector = Detector::from_prefix("V1").unwrap();
+        for day in 0..12 {
+            let time = Time::from_gps_seconds(1.2e9 + day as f64 * 30.0 * 86400.0);
+            // One astronomical unit corresponds to about 499 light seconds
+            assert!(roemer_delay(&detector, 2.0, -0.4, time).abs() < 510.0);
+        }
+    }
+
+    #[test]
+    fn test_doppler_modulated_frequency() {
+        let detector = Detector::from_prefix("L1").unwrap();
+        let frequency = Quantity::new(array![100.0], HERTZ);
+        let dt = Quantity::new(array![3600.0], SECOND);
+        let t0 = Time::from_gps_seconds(1.2e9);
+
+        let ts = doppler_modulated_frequency(&detector, 1.0, 0.3, &frequency, t0, &dt, 48).unwrap();
+
+        assert_eq!(ts.value().len(), 48);
+        assert_eq!(ts.unit(), &HERTZ);
+        assert_eq!(ts.get_epoch(), Some(t0));
+        // The fractional shift can never exceed (v_orbit + v_rotation) / c
+        assert!(
+            ts.value()
+                .iter()
+                .all(|f| ((f - 100.0) / 100.0).abs() < 1.03e-4)
+        );
+        // Daily rotation makes the frequency vary within two days
+        let max = ts.value().iter().cloned().fold(f64::MIN, f64::max);
+        let min = ts.value().iter().cloned().fold(f64::MAX, f64::min);
+        assert!(max > min);
+    }
+
+    #[test]
+    fn test_doppler_rejects_non_scalar_frequency() {
+        let detector = Detector::from_prefix("H1").unwrap();
+        let frequency = Quantity::new(array![100.0, 200.0], HERTZ);
+        let dt = Quantity::new(array![1.0], SECOND);
+        let result = doppler_modulated_frequency(
+            &detector,
+            0.0,
+            0.0,
+            &frequency,
+            Time::from_gps_seconds(0.0),
+            &dt,
+            4,
+        );
+        assert!(result.is_err());
+    }
+}
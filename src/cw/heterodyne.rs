@@ -0,0 +1,199 @@
+use crate::cw::doppler::roemer_delay;
+use crate::detector::geometry::{Detector, DetectorError};
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use astronomy::time::Time;
+use astronomy::units::{Quantity, QuantityError, SECOND};
+use ndarray::{Array1, array};
+use std::f64::consts::PI;
+use thiserror::Error;
+
+/// Sample interval of the heterodyned `B_k` series, in seconds.
+pub const BK_SAMPLE_INTERVAL: f64 = 60.0;
+
+// Largest departure from a whole number of input samples per B_k bin, in samples
+const BIN_TOLERANCE: f64 = 1e-6;
+
+/// Errors that can occur while heterodyning continuous-wave data.
+#[derive(Debug, Error)]
+pub enum HeterodyneError {
+    #[error("Missing metadata: {0}")]
+    MissingMetadata(String),
+    #[error("Detector error: {0}")]
+    DetectorError(#[from] DetectorError),
+    #[error("Invalid quantity error: {0}")]
+    QuantityError(#[from] QuantityError),
+}
+
+/// Phase-evolution parameters of a continuous-wave source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceParameters {
+    // Right ascension and declination in radians
+    pub ra: f64,
+    pub dec: f64,
+
+    // Gravitational-wave frequency at the reference epoch, in Hz
+    pub frequency: f64,
+
+    // First frequency derivative (spin-down), in Hz/s
+    pub frequency_derivative: f64,
+
+    // Solar system arrival time at which `frequency` is defined
+    pub reference_epoch: Time,
+}
+
+/// Heterodynes a continuous-wave data stream into the standard complex `B_k` series.
+///
+/// Each sample is multiplied by `exp(-iφ(t))`, where `φ` is the source phase evaluated at
+/// the Roemer-delayed solar arrival time including spin-down, and the result is low-passed
+/// and decimated by averaging over consecutive `BK_SAMPLE_INTERVAL` bins. Incomplete trailing
+/// bins are discarded. The detector is taken from the channel name prefix (e.g. "H1:...").
+/// The sample interval must divide `BK_SAMPLE_INTERVAL`, so that every bin spans exactly
+/// the interval its timestamp claims.
+///
+/// Returns `B_k` as a complex `TimeSeriesBase` sampled every `BK_SAMPLE_INTERVAL` seconds,
+/// with the unit and channel of the input.
+pub fn heterodyne(
+    ts: &TimeSeriesBase,
+    source: &SourceParameters,
+) -> Result<TimeSeriesBase, HeterodyneError> {
+    let channel = ts.get_channel().ok_or_else(|| {
+        HeterodyneError::MissingMetadata("A channel is required to identify the detector".into())
+    })?;
+    let prefix = channel.get_name().split(':').next().unwrap_or_default();
+    let detector = Detector::from_prefix(prefix)?;
+
+    let t0 = ts
+        .get_t0()
+        .ok_or_else(|| HeterodyneError::MissingMetadata("t0 is required".into()))?
+        .to(&SECOND)?
+        .value[0];
+    let dt = ts
+        .get_dt()
+        .ok_or_else(|| HeterodyneError::MissingMetadata("dt is required".into()))?
+        .to(&SECOND)?
+        .value[0];
+    let bin_samples = BK_SAMPLE_INTERVAL / dt;
+    if bin_samples < 1.0 - BIN_TOLERANCE
+        || (bin_samples - bin_samples.round()).abs() > BIN_TOLERANCE
+    {
+        return Err(QuantityError::InvalidQuantity(format!(
+            "Sample interval ({dt} s) must divide the B_k interval ({BK_SAMPLE_INTERVAL} s)"
+        ))
+        .into());
+    }
+    let samples_per_bin = bin_samples.round() as usize;
+
+    let reference = source.reference_epoch.as_gps_seconds_f64();
+    let values = ts.value();
+    let n_bins = values.len() / samples_per_bin;
+    let mut real = Array1::zeros(n_bins);
+    let mut imag = Array1::zeros(n_bins);
+    for (i, &x) in values.iter().take(n_bins * samples_per_bin).enumerate() {
+        let gps = t0 + i as f64 * dt;
+        let delay = roemer_delay(
+            &detector,
+            source.ra,
+            source.dec,
+            Time::from_gps_seconds(gps),
+        );
+        let tau = gps + delay - reference;
+        // Keep only the fractional cycles to preserve precision over long baselines
+        let cycles = source.frequency * tau + 0.5 * source.frequency_derivative * tau * tau;
+        let phase = 2.0 * PI * cycles.rem_euclid(1.0);
+        real[i / samples_per_bin] += x * phase.cos();
+        imag[i / samples_per_bin] -= x * phase.sin();
+    }
+    real /= samples_per_bin as f64;
+    imag /= samples_per_bin as f64;
+
+    let mut builder = TimeSeriesBaseBuilder::new()
+        .value(real)
+        .imag(imag)
+        .unit(ts.unit().clone())
+        .source_channel(channel.clone())
+        .t0(t0)
+        .dt(Quantity::new(array![BK_SAMPLE_INTERVAL], SECOND));
+    if let Some(name) = ts.get_name() {
+        builder = builder.name(format!("{name} B_k"));
+    }
+    Ok(builder.build()?)
+}
+
+// --- Tests for the heterodyne pipeline ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::channel::Channel;
+
+    fn source() -> SourceParameters {
+        SourceParameters {
+            ra: 1.2,
+            dec: 0.4,
+            frequency: 10.0,
+            frequency_derivative: -1e-9,
+            reference_epoch: Time::from_gps_seconds(1.2e9),
+        }
+    }
+
+    fn signal(channel: &str, amplitude: f64, duration: f64, rate: f64) -> TimeSeriesBase {
+        let detector = Detector::from_prefix("H1").unwrap();
+        let params = source();
+        let t0 = 1.2e9 + 1000.0;
+        let n = (duration * rate) as usize;
+        let values = Array1::from_shape_fn(n, |i| {
+            let gps = t0 + i as f64 / rate;
+            let delay = roemer_delay(
+                &detector,
+                params.ra,
+                params.dec,
+                Time::from_gps_seconds(gps),
+            );
+            let tau = gps + delay - 1.2e9;
+            let cycles = params.frequency * tau + 0.5 * params.frequency_derivative * tau * tau;
+            amplitude * (2.0 * PI * cycles.rem_euclid(1.0) + 0.3).cos()
+        });
+        TimeSeriesBaseBuilder::new()
+            .value(values)
+            .t0(t0)
+            .dt(Quantity::new(array![1.0 / rate], SECOND))
+            .name("Strain")
+            .channel(Channel::new(channel, Some(rate), None, None, None, None, None).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_heterodyne_recovers_amplitude_and_phase() {
+        let ts = signal("H1:STRAIN", 2.0, 300.0, 64.0);
+        let bk = heterodyne(&ts, &source()).unwrap();
+
+        assert_eq!(bk.value().len(), 5);
+        assert!(bk.is_complex());
+        assert_eq!(bk.get_dt().unwrap().value[0], BK_SAMPLE_INTERVAL);
+        assert_eq!(bk.get_name(), Some("Strain B_k"));
+        // B_k = (A / 2) exp(i 0.3) for a signal A cos(φ + 0.3)
+        for (re, im) in bk.value().iter().zip(bk.imag().unwrap().iter()) {
+            assert!((re - 0.3_f64.cos()).abs() < 1e-2, "re = {re}");
+            assert!((im - 0.3_f64.sin()).abs() < 1e-2, "im = {im}");
+        }
+    }
+
+    #[test]
+    fn test_heterodyne_requires_known_detector() {
+        let ts = signal("X9:STRAIN", 1.0, 60.0, 16.0);
+        assert!(matches!(
+            heterodyne(&ts, &source()),
+            Err(HeterodyneError::DetectorError(_))
+        ));
+    }
+
+    #[test]
+    fn test_heterodyne_requires_whole_samples_per_bin() {
+        // 60 s is 85.7 samples at 0.7 s, so bins would drift off the minute grid
+        let ts = signal("H1:STRAIN", 1.0, 300.0, 1.0 / 0.7);
+        assert!(matches!(
+            heterodyne(&ts, &source()),
+            Err(HeterodyneError::QuantityError(_))
+        ));
+    }
+}
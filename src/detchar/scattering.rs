@@ -0,0 +1,233 @@
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use crate::types::spectrogram::Spectrogram;
+use astronomy::units::{HERTZ, METRE, Quantity, QuantityError, SECOND};
+use ndarray::{Array1, array};
+
+/// Wavelength of the main interferometer laser (Nd:YAG), in metres.
+pub const LASER_WAVELENGTH: f64 = 1.064e-6;
+
+/// A witness channel ranked by how well its predicted fringes match the strain spectrogram.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScatteringCandidate {
+    // Index of the witness in the list that was ranked
+    pub witness: usize,
+    pub name: Option<String>,
+    pub harmonic: u32,
+    // Mean power along the predicted fringe track relative to the median power in each
+    // frequency bin; about one when the track and the excess power are unrelated
+    pub score: f64,
+}
+
+// -- Scattered-light fringe prediction for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Predicts the frequency of scattered-light fringes from the displacement of a
+    /// scattering surface, `f = 2 n |dx/dt| / wavelength` for the `harmonic` n.
+    ///
+    /// These data must be a displacement, convertible to metres; the velocity is taken by
+    /// central differences. The result is a series in Hz on the same time axis.
+    pub fn fringe_frequency(
+        &self,
+        harmonic: u32,
+        wavelength: &Quantity,
+    ) -> Result<TimeSeriesBase, QuantityError> {
+        if wavelength.value.len() != 1 || wavelength.value[0] <= 0.0 {
+            return Err(QuantityError::InvalidQuantity(
+                "wavelength must be a positive scalar quantity.".to_string(),
+            ));
+        }
+        let wavelength = wavelength.to(&METRE)?.value[0];
+        let metres = Quantity::new(array![1.0], self.unit().clone())
+            .to(&METRE)?
+            .value[0];
+        let dt = self.dt_seconds()?;
+        let x = self.value();
+        let n = x.len();
+        if n < 2 {
+            return Err(QuantityError::InvalidQuantity(
+                "At least two samples are needed to estimate a velocity".to_string(),
+            ));
+        }
+        let frequency = Array1::from_shape_fn(n, |i| {
+            let (before, after) = (i.saturating_sub(1), (i + 1).min(n - 1));
+            let velocity = metres * (x[after] - x[before]) / ((after - before) as f64 * dt);
+            2.0 * harmonic as f64 * velocity.abs() / wavelength
+        });
+        let mut builder = TimeSeriesBaseBuilder::new()
+            .value(frequency)
+            .unit(HERTZ)
+            .dt(Quantity::new(array![dt], SECOND));
+        if let Some(t0) = self.get_t0() {
+            builder = builder.t0(t0.to(&SECOND)?.value[0]);
+        }
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        builder.build()
+    }
+}
+
+/// Scores how strongly the excess power in `spectrogram` follows a predicted `fringe` track.
+///
+/// Each spectrogram row is normalised by the median of its frequency bin over time, and the
+/// relative power is read at the highest fringe frequency predicted during the row, where
+/// scattering shelves and arches end. The score is the mean over rows whose fringe
+/// frequency falls inside the spectrogram band, or `None` when there are no such rows.
+pub fn score_fringe_track(
+    spectrogram: &Spectrogram,
+    fringe: &TimeSeriesBase,
+) -> Result<Option<f64>, QuantityError> {
+    let times = spectrogram.get_times().to(&SECOND)?.value;
+    let frequencies = spectrogram.get_frequencies().to(&HERTZ)?.value;
+    let power = spectrogram.value();
+    if times.is_empty() || frequencies.len() < 2 {
+        return Ok(None);
+    }
+    let df = frequencies[1] - frequencies[0];
+    let fringe_times = fringe
+        .get_times()
+        .ok_or_else(|| QuantityError::InvalidQuantity("The fringe track needs times".to_string()))?
+        .to(&SECOND)?
+        .value;
+    let stride = match times.len() {
+        1 => f64::INFINITY,
+        _ => times[1] - times[0],
+    };
+
+    let medians: Vec<f64> = power
+        .columns()
+        .into_iter()
+        .map(|c| median(c.to_vec()))
+        .collect();
+    let mut total = 0.0;
+    let mut rows = 0;
+    for (row, &start) in times.iter().enumerate() {
+        let peak = fringe_times
+            .iter()
+            .zip(fringe.value())
+            .filter(|&(&t, _)| start <= t && t < start + stride)
+            .map(|(_, &f)| f)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let bin = ((peak - frequencies[0]) / df).round();
+        if !peak.is_finite() || bin < 0.0 || bin as usize >= frequencies.len() {
+            continue;
+        }
+        let bin = bin as usize;
+        if medians[bin] > 0.0 {
+            total += power[[row, bin]] / medians[bin];
+            rows += 1;
+        }
+    }
+    Ok((rows > 0).then(|| total / rows as f64))
+}
+
+/// Ranks scattering surfaces by predicting fringes from each displacement `witness` at
+/// harmonics `1..=harmonics` and scoring them against the strain `spectrogram` with
+/// [`score_fringe_track`], most significant first.
+pub fn rank_scattering_surfaces(
+    spectrogram: &Spectrogram,
+    witnesses: &[&TimeSeriesBase],
+    harmonics: u32,
+    wavelength: &Quantity,
+) -> Result<Vec<ScatteringCandidate>, QuantityError> {
+    let mut candidates = Vec::new();
+    for (index, witness) in witnesses.iter().enumerate() {
+        for harmonic in 1..=harmonics {
+            let fringe = witness.fringe_frequency(harmonic, wavelength)?;
+            if let Some(score) = score_fringe_track(spectrogram, &fringe)? {
+                candidates.push(ScatteringCandidate {
+                    witness: index,
+                    name: witness.get_name().map(str::to_string),
+                    harmonic,
+                    score,
+                });
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    Ok(candidates)
+}
+
+// Median of a non-empty set of values
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        0.5 * (values[mid - 1] + values[mid])
+    } else {
+        values[mid]
+    }
+}
+
+// --- Tests for scattering analysis ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::spectrogram::SpectrogramBuilder;
+    use ndarray::Array2;
+    use std::f64::consts::PI;
+
+    fn displacement(amplitude: f64, frequency: f64, name: &str) -> TimeSeriesBase {
+        TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(1024, |i| {
+                amplitude * (2.0 * PI * frequency * i as f64 / 64.0).sin()
+            }))
+            .unit(METRE)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / 64.0], SECOND))
+            .name(name)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fringe_frequency() {
+        // Peak velocity 2 pi A f gives a peak fringe frequency 4 pi A f / wavelength
+        let ts = displacement(1e-6, 0.25, "X1:SUS");
+        let wavelength = Quantity::new(array![LASER_WAVELENGTH], METRE);
+        let fringe = ts.fringe_frequency(1, &wavelength).unwrap();
+        let peak = fringe.value().fold(0.0_f64, |m, &f| m.max(f));
+        let expected = 4.0 * PI * 1e-6 * 0.25 / LASER_WAVELENGTH;
+        assert!((peak - expected).abs() / expected < 1e-3);
+        assert_eq!(fringe.unit(), &HERTZ);
+        let second = ts.fringe_frequency(2, &wavelength).unwrap();
+        assert!((second.value()[10] - 2.0 * fringe.value()[10]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rank_scattering_surfaces() {
+        let wavelength = Quantity::new(array![LASER_WAVELENGTH], METRE);
+        let culprit = displacement(2e-5, 0.1, "X1:CULPRIT");
+        let bystander = displacement(2e-6, 0.37, "X1:BYSTANDER");
+
+        // Strain spectrogram with unit background plus excess at the culprit's fringe peaks
+        let times = Array1::from_shape_fn(16, |k| k as f64);
+        let frequencies = Array1::from_shape_fn(65, |k| k as f64);
+        let fringe = culprit.fringe_frequency(1, &wavelength).unwrap();
+        let mut power = Array2::<f64>::ones((16, 65));
+        for k in 0..16 {
+            let peak = fringe
+                .value()
+                .slice(ndarray::s![k * 64..(k + 1) * 64])
+                .fold(0.0_f64, |m, &f| m.max(f));
+            power[[k, peak.round() as usize]] = 20.0;
+        }
+        let spectrogram = SpectrogramBuilder::new()
+            .value(power)
+            .times(Quantity::new(times, SECOND))
+            .frequencies(Quantity::new(frequencies, HERTZ))
+            .build()
+            .unwrap();
+
+        let ranked =
+            rank_scattering_surfaces(&spectrogram, &[&bystander, &culprit], 2, &wavelength)
+                .unwrap();
+        assert_eq!(ranked[0].witness, 1);
+        assert_eq!(ranked[0].name.as_deref(), Some("X1:CULPRIT"));
+        assert_eq!(ranked[0].harmonic, 1);
+        assert!(ranked[0].score > 5.0);
+        assert!(ranked.iter().skip(1).all(|c| c.score < ranked[0].score));
+    }
+}
@@ -0,0 +1,538 @@
+use crate::detector::channel::Channel;
+use crate::types::provenance::Provenance;
+use crate::types::series::{DecibelScale, Series, SeriesBuilder};
+use astronomy::time::Time;
+use astronomy::units::{HERTZ, Quantity, QuantityError, Unit};
+use ndarray::{Array1, array};
+use std::mem::size_of;
+use std::ops::{Add, Div, Mul, Sub};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrequencySeries {
+    // FrequencySeries re-interprets the x-axis of a Series as frequency,
+    // with `f0`, `df` and `frequencies` mapping to `x0`, `dx` and `xindex`.
+    // Complex spectra (e.g. FFT output) carry the imaginary part in the Series;
+    // real-valued spectra such as PSDs have none.
+    series_data: Series,
+    // Whether the data are a density per square-root hertz, in `unit/Hz^(1/2)`, as for an
+    // ASD; `astronomy` units have integer exponents and cannot carry that factor themselves
+    per_root_hertz: bool,
+    // How the spectrum was derived, for products computed by this crate
+    provenance: Option<Provenance>,
+}
+
+/// Builder for FrequencySeries
+///
+/// This builder maps the frequency-domain arguments (`f0`, `df`, `frequencies`)
+/// onto the underlying `SeriesBuilder`'s `x0`, `dx` and `xindex` fields.
+pub struct FrequencySeriesBuilder {
+    value: Option<Array1<f64>>,
+    imag: Option<Array1<f64>>,
+    unit: Option<Unit>,
+    name: Option<String>,
+    epoch: Option<Time>,
+    channel: Option<Channel>,
+    // Whether the unit of the channel defaults and cross-checks that of the data
+    check_channel_unit: bool,
+    // Frequency-domain specific fields
+    f0: Option<Quantity>,          // frequency of the first sample
+    df: Option<Quantity>,          // frequency spacing between samples
+    frequencies: Option<Quantity>, // explicit array of frequencies
+    per_root_hertz: bool,
+    provenance: Option<Provenance>,
+}
+
+impl FrequencySeriesBuilder {
+    pub fn new() -> Self {
+        FrequencySeriesBuilder {
+            value: None,
+            imag: None,
+            unit: None,
+            name: None,
+            epoch: None,
+            channel: None,
+            check_channel_unit: true,
+            f0: None,
+            df: None,
+            frequencies: None,
+            per_root_hertz: false,
+            provenance: None,
+        }
+    }
+
+    // Builder setters for the FrequencySeriesBuilder
+    pub fn value(mut self, value: Array1<f64>) -> Self {
+        self.value = Some(value);
+        self
+    }
+    /// Sets the imaginary part of the data, making this a complex spectrum.
+    pub fn imag(mut self, imag: Array1<f64>) -> Self {
+        self.imag = Some(imag);
+        self
+    }
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+    /// Sets the GPS epoch of the data this spectrum was computed from.
+    pub fn epoch(mut self, epoch: Time) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+    /// Sets the channel the data were recorded on. Without an explicit unit the series takes
+    /// the unit of the channel; with one, the two must agree.
+    pub fn channel(mut self, channel: Channel) -> Self {
+        self.channel = Some(channel);
+        self.check_channel_unit = true;
+        self
+    }
+    // Sets the channel that derived data were computed from, leaving their unit independent
+    pub(crate) fn source_channel(mut self, channel: Channel) -> Self {
+        self.channel = Some(channel);
+        self.check_channel_unit = false;
+        self
+    }
+    /// Sets the frequency of the first sample (f0) as a `Quantity`.
+    pub fn f0(mut self, f0: Quantity) -> Self {
+        self.f0 = Some(f0);
+        self
+    }
+    /// Sets the frequency spacing between samples (df) as a `Quantity`.
+    pub fn df(mut self, df: Quantity) -> Self {
+        self.df = Some(df);
+        self
+    }
+    /// Sets the complete array of frequencies accompanying the data as a `Quantity`.
+    pub fn frequencies(mut self, frequencies: Quantity) -> Self {
+        self.frequencies = Some(frequencies);
+        self
+    }
+    /// Marks the data as a density per square-root hertz, in `unit/Hz^(1/2)` for the unit
+    /// set with [`unit`](Self::unit), as for an amplitude spectral density.
+    pub fn per_root_hertz(mut self) -> Self {
+        self.per_root_hertz = true;
+        self
+    }
+    /// Sets the record of how this spectrum was derived.
+    pub fn provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+    /// Builds the `FrequencySeries` instance from the builder.
+    pub fn build(self) -> Result<FrequencySeries, QuantityError> {
+        let value = self.value.ok_or_else(|| {
+            QuantityError::InvalidQuantity("Value is required to build FrequencySeries".to_string())
+        })?;
+        let mut series_builder = SeriesBuilder::new().value(value);
+        if let Some(imag) = self.imag {
+            series_builder = series_builder.imag(imag);
+        }
+        if let Some(unit) = self.unit {
+            series_builder = series_builder.unit(unit);
+        }
+        if let Some(name) = self.name {
+            series_builder = series_builder.name(name);
+        }
+        if let Some(epoch) = self.epoch {
+            series_builder = series_builder.epoch(epoch);
+        }
+        if let Some(channel) = self.channel {
+            series_builder = if self.check_channel_unit {
+                series_builder.channel(channel)
+            } else {
+                series_builder.source_channel(channel)
+            };
+        }
+        if let Some(frequencies) = self.frequencies {
+            series_builder = series_builder.xindex(frequencies);
+        } else {
+            if let Some(f0) = self.f0 {
+                series_builder = series_builder.x0(f0);
+            }
+            if let Some(df) = self.df {
+                series_builder = series_builder.dx(df);
+            }
+        }
+        let series_data = series_builder.build()?;
+        let mut frequency_series = FrequencySeries::new_internal(series_data);
+        frequency_series.per_root_hertz = self.per_root_hertz;
+        frequency_series.provenance = self.provenance;
+        Ok(frequency_series)
+    }
+}
+
+impl Default for FrequencySeriesBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Private constructor for FrequencySeries
+/// This constructor is used internally by the builder to create a FrequencySeries instance.
+impl FrequencySeries {
+    fn new_internal(series_data: Series) -> Self {
+        FrequencySeries {
+            series_data,
+            per_root_hertz: false,
+            provenance: None,
+        }
+    }
+
+    /// Returns the data, or its real part for a complex spectrum.
+    pub fn value(&self) -> &Array1<f64> {
+        self.series_data.value()
+    }
+    /// Returns the imaginary part of the data, if this is a complex spectrum.
+    pub fn imag(&self) -> Option<&Array1<f64>> {
+        self.series_data.imag()
+    }
+    /// Returns true if this spectrum carries an imaginary part.
+    pub fn is_complex(&self) -> bool {
+        self.series_data.is_complex()
+    }
+    /// Returns the magnitude of each sample (the absolute value for a real spectrum).
+    pub fn abs(&self) -> Array1<f64> {
+        self.series_data.abs().value().clone()
+    }
+    /// Returns the phase of each sample in radians, in `(-pi, pi]`; a real spectrum has
+    /// phase 0 or pi.
+    pub fn phase(&self) -> Array1<f64> {
+        self.series_data.phase().value().clone()
+    }
+    /// Returns the magnitude in decibels relative to `reference`, as a real spectrum labelled
+    /// `dB` on the same frequencies; see [`Series::to_db`].
+    ///
+    /// Use [`DecibelScale::Power`] for PSDs and [`DecibelScale::Amplitude`] for ASDs and
+    /// transfer functions.
+    pub fn to_db(
+        &self,
+        reference: &Quantity,
+        scale: DecibelScale,
+    ) -> Result<FrequencySeries, QuantityError> {
+        let magnitude = self.series_data.with_values(self.abs());
+        let mut decibels = FrequencySeries::new_internal(magnitude.to_db(reference, scale)?);
+        decibels.provenance = self
+            .provenance
+            .clone()
+            .map(|provenance| provenance.step("to_db"));
+        Ok(decibels)
+    }
+    /// Returns the approximate memory footprint in bytes, including the imaginary part.
+    pub fn mem_size(&self) -> usize {
+        self.series_data.mem_size() + size_of::<Self>() - size_of::<Series>()
+    }
+    /// Returns the unit of the data, which a density per square-root hertz is further
+    /// divided by `Hz^(1/2)`; see [`is_per_root_hertz`](Self::is_per_root_hertz).
+    pub fn unit(&self) -> &Unit {
+        self.series_data.unit()
+    }
+    /// Returns true if the data are a density per square-root hertz, such as an ASD, in
+    /// [`unit`](Self::unit)`/Hz^(1/2)`.
+    pub fn is_per_root_hertz(&self) -> bool {
+        self.per_root_hertz
+    }
+    /// Returns the unit of the squared data: the square of [`unit`](Self::unit), divided by
+    /// hertz for a density per square-root hertz, so that an ASD gives the unit of its PSD.
+    pub fn power_unit(&self) -> Result<Unit, QuantityError> {
+        let one = Quantity::new(array![1.0], self.unit().clone());
+        let power = one.clone() * one;
+        if self.per_root_hertz {
+            Ok((power / Quantity::new(array![1.0], HERTZ))?.unit)
+        } else {
+            Ok(power.unit)
+        }
+    }
+    pub fn get_name(&self) -> Option<&str> {
+        self.series_data.get_name()
+    }
+    pub fn get_epoch(&self) -> Option<Time> {
+        self.series_data.get_epoch()
+    }
+    pub fn get_channel(&self) -> Option<&Channel> {
+        self.series_data.get_channel()
+    }
+    pub fn get_f0(&self) -> Option<&Quantity> {
+        self.series_data.get_x0()
+    }
+    pub fn get_df(&self) -> Option<&Quantity> {
+        self.series_data.get_dx()
+    }
+    pub fn get_frequencies(&self) -> Option<&Quantity> {
+        self.series_data.get_xindex()
+    }
+    /// Returns the record of how this spectrum was derived, if one is attached.
+    pub fn get_provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+    /// Attaches a record of how this spectrum was derived, replacing any previous one.
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+}
+
+// -- Arithmetic operations for FrequencySeries
+//
+// Complex spectra combine as complex numbers, with a real operand taken to have zero
+// imaginary part; the axis and metadata follow the rules of the underlying Series.
+// Densities per square-root hertz add only to each other; a product of two of them is a
+// density per hertz, and a quotient by one would need a factor `Hz^(1/2)` and is an error.
+
+impl FrequencySeries {
+    // The result of an operation on the underlying Series, `per_root_hertz` or not
+    fn from_operation(series_data: Series, per_root_hertz: bool) -> Self {
+        let mut result = FrequencySeries::new_internal(series_data);
+        result.per_root_hertz = per_root_hertz;
+        result
+    }
+
+    // Checks that two operands of a sum or difference are both densities per square-root
+    // hertz or both not
+    fn check_same_density(&self, rhs: &Self) -> Result<(), QuantityError> {
+        if self.per_root_hertz != rhs.per_root_hertz {
+            return Err(QuantityError::MismatchError(
+                "Cannot combine a density per square-root hertz with other data".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Add for FrequencySeries {
+    type Output = Result<Self, QuantityError>;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.check_same_density(&rhs)?;
+        let per_root_hertz = self.per_root_hertz;
+        let result_series = (self.series_data + rhs.series_data)?;
+        Ok(FrequencySeries::from_operation(
+            result_series,
+            per_root_hertz,
+        ))
+    }
+}
+
+impl Div for FrequencySeries {
+    type Output = Result<Self, QuantityError>;
+    fn div(self, rhs: Self) -> Self::Output {
+        if rhs.per_root_hertz && !self.per_root_hertz {
+            return Err(QuantityError::InvalidQuantity(
+                "Dividing by a density per square-root hertz needs a unit in Hz^(1/2)".to_string(),
+            ));
+        }
+        let per_root_hertz = self.per_root_hertz && !rhs.per_root_hertz;
+        let result_series = (self.series_data / rhs.series_data)?;
+        Ok(FrequencySeries::from_operation(
+            result_series,
+            per_root_hertz,
+        ))
+    }
+}
+
+impl Mul for FrequencySeries {
+    type Output = Result<Self, QuantityError>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let both = self.per_root_hertz && rhs.per_root_hertz;
+        let per_root_hertz = self.per_root_hertz != rhs.per_root_hertz;
+        let mut result_series = (self.series_data * rhs.series_data)?;
+        if both {
+            let hertz = SeriesBuilder::new()
+                .value(Array1::ones(result_series.value().len()))
+                .unit(HERTZ)
+                .build()?;
+            result_series = (result_series / hertz)?;
+        }
+        Ok(FrequencySeries::from_operation(
+            result_series,
+            per_root_hertz,
+        ))
+    }
+}
+
+impl Sub for FrequencySeries {
+    type Output = Result<Self, QuantityError>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.check_same_density(&rhs)?;
+        let per_root_hertz = self.per_root_hertz;
+        let result_series = (self.series_data - rhs.series_data)?;
+        Ok(FrequencySeries::from_operation(
+            result_series,
+            per_root_hertz,
+        ))
+    }
+}
+
+// --- Test Module for FrequencySeries ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use astronomy::units::{METRE, SECOND, UnitProduct};
+
+    #[test]
+    fn test_frequencyseries_creation_f0_df() {
+        let fs = FrequencySeriesBuilder::new()
+            .value(array![1.0, 2.0, 3.0])
+            .unit(METRE)
+            .f0(Quantity::new(array![10.0], HERTZ))
+            .df(Quantity::new(array![0.5], HERTZ))
+            .name("Spectrum")
+            .build()
+            .unwrap();
+
+        assert_eq!(fs.value(), &array![1.0, 2.0, 3.0]);
+        assert_eq!(fs.unit(), &METRE);
+        assert_eq!(fs.get_name(), Some("Spectrum"));
+        assert_eq!(fs.get_f0().unwrap().value, array![10.0]);
+        assert_eq!(fs.get_df().unwrap().value, array![0.5]);
+        assert_eq!(
+            fs.get_frequencies().unwrap().value,
+            array![10.0, 10.5, 11.0]
+        );
+        assert_eq!(fs.get_frequencies().unwrap().unit, HERTZ);
+        assert!(!fs.is_complex());
+    }
+
+    #[test]
+    fn test_frequencyseries_complex_magnitude() {
+        let fs = FrequencySeriesBuilder::new()
+            .value(array![3.0, -1.0])
+            .imag(array![4.0, 0.0])
+            .build()
+            .unwrap();
+
+        assert!(fs.is_complex());
+        assert_eq!(fs.imag(), Some(&array![4.0, 0.0]));
+        assert_eq!(fs.abs(), array![5.0, 1.0]);
+        assert_eq!(fs.phase(), array![4.0_f64.atan2(3.0), std::f64::consts::PI]);
+    }
+
+    #[test]
+    fn test_frequencyseries_to_db() {
+        let fs = FrequencySeriesBuilder::new()
+            .value(array![3.0, 0.0, 0.01])
+            .imag(array![4.0, -10.0, 0.0])
+            .unit(METRE)
+            .f0(Quantity::new(array![1.0], HERTZ))
+            .df(Quantity::new(array![1.0], HERTZ))
+            .build()
+            .unwrap();
+        let reference = Quantity::new(array![0.5], METRE);
+
+        let amplitude = fs.to_db(&reference, DecibelScale::Amplitude).unwrap();
+        assert!(!amplitude.is_complex());
+        assert_eq!(amplitude.unit().name, "dB");
+        assert_eq!(amplitude.get_f0(), fs.get_f0());
+        let expected = [20.0, 26.0206, -33.9794];
+        for (a, b) in amplitude.value().iter().zip(expected) {
+            assert!((a - b).abs() < 1e-4, "{a} {b}");
+        }
+        let power = fs.to_db(&reference, DecibelScale::Power).unwrap();
+        assert!((power.value()[0] - 10.0).abs() < 1e-12);
+        assert!(
+            fs.to_db(&Quantity::new(array![1.0], HERTZ), DecibelScale::Power)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_frequencyseries_arithmetic() {
+        let spectrum = |real: Array1<f64>, imag: Option<Array1<f64>>, unit: Unit| {
+            let mut builder = FrequencySeriesBuilder::new()
+                .value(real)
+                .unit(unit)
+                .f0(Quantity::new(array![0.0], HERTZ))
+                .df(Quantity::new(array![0.5], HERTZ))
+                .name("H");
+            if let Some(imag) = imag {
+                builder = builder.imag(imag);
+            }
+            builder.build().unwrap()
+        };
+        let dimensionless = Unit::new("", 1.0, UnitProduct::zero());
+        // (1 + 2i)(3 - i) = 5 + 5i and (2 + 0i)(3 - i) = 6 - 2i
+        let response = spectrum(array![1.0, 2.0], Some(array![2.0, 0.0]), dimensionless);
+        let data = spectrum(array![3.0, 3.0], Some(array![-1.0, -1.0]), METRE);
+        let product = (response.clone() * data.clone()).unwrap();
+        assert_eq!(product.value(), &array![5.0, 6.0]);
+        assert_eq!(product.imag(), Some(&array![5.0, -2.0]));
+        assert_eq!(product.unit().dimensions, METRE.dimensions);
+        assert_eq!(product.get_df(), data.get_df());
+        assert_eq!(product.get_name(), Some("H"));
+
+        // Division undoes the product
+        let quotient = (product / data.clone()).unwrap();
+        for (x, y) in quotient.abs().iter().zip(response.abs().iter()) {
+            assert!((x - y).abs() < 1e-12);
+        }
+        let difference = (data.clone() - data.clone()).unwrap();
+        assert!(difference.abs().iter().all(|x| *x == 0.0));
+
+        // Real spectra stay real, and a real operand has no imaginary part
+        let psd = spectrum(array![4.0, 9.0], None, METRE);
+        let ratio = (psd.clone() / psd.clone()).unwrap();
+        assert!(!ratio.is_complex());
+        assert_eq!(ratio.value(), &array![1.0, 1.0]);
+        let sum = (psd.clone() + data).unwrap();
+        assert_eq!(sum.value(), &array![7.0, 12.0]);
+        assert_eq!(sum.imag(), Some(&array![-1.0, -1.0]));
+        let duration = spectrum(array![1.0, 2.0], None, SECOND);
+        assert!((psd + duration).is_err());
+    }
+
+    #[test]
+    fn test_frequencyseries_per_root_hertz() {
+        let spectrum = |values: Array1<f64>, per_root_hertz: bool| {
+            let builder = FrequencySeriesBuilder::new()
+                .value(values)
+                .unit(METRE)
+                .f0(Quantity::new(array![0.0], HERTZ))
+                .df(Quantity::new(array![0.5], HERTZ));
+            if per_root_hertz {
+                builder.per_root_hertz().build().unwrap()
+            } else {
+                builder.build().unwrap()
+            }
+        };
+        let asd = spectrum(array![2.0, 3.0], true);
+        let area_per_hertz = (Quantity::new(array![1.0], METRE)
+            * Quantity::new(array![1.0], METRE)
+            / Quantity::new(array![1.0], HERTZ))
+        .unwrap()
+        .unit;
+        assert!(asd.is_per_root_hertz());
+        assert_eq!(
+            asd.power_unit().unwrap().dimensions,
+            area_per_hertz.dimensions
+        );
+
+        // The square of an ASD is a PSD, in m²/Hz
+        let psd = (asd.clone() * asd.clone()).unwrap();
+        assert!(!psd.is_per_root_hertz());
+        assert_eq!(psd.value(), &array![4.0, 9.0]);
+        assert_eq!(psd.unit().dimensions, area_per_hertz.dimensions);
+        let sum = (asd.clone() + asd.clone()).unwrap();
+        assert!(sum.is_per_root_hertz());
+        let ratio = (asd.clone() / asd.clone()).unwrap();
+        assert!(!ratio.is_per_root_hertz());
+
+        // Amplitudes per root hertz do not mix with plain amplitudes
+        let amplitude = spectrum(array![1.0, 1.0], false);
+        assert!(matches!(
+            asd.clone() + amplitude.clone(),
+            Err(QuantityError::MismatchError(_))
+        ));
+        assert!((amplitude / asd).is_err());
+    }
+
+    #[test]
+    fn test_frequencyseries_imag_length_mismatch() {
+        let result = FrequencySeriesBuilder::new()
+            .value(array![1.0, 2.0])
+            .imag(array![1.0])
+            .build();
+        assert!(matches!(result, Err(QuantityError::MismatchError(_))));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::frequencyseries::core::FrequencySeries;
+use astronomy::units::{HERTZ, Quantity, QuantityError};
+
+// Number of bins in the running median that estimates the background under a line
+const BACKGROUND_BINS: usize = 65;
+
+/// A narrow line found by [`find_lines`](FrequencySeries::find_lines).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectralLine {
+    // Frequency of the highest bin and full width at half the height, in hertz
+    pub frequency: f64,
+    pub width: f64,
+
+    // Peak value above the local background, in the unit of the spectrum
+    pub height: f64,
+}
+
+// -- Line identification for FrequencySeries
+impl FrequencySeries {
+    /// Finds the narrow lines that rise above the local background, e.g. of an ASD.
+    ///
+    /// The background at each bin is the median magnitude over the surrounding 65 bins,
+    /// truncated at the ends of the spectrum, so it is insensitive to lines much narrower
+    /// than that. Every contiguous run of bins that reaches `threshold` times the background
+    /// and exceeds it by at least `min_prominence`, in the unit of the spectrum, is one line,
+    /// reported at its highest bin. The width is measured where the excess over the
+    /// background falls to half its peak, interpolating between bins, so it is limited by
+    /// the frequency resolution. Lines are returned in frequency order.
+    pub fn find_lines(
+        &self,
+        threshold: f64,
+        min_prominence: &Quantity,
+    ) -> Result<Vec<SpectralLine>, QuantityError> {
+        if min_prominence.value.len() != 1 {
+            return Err(QuantityError::InvalidQuantity(
+                "min_prominence must be a scalar quantity.".to_string(),
+            ));
+        }
+        let min_prominence = min_prominence.to(self.unit())?.value[0];
+        let frequencies = self.get_frequencies().ok_or_else(|| {
+            QuantityError::InvalidQuantity(
+                "Cannot find lines in a spectrum without a frequency axis".to_string(),
+            )
+        })?;
+        let frequencies = frequencies.to(&HERTZ)?.value;
+
+        let magnitude = self.abs().to_vec();
+        let n = magnitude.len();
+        let background = running_median(&magnitude, BACKGROUND_BINS / 2);
+        let excess: Vec<f64> = (0..n).map(|k| magnitude[k] - background[k]).collect();
+        let in_line =
+            |k: usize| magnitude[k] >= threshold * background[k] && excess[k] >= min_prominence;
+
+        let mut lines = Vec::new();
+        let mut k = 0;
+        while k < n {
+            if !in_line(k) {
+                k += 1;
+                continue;
+            }
+            let start = k;
+            while k < n && in_line(k) {
+                k += 1;
+            }
+            let peak = (start..k)
+                .max_by(|&a, &b| excess[a].total_cmp(&excess[b]))
+                .unwrap();
+            let half = 0.5 * excess[peak];
+            // Frequency at which the excess falls to half, walking away from the peak
+            let crossing = |bins: &mut dyn Iterator<Item = usize>| {
+                let mut inner = peak;
+                for outer in bins {
+                    if excess[outer] <= half {
+                        let fraction = (excess[inner] - half) / (excess[inner] - excess[outer]);
+                        return frequencies[inner]
+                            + fraction * (frequencies[outer] - frequencies[inner]);
+                    }
+                    inner = outer;
+                }
+                frequencies[inner]
+            };
+            let lower = crossing(&mut (0..peak).rev());
+            let upper = crossing(&mut (peak + 1..n));
+            lines.push(SpectralLine {
+                frequency: frequencies[peak],
+                width: upper - lower,
+                height: excess[peak],
+            });
+        }
+        Ok(lines)
+    }
+}
+
+// Median of `values` over a centred window of `half_width` bins either side, truncated at
+// the ends
+fn running_median(values: &[f64], half_width: usize) -> Vec<f64> {
+    (0..values.len())
+        .map(|k| {
+            let end = (k + half_width + 1).min(values.len());
+            let mut window = values[k.saturating_sub(half_width)..end].to_vec();
+            window.sort_by(f64::total_cmp);
+            let middle = window.len() / 2;
+            if window.len().is_multiple_of(2) {
+                0.5 * (window[middle - 1] + window[middle])
+            } else {
+                window[middle]
+            }
+        })
+        .collect()
+}
+
+// --- Tests for line identification ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frequencyseries::core::FrequencySeriesBuilder;
+    use astronomy::units::{METRE, SECOND};
+    use ndarray::{Array1, array};
+
+    #[test]
+    fn test_find_lines() {
+        // A sloping background with Gaussian lines at 60 Hz and 180 Hz
+        let line = |f: f64, centre: f64, height: f64, sigma: f64| {
+            height * (-(f - centre).powi(2) / (2.0 * sigma * sigma)).exp()
+        };
+        let spectrum = FrequencySeriesBuilder::new()
+            .value(Array1::from_shape_fn(1025, |k| {
+                let f = k as f64 * 0.25;
+                1.0 + f / 100.0 + line(f, 60.0, 10.0, 0.5) + line(f, 180.0, 3.0, 0.25)
+            }))
+            .unit(METRE)
+            .f0(Quantity::new(array![0.0], HERTZ))
+            .df(Quantity::new(array![0.25], HERTZ))
+            .build()
+            .unwrap();
+
+        let lines = spectrum
+            .find_lines(1.5, &Quantity::new(array![1.0], METRE))
+            .unwrap();
+        assert_eq!(lines.len(), 2, "{lines:?}");
+        assert_eq!(lines[0].frequency, 60.0);
+        // The line itself lifts the median slightly on a sloping background
+        assert!((lines[0].height - 10.0).abs() < 0.05, "{:?}", lines[0]);
+        // The full width at half maximum of a Gaussian is 2 sqrt(2 ln 2) sigma
+        let fwhm = 2.0 * (2.0 * 2.0_f64.ln()).sqrt() * 0.5;
+        assert!((lines[0].width - fwhm).abs() < 0.1, "{:?}", lines[0]);
+        assert_eq!(lines[1].frequency, 180.0);
+        assert!((lines[1].height - 3.0).abs() < 0.05, "{:?}", lines[1]);
+
+        // Either criterion alone rejects the weaker line
+        let strong = spectrum
+            .find_lines(3.0, &Quantity::new(array![1.0], METRE))
+            .unwrap();
+        assert_eq!(strong.len(), 1);
+        let prominent = spectrum
+            .find_lines(1.5, &Quantity::new(array![5.0], METRE))
+            .unwrap();
+        assert_eq!(prominent.len(), 1);
+        assert!(
+            spectrum
+                .find_lines(1.5, &Quantity::new(array![1.0], SECOND))
+                .is_err()
+        );
+    }
+}
@@ -1,13 +1,76 @@
 pub mod types {
     pub mod array;
+    pub mod calculus;
+    pub mod lombscargle;
+    pub mod provenance;
     pub mod series;
+    pub mod spectrogram;
 }
 pub mod timeseries {
+    pub mod align;
+    pub mod compact;
+    pub mod convolve;
     pub mod core;
+    pub mod correlation;
+    pub mod cross;
+    pub mod emd;
+    pub mod filter;
+    pub mod filterbank;
+    pub mod inject;
+    pub mod interp;
+    pub mod lines;
+    pub mod map;
+    pub mod peaks;
+    pub mod resample;
+    pub mod rolling;
+    pub mod saturation;
+    pub mod shared;
+    pub mod shift;
+    pub mod spectral;
+    pub mod stack;
+    pub mod taper;
+    #[cfg(test)]
+    pub(crate) mod testing;
+    pub mod timing;
+    pub mod trend;
+    pub mod wavelet;
+}
+pub mod frequencyseries {
+    pub mod core;
+    pub mod lines;
 }
 pub mod detector {
     pub mod channel;
+    pub mod geometry;
 }
 pub mod segments {
     pub mod core;
+    #[cfg(any(test, feature = "testing"))]
+    pub mod testing;
+    pub mod veto;
+}
+pub mod detchar {
+    pub mod scattering;
+}
+pub mod cw {
+    pub mod doppler;
+    pub mod heterodyne;
+}
+pub mod astro {
+    pub mod stochastic;
+}
+pub mod batch {
+    pub mod executor;
+}
+pub mod signal {
+    pub mod fft;
+    pub mod filter;
+    pub mod fusion;
+    pub mod kalman;
+    pub mod linalg;
+    pub mod matchedfilter;
+    pub mod overlapsave;
+    pub mod templatebank;
+    pub mod wiener;
+    pub mod window;
 }
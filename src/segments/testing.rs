@@ -0,0 +1,103 @@
+//! Proptest strategies for segment types.
+//!
+//! Available in this crate's tests and, for downstream crates, behind the `testing` feature.
+use crate::segments::core::Segment;
+use proptest::prelude::*;
+
+// Bounds for generated segment edges, kept small so that overlaps are common
+const EDGE_RANGE: std::ops::Range<i32> = -50..50;
+
+/// Generates segments with integer-valued edges in `[-50, 50)`, including empty segments.
+pub fn segment() -> impl Strategy<Value = Segment> {
+    (EDGE_RANGE, EDGE_RANGE).prop_map(|(a, b)| Segment::new(a as f64, b as f64))
+}
+
+/// Generates non-empty segments with integer-valued edges in `[-50, 50]`.
+pub fn non_empty_segment() -> impl Strategy<Value = Segment> {
+    (EDGE_RANGE, 1..50_i32).prop_map(|(start, length)| {
+        Segment::new(start as f64, start as f64 + length.min(50 - start) as f64)
+    })
+}
+
+// --- Algebraic invariants of segment arithmetic ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn new_orders_edges(a in -1e3..1e3_f64, b in -1e3..1e3_f64) {
+            let s = Segment::new(a, b);
+            prop_assert!(s.start() <= s.end());
+            prop_assert_eq!(s, Segment::new(b, a));
+        }
+
+        #[test]
+        fn union_is_commutative(a in segment(), b in segment()) {
+            prop_assert_eq!(a | b, b | a);
+        }
+
+        #[test]
+        fn intersection_is_commutative(a in segment(), b in segment()) {
+            let (ab, ba) = (a & b, b & a);
+            prop_assert_eq!(ab.is_empty(), ba.is_empty());
+            if !ab.is_empty() {
+                prop_assert_eq!(ab, ba);
+            }
+        }
+
+        #[test]
+        fn union_and_intersection_are_idempotent(a in segment()) {
+            prop_assert_eq!(a | a, a);
+            prop_assert_eq!(a & a, a);
+        }
+
+        #[test]
+        fn union_contains_operands(a in segment(), b in segment()) {
+            let union = a | b;
+            prop_assert!(union.contains(&a));
+            prop_assert!(union.contains(&b));
+        }
+
+        #[test]
+        fn intersection_is_contained_in_operands(
+            a in non_empty_segment(),
+            b in non_empty_segment(),
+        ) {
+            let intersection = a & b;
+            if !intersection.is_empty() {
+                prop_assert!(a.contains(&intersection));
+                prop_assert!(b.contains(&intersection));
+            }
+        }
+
+        #[test]
+        fn intersection_distributes_over_union(
+            a in non_empty_segment(),
+            b in non_empty_segment(),
+            c in non_empty_segment(),
+        ) {
+            // `|` spans any gap between its operands, so the law holds only when both
+            // intersections are non-empty; an empty one still has a position the span reaches
+            let (ab, ac) = (a & b, a & c);
+            if !ab.is_empty() && !ac.is_empty() {
+                prop_assert_eq!(a & (b | c), ab | ac);
+            }
+        }
+
+        #[test]
+        fn difference_is_contained_and_disjoint(
+            a in non_empty_segment(),
+            b in non_empty_segment(),
+        ) {
+            let difference = a - b;
+            prop_assert!(a.contains(&difference));
+            prop_assert!((difference & b).is_empty());
+        }
+
+        #[test]
+        fn difference_with_self_is_empty(a in segment()) {
+            prop_assert!((a - a).is_empty());
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::segments::core::Segment;
+
+/// Figures of merit of a candidate veto, as computed by [`evaluate_veto`].
+///
+/// Only triggers and veto time inside the analysed segments are counted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VetoReport {
+    // Number of analysed triggers, and how many of those fall inside the veto
+    pub triggers: usize,
+    pub vetoed_triggers: usize,
+
+    // Analysed time, and how much of it is vetoed, in seconds
+    pub livetime: f64,
+    pub vetoed_time: f64,
+
+    // Number of distinct veto segments, and how many of those contain a trigger
+    pub veto_segments: usize,
+    pub used_segments: usize,
+}
+
+impl VetoReport {
+    /// Fraction of triggers removed by the veto, or `None` without triggers.
+    pub fn efficiency(&self) -> Option<f64> {
+        ratio(self.vetoed_triggers as f64, self.triggers as f64)
+    }
+    /// Fraction of the analysed time removed by the veto, or `None` without livetime.
+    pub fn deadtime(&self) -> Option<f64> {
+        ratio(self.vetoed_time, self.livetime)
+    }
+    /// Fraction of veto segments containing at least one trigger, or `None` for an empty veto.
+    pub fn use_percentage(&self) -> Option<f64> {
+        ratio(self.used_segments as f64, self.veto_segments as f64)
+    }
+    /// Efficiency divided by deadtime; a random veto scores about one.
+    pub fn efficiency_over_deadtime(&self) -> Option<f64> {
+        ratio(self.efficiency()?, self.deadtime()?)
+    }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> Option<f64> {
+    (denominator > 0.0).then(|| numerator / denominator)
+}
+
+/// Evaluates a candidate veto against a set of triggers.
+///
+/// `veto` holds the active segments of the veto flag, `analysed` the segments over which the
+/// veto is assessed and `triggers` the GPS times of the events it is meant to remove. Both
+/// segment lists may be unsorted and overlapping. A trigger is vetoed when it lies in a
+/// veto segment, using the usual `[start, end)` convention.
+pub fn evaluate_veto(veto: &[Segment], analysed: &[Segment], triggers: &[f64]) -> VetoReport {
+    let analysed = coalesce(analysed);
+    let veto: Vec<Segment> = coalesce(veto)
+        .into_iter()
+        .flat_map(|v| analysed.iter().map(move |a| v & *a))
+        .filter(|s| !s.is_empty())
+        .collect();
+    // Clipping to the analysed segments keeps the pieces disjoint; this only sorts them
+    let veto = coalesce(&veto);
+
+    let in_any =
+        |segments: &[Segment], t: f64| segments.iter().position(|s| s.start <= t && t < s.end);
+    let mut used = vec![false; veto.len()];
+    let mut report = VetoReport {
+        triggers: 0,
+        vetoed_triggers: 0,
+        livetime: analysed.iter().map(duration).sum(),
+        vetoed_time: veto.iter().map(duration).sum(),
+        veto_segments: veto.len(),
+        used_segments: 0,
+    };
+    for &t in triggers {
+        if in_any(&analysed, t).is_none() {
+            continue;
+        }
+        report.triggers += 1;
+        if let Some(index) = in_any(&veto, t) {
+            report.vetoed_triggers += 1;
+            used[index] = true;
+        }
+    }
+    report.used_segments = used.iter().filter(|&&u| u).count();
+    report
+}
+
+fn duration(segment: &Segment) -> f64 {
+    segment.end - segment.start
+}
+
+// Sorts the segments and merges any that overlap or touch, dropping empty ones
+fn coalesce(segments: &[Segment]) -> Vec<Segment> {
+    let mut sorted: Vec<Segment> = segments.iter().filter(|s| !s.is_empty()).copied().collect();
+    sorted.sort_by(|a, b| a.start.total_cmp(&b.start));
+    let mut merged: Vec<Segment> = Vec::with_capacity(sorted.len());
+    for segment in sorted {
+        match merged.last_mut() {
+            Some(last) if segment.start <= last.end => last.end = last.end.max(segment.end),
+            _ => merged.push(segment),
+        }
+    }
+    merged
+}
+
+// --- Tests for veto evaluation ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_veto() {
+        let analysed = [Segment::new(0.0, 100.0), Segment::new(200.0, 300.0)];
+        let veto = [
+            Segment::new(10.0, 20.0),
+            Segment::new(15.0, 25.0),
+            Segment::new(50.0, 55.0),
+            Segment::new(95.0, 205.0),
+        ];
+        let triggers = [12.0, 24.0, 30.0, 60.0, 98.0, 150.0, 250.0, 260.0];
+        let report = evaluate_veto(&veto, &analysed, &triggers);
+
+        // The trigger at 150 s is outside the analysed time
+        assert_eq!(report.triggers, 7);
+        assert_eq!(report.vetoed_triggers, 3);
+        assert_eq!(report.livetime, 200.0);
+        assert_eq!(report.vetoed_time, 15.0 + 5.0 + 5.0 + 5.0);
+        assert_eq!(report.veto_segments, 4);
+        assert_eq!(report.used_segments, 2);
+
+        assert!((report.efficiency().unwrap() - 3.0 / 7.0).abs() < 1e-12);
+        assert!((report.deadtime().unwrap() - 0.15).abs() < 1e-12);
+        assert_eq!(report.use_percentage(), Some(0.5));
+        let ratio = report.efficiency_over_deadtime().unwrap();
+        assert!((ratio - (3.0 / 7.0) / 0.15).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_evaluate_empty_veto() {
+        let report = evaluate_veto(&[], &[Segment::new(0.0, 10.0)], &[1.0]);
+        assert_eq!(report.efficiency(), Some(0.0));
+        assert_eq!(report.deadtime(), Some(0.0));
+        assert_eq!(report.use_percentage(), None);
+        assert_eq!(report.efficiency_over_deadtime(), None);
+        assert_eq!(evaluate_veto(&[], &[], &[]).efficiency(), None);
+    }
+}
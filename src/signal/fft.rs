@@ -0,0 +1,80 @@
+use ndarray::Array1;
+use rustfft::FftPlanner;
+use rustfft::num_complex::Complex64;
+
+/// Computes the discrete Fourier transform of real data, returning the `n / 2 + 1`
+/// non-negative frequency bins (unnormalized, as in `numpy.fft.rfft`).
+pub fn rfft(data: &Array1<f64>) -> Vec<Complex64> {
+    let n = data.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut buffer: Vec<Complex64> = data.iter().map(|&x| Complex64::new(x, 0.0)).collect();
+    FftPlanner::new().plan_fft_forward(n).process(&mut buffer);
+    buffer.truncate(n / 2 + 1);
+    buffer
+}
+
+/// Inverse of `rfft`: reconstructs `n` real samples from the non-negative frequency bins,
+/// including the `1 / n` normalization.
+pub fn irfft(spectrum: &[Complex64], n: usize) -> Array1<f64> {
+    if n == 0 {
+        return Array1::zeros(0);
+    }
+    let mut buffer = vec![Complex64::new(0.0, 0.0); n];
+    for (k, value) in spectrum.iter().take(n / 2 + 1).enumerate() {
+        buffer[k] = *value;
+        // Hermitian symmetry for the negative frequencies
+        if k > 0 && k < n - k {
+            buffer[n - k] = value.conj();
+        }
+    }
+    FftPlanner::new().plan_fft_inverse(n).process(&mut buffer);
+    buffer.iter().map(|value| value.re / n as f64).collect()
+}
+
+/// Computes the inverse discrete Fourier transform of complex data, including the `1 / n`
+/// normalization.
+pub fn ifft(spectrum: &[Complex64]) -> Vec<Complex64> {
+    let n = spectrum.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut buffer = spectrum.to_vec();
+    FftPlanner::new().plan_fft_inverse(n).process(&mut buffer);
+    buffer.iter().map(|value| value / n as f64).collect()
+}
+
+// --- Tests for the FFT helpers ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_rfft_of_cosine() {
+        let n = 16;
+        let data = Array1::from_shape_fn(n, |i| (2.0 * PI * 3.0 * i as f64 / n as f64).cos());
+        let spectrum = rfft(&data);
+        assert_eq!(spectrum.len(), 9);
+        for (k, value) in spectrum.iter().enumerate() {
+            let expected = if k == 3 { 8.0 } else { 0.0 };
+            assert!((value.re - expected).abs() < 1e-12);
+            assert!(value.im.abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_irfft_round_trip() {
+        for data in [
+            array![1.0, -2.0, 3.5, 0.25, 7.0],
+            array![1.0, 2.0, 3.0, 4.0],
+        ] {
+            let restored = irfft(&rfft(&data), data.len());
+            for (a, b) in data.iter().zip(restored.iter()) {
+                assert!((a - b).abs() < 1e-12);
+            }
+        }
+    }
+}
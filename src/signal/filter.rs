@@ -0,0 +1,667 @@
+use crate::signal::window::Window;
+use astronomy::units::QuantityError;
+use ndarray::Array1;
+use rustfft::num_complex::Complex64;
+use std::f64::consts::PI;
+use thiserror::Error;
+
+// Imaginary parts below this are treated as zero when pairing roots into sections
+const REAL_TOLERANCE: f64 = 1e-10;
+
+// Relative imaginary part below which numerically found polynomial roots are taken as real
+const ROOT_REAL_TOLERANCE: f64 = 1e-8;
+
+// Iteration limit and relative convergence threshold of the polynomial root finder
+const ROOT_MAX_ITERATIONS: usize = 1000;
+const ROOT_CONVERGENCE: f64 = 1e-15;
+
+/// Errors that can occur while designing or applying digital filters.
+#[derive(Debug, Error)]
+pub enum FilterError {
+    #[error("Invalid filter design: {0}")]
+    InvalidDesign(String),
+    #[error("Invalid quantity error: {0}")]
+    QuantityError(#[from] QuantityError),
+}
+
+/// A digital filter that can be applied to data through its second-order-section form.
+pub trait DigitalFilter {
+    /// Returns the filter as a cascade of second-order sections.
+    fn to_sos(&self) -> Sos;
+}
+
+/// A digital IIR filter as a cascade of second-order sections.
+///
+/// Each section is stored as `[b0, b1, b2, a0, a1, a2]`, the `scipy.signal` convention, with
+/// `a0 = 1`. Cascading biquads keeps high-order filters numerically stable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sos {
+    sections: Vec<[f64; 6]>,
+}
+
+impl Sos {
+    /// Creates a filter from sections `[b0, b1, b2, a0, a1, a2]`, normalizing each so that
+    /// `a0 = 1`.
+    pub fn new(sections: Vec<[f64; 6]>) -> Result<Sos, FilterError> {
+        let sections = sections
+            .into_iter()
+            .map(|s| {
+                if s[3] == 0.0 {
+                    return Err(FilterError::InvalidDesign(
+                        "The leading denominator coefficient a0 of a section must be non-zero"
+                            .to_string(),
+                    ));
+                }
+                Ok(s.map(|c| c / s[3]))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Sos { sections })
+    }
+
+    /// Converts transfer-function coefficients `b` and `a` (in powers of `z^-1`, as in
+    /// `scipy.signal.lfilter`) into second-order sections, see [`Zpk::from_coefficients`].
+    pub fn from_coefficients(b: &[f64], a: &[f64]) -> Result<Sos, FilterError> {
+        Ok(Zpk::from_coefficients(b, a)?.to_sos())
+    }
+
+    /// Returns the second-order sections.
+    pub fn sections(&self) -> &[[f64; 6]] {
+        &self.sections
+    }
+
+    /// Returns the complex frequency response at `frequency`, for a filter designed at
+    /// `sample_rate` (both in Hz).
+    pub fn response(&self, frequency: f64, sample_rate: f64) -> Complex64 {
+        let z_inv = Complex64::from_polar(1.0, -2.0 * PI * frequency / sample_rate);
+        self.sections.iter().fold(Complex64::new(1.0, 0.0), |h, s| {
+            let numerator = s[0] + z_inv * (s[1] + z_inv * s[2]);
+            let denominator = s[3] + z_inv * (s[4] + z_inv * s[5]);
+            h * numerator / denominator
+        })
+    }
+
+    /// Applies the filter causally (a single forward pass), starting from rest.
+    pub fn filter(&self, data: &Array1<f64>) -> Array1<f64> {
+        self.filter_from(data, &vec![[0.0; 2]; self.sections.len()])
+    }
+
+    /// Applies the filter forward and backward, giving zero phase shift and the squared
+    /// magnitude response.
+    ///
+    /// As in `scipy.signal.sosfiltfilt`, the data are extended at both ends by odd reflection
+    /// and each pass starts from the steady state for its first sample, which suppresses
+    /// start-up transients.
+    pub fn filtfilt(&self, data: &Array1<f64>) -> Array1<f64> {
+        let n = data.len();
+        if n == 0 {
+            return Array1::zeros(0);
+        }
+        let padlen = (3 * (2 * self.sections.len() + 1)).min(n - 1);
+        let extended: Array1<f64> = (0..n + 2 * padlen)
+            .map(|i| {
+                if i < padlen {
+                    2.0 * data[0] - data[padlen - i]
+                } else if i < padlen + n {
+                    data[i - padlen]
+                } else {
+                    2.0 * data[n - 1] - data[n - 2 - (i - padlen - n)]
+                }
+            })
+            .collect();
+
+        let steady_state = self.steady_state();
+        let scaled = |x0: f64| -> Vec<[f64; 2]> {
+            steady_state
+                .iter()
+                .map(|zi| [zi[0] * x0, zi[1] * x0])
+                .collect()
+        };
+        let forward = self.filter_from(&extended, &scaled(extended[0]));
+        let reversed: Array1<f64> = forward.iter().rev().cloned().collect();
+        let backward = self.filter_from(&reversed, &scaled(reversed[0]));
+        backward
+            .iter()
+            .rev()
+            .skip(padlen)
+            .take(n)
+            .cloned()
+            .collect()
+    }
+
+    // Direct form II transposed filtering from the given per-section state
+    fn filter_from(&self, data: &Array1<f64>, initial: &[[f64; 2]]) -> Array1<f64> {
+        let mut state = initial.to_vec();
+        data.mapv(|x| {
+            let mut y = x;
+            for (s, z) in self.sections.iter().zip(state.iter_mut()) {
+                let input = y;
+                y = s[0] * input + z[0];
+                z[0] = s[1] * input - s[4] * y + z[1];
+                z[1] = s[2] * input - s[5] * y;
+            }
+            y
+        })
+    }
+
+    // Per-section state for a unit step input in steady state (`scipy.signal.sosfilt_zi`)
+    fn steady_state(&self) -> Vec<[f64; 2]> {
+        let mut scale = 1.0;
+        self.sections
+            .iter()
+            .map(|s| {
+                let (b, a) = ([s[0], s[1], s[2]], [s[3], s[4], s[5]]);
+                // Solve [[1 + a1, -1], [a2, 1]] zi = [b1 - a1 b0, b2 - a2 b0]
+                let rhs = [b[1] - a[1] * b[0], b[2] - a[2] * b[0]];
+                let determinant = 1.0 + a[1] + a[2];
+                let z0 = (rhs[0] + rhs[1]) / determinant;
+                let z1 = rhs[1] - a[2] * z0;
+                let zi = [z0 * scale, z1 * scale];
+                scale *= (b[0] + b[1] + b[2]) / (a[0] + a[1] + a[2]);
+                zi
+            })
+            .collect()
+    }
+}
+
+impl DigitalFilter for Sos {
+    fn to_sos(&self) -> Sos {
+        self.clone()
+    }
+}
+
+/// A digital filter in zero-pole-gain form, `H(z) = k Π(z - zᵢ) / Π(z - pᵢ)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Zpk {
+    zeros: Vec<Complex64>,
+    poles: Vec<Complex64>,
+    gain: f64,
+}
+
+impl Zpk {
+    /// Creates a filter from its zeros, poles and gain. Complex zeros and poles must come in
+    /// conjugate pairs so that the filter has real coefficients.
+    pub fn new(
+        zeros: Vec<Complex64>,
+        poles: Vec<Complex64>,
+        gain: f64,
+    ) -> Result<Zpk, FilterError> {
+        for (roots, kind) in [(&zeros, "zeros"), (&poles, "poles")] {
+            let upper = roots.iter().filter(|r| r.im > REAL_TOLERANCE).count();
+            let lower = roots.iter().filter(|r| r.im < -REAL_TOLERANCE).count();
+            let paired = roots.iter().filter(|r| r.im > REAL_TOLERANCE).all(|r| {
+                roots
+                    .iter()
+                    .any(|other| (other - r.conj()).norm() <= REAL_TOLERANCE * (1.0 + r.norm()))
+            });
+            if upper != lower || !paired {
+                return Err(FilterError::InvalidDesign(format!(
+                    "Complex {kind} must come in conjugate pairs"
+                )));
+            }
+        }
+        if zeros.len() > poles.len() {
+            return Err(FilterError::InvalidDesign(format!(
+                "A causal filter cannot have more zeros ({}) than poles ({})",
+                zeros.len(),
+                poles.len()
+            )));
+        }
+        Ok(Zpk { zeros, poles, gain })
+    }
+
+    /// Converts transfer-function coefficients `b` and `a`, in powers of `z^-1` as in
+    /// `scipy.signal.lfilter`, into zero-pole-gain form by finding the polynomial roots.
+    ///
+    /// The transfer-function form is ill-conditioned for high orders, so filters should be
+    /// designed directly as zeros and poles or sections where possible.
+    pub fn from_coefficients(b: &[f64], a: &[f64]) -> Result<Zpk, FilterError> {
+        let delay = b.iter().take_while(|&&c| c == 0.0).count();
+        let b = &b[delay.min(b.len())..];
+        if b.is_empty() {
+            return Err(FilterError::InvalidDesign(
+                "The numerator must have a non-zero coefficient".to_string(),
+            ));
+        }
+        if a.first().is_none_or(|&a0| a0 == 0.0) {
+            return Err(FilterError::InvalidDesign(
+                "The leading denominator coefficient a0 must be non-zero".to_string(),
+            ));
+        }
+        let mut zeros = polynomial_roots(b);
+        let mut poles = polynomial_roots(a);
+        // Multiplying through by z^max(...) leaves the surplus order as roots at the origin
+        let excess = a.len() as i64 - (b.len() + delay) as i64;
+        let origin = vec![Complex64::new(0.0, 0.0); excess.unsigned_abs() as usize];
+        if excess > 0 {
+            zeros.extend(origin);
+        } else {
+            poles.extend(origin);
+        }
+        Ok(Zpk {
+            zeros,
+            poles,
+            gain: b[0] / a[0],
+        })
+    }
+
+    pub fn zeros(&self) -> &[Complex64] {
+        &self.zeros
+    }
+    pub fn poles(&self) -> &[Complex64] {
+        &self.poles
+    }
+    pub fn gain(&self) -> f64 {
+        self.gain
+    }
+}
+
+impl DigitalFilter for Zpk {
+    fn to_sos(&self) -> Sos {
+        zpk_to_sos(&self.zeros, &self.poles, self.gain)
+    }
+}
+
+// Roots of the polynomial with real `coefficients` (highest power first, leading coefficient
+// non-zero) by Durand-Kerner iteration, symmetrized into exact conjugate pairs
+fn polynomial_roots(coefficients: &[f64]) -> Vec<Complex64> {
+    let degree = coefficients.len().saturating_sub(1);
+    if degree == 0 {
+        return Vec::new();
+    }
+    let monic: Vec<f64> = coefficients.iter().map(|c| c / coefficients[0]).collect();
+    let evaluate = |x: Complex64| {
+        monic
+            .iter()
+            .fold(Complex64::new(0.0, 0.0), |acc, &c| acc * x + c)
+    };
+    let radius = 1.0 + monic[1..].iter().fold(0.0_f64, |m, c| m.max(c.abs()));
+    let seed = Complex64::from_polar(1.0, 0.4);
+    let mut roots: Vec<Complex64> = (0..degree).map(|k| seed.powu(k as u32) * radius).collect();
+    for _ in 0..ROOT_MAX_ITERATIONS {
+        let mut largest_step: f64 = 0.0;
+        for i in 0..degree {
+            let denominator: Complex64 = (0..degree)
+                .filter(|&j| j != i)
+                .map(|j| roots[i] - roots[j])
+                .product();
+            let step = evaluate(roots[i]) / denominator;
+            roots[i] -= step;
+            largest_step = largest_step.max(step.norm() / (1.0 + roots[i].norm()));
+        }
+        if largest_step < ROOT_CONVERGENCE {
+            break;
+        }
+    }
+
+    let mut symmetric = Vec::with_capacity(degree);
+    while let Some(root) = roots.pop() {
+        if root.im.abs() <= ROOT_REAL_TOLERANCE * (1.0 + root.norm()) || roots.is_empty() {
+            symmetric.push(Complex64::new(root.re, 0.0));
+            continue;
+        }
+        let partner = (0..roots.len())
+            .min_by(|&i, &j| {
+                (roots[i] - root.conj())
+                    .norm()
+                    .total_cmp(&(roots[j] - root.conj()).norm())
+            })
+            .unwrap_or_default();
+        let mean = (root + roots.swap_remove(partner).conj()) / 2.0;
+        symmetric.push(mean);
+        symmetric.push(mean.conj());
+    }
+    symmetric
+}
+
+/// Designs a digital Butterworth band-pass filter of the given `order` passing `flow` to
+/// `fhigh` (Hz) for data sampled at `sample_rate` (Hz).
+///
+/// The analog prototype is transformed with pre-warped band edges and the bilinear
+/// transform, matching `scipy.signal.butter(order, [flow, fhigh], "bandpass", fs=...)`. The
+/// resulting filter has `order` second-order sections and is -3 dB at both band edges.
+pub fn butterworth_bandpass(
+    order: usize,
+    flow: f64,
+    fhigh: f64,
+    sample_rate: f64,
+) -> Result<Sos, FilterError> {
+    if order == 0 {
+        return Err(FilterError::InvalidDesign(
+            "Filter order must be at least 1".to_string(),
+        ));
+    }
+    let nyquist = sample_rate / 2.0;
+    if !(flow > 0.0 && flow < fhigh && fhigh < nyquist) {
+        return Err(FilterError::InvalidDesign(format!(
+            "Band edges must satisfy 0 < flow ({flow} Hz) < fhigh ({fhigh} Hz) < Nyquist \
+             ({nyquist} Hz)"
+        )));
+    }
+    let warp = |f: f64| 2.0 * sample_rate * (PI * f / sample_rate).tan();
+    let (low, high) = (warp(flow), warp(fhigh));
+    let bandwidth = high - low;
+    let centre_squared = low * high;
+
+    // Low-pass to band-pass transform of the prototype: every pole p maps to the two roots of
+    // s² - p B s + ω0² = 0 and `order` zeros appear at the origin
+    let mut poles = Vec::with_capacity(2 * order);
+    for p in butterworth_prototype(order) {
+        let half = p * (bandwidth / 2.0);
+        let root = (half * half - centre_squared).sqrt();
+        poles.push(half + root);
+        poles.push(half - root);
+    }
+    let zeros = vec![Complex64::new(0.0, 0.0); order];
+    let gain = bandwidth.powi(order as i32);
+
+    let (zeros, poles, gain) = bilinear(&zeros, &poles, gain, sample_rate);
+    Ok(zpk_to_sos(&zeros, &poles, gain))
+}
+
+/// Designs a digital Butterworth low-pass filter of the given `order` with its -3 dB point
+/// at `frequency` (Hz) for data sampled at `sample_rate` (Hz), matching
+/// `scipy.signal.butter(order, frequency, "lowpass", fs=...)`.
+pub fn butterworth_lowpass(
+    order: usize,
+    frequency: f64,
+    sample_rate: f64,
+) -> Result<Sos, FilterError> {
+    let cutoff = warped_cutoff(order, frequency, sample_rate)?;
+    let poles: Vec<Complex64> = butterworth_prototype(order)
+        .iter()
+        .map(|p| p * cutoff)
+        .collect();
+    let gain = cutoff.powi(order as i32);
+    let (zeros, poles, gain) = bilinear(&[], &poles, gain, sample_rate);
+    Ok(zpk_to_sos(&zeros, &poles, gain))
+}
+
+/// Designs a digital Butterworth high-pass filter of the given `order` with its -3 dB point
+/// at `frequency` (Hz) for data sampled at `sample_rate` (Hz), matching
+/// `scipy.signal.butter(order, frequency, "highpass", fs=...)`.
+pub fn butterworth_highpass(
+    order: usize,
+    frequency: f64,
+    sample_rate: f64,
+) -> Result<Sos, FilterError> {
+    let cutoff = warped_cutoff(order, frequency, sample_rate)?;
+    // Low-pass to high-pass transform s -> ωc / s; the prototype poles multiply to (-1)^N,
+    // so the gain stays 1
+    let poles: Vec<Complex64> = butterworth_prototype(order)
+        .iter()
+        .map(|p| cutoff / p)
+        .collect();
+    let zeros = vec![Complex64::new(0.0, 0.0); order];
+    let (zeros, poles, gain) = bilinear(&zeros, &poles, 1.0, sample_rate);
+    Ok(zpk_to_sos(&zeros, &poles, gain))
+}
+
+/// Designs a linear-phase low-pass FIR filter of `numtaps` coefficients with cutoff
+/// `frequency` (Hz) for data sampled at `sample_rate` (Hz) by the window method, scaled to
+/// unit gain at zero frequency, as `scipy.signal.firwin(numtaps, frequency, window=...,
+/// fs=sample_rate)`.
+pub fn fir_lowpass(
+    numtaps: usize,
+    frequency: f64,
+    sample_rate: f64,
+    window: Window,
+) -> Result<Array1<f64>, FilterError> {
+    if numtaps == 0 {
+        return Err(FilterError::InvalidDesign(
+            "An FIR filter needs at least one tap".to_string(),
+        ));
+    }
+    let nyquist = sample_rate / 2.0;
+    if !(frequency > 0.0 && frequency <= nyquist) {
+        return Err(FilterError::InvalidDesign(format!(
+            "Cutoff frequency ({frequency} Hz) must lie in (0, {nyquist}] Hz"
+        )));
+    }
+    let fraction = frequency / nyquist;
+    let centre = (numtaps - 1) as f64 / 2.0;
+    let taper = window.generate_symmetric(numtaps);
+    let mut taps = Array1::from_shape_fn(numtaps, |i| {
+        let x = PI * fraction * (i as f64 - centre);
+        let sinc = if x == 0.0 { 1.0 } else { x.sin() / x };
+        fraction * sinc * taper[i]
+    });
+    taps /= taps.sum();
+    Ok(taps)
+}
+
+// Validates a single cutoff frequency and returns its pre-warped analog equivalent
+fn warped_cutoff(order: usize, frequency: f64, sample_rate: f64) -> Result<f64, FilterError> {
+    if order == 0 {
+        return Err(FilterError::InvalidDesign(
+            "Filter order must be at least 1".to_string(),
+        ));
+    }
+    let nyquist = sample_rate / 2.0;
+    if !(frequency > 0.0 && frequency < nyquist) {
+        return Err(FilterError::InvalidDesign(format!(
+            "Cutoff frequency ({frequency} Hz) must lie between 0 and the Nyquist frequency \
+             ({nyquist} Hz)"
+        )));
+    }
+    Ok(2.0 * sample_rate * (PI * frequency / sample_rate).tan())
+}
+
+// Poles of the normalized analog Butterworth low-pass prototype, all in the left half-plane
+fn butterworth_prototype(order: usize) -> Vec<Complex64> {
+    (0..order)
+        .map(|k| {
+            let angle = PI * (2 * k + order + 1) as f64 / (2 * order) as f64;
+            Complex64::from_polar(1.0, angle)
+        })
+        .collect()
+}
+
+// Bilinear transform of an analog zero-pole-gain system. Zeros at infinity map to z = -1.
+fn bilinear(
+    zeros: &[Complex64],
+    poles: &[Complex64],
+    gain: f64,
+    sample_rate: f64,
+) -> (Vec<Complex64>, Vec<Complex64>, f64) {
+    let fs2 = 2.0 * sample_rate;
+    let map = |s: &Complex64| (fs2 + s) / (fs2 - s);
+    let mut digital_zeros: Vec<Complex64> = zeros.iter().map(map).collect();
+    digital_zeros.resize(poles.len().max(zeros.len()), Complex64::new(-1.0, 0.0));
+    let digital_poles = poles.iter().map(map).collect();
+    let numerator: Complex64 = zeros.iter().map(|z| fs2 - z).product();
+    let denominator: Complex64 = poles.iter().map(|p| fs2 - p).product();
+    (
+        digital_zeros,
+        digital_poles,
+        gain * (numerator / denominator).re,
+    )
+}
+
+// Groups roots into conjugate (or real) pairs, returning the degree and monic coefficients
+// in z, highest power first, of each factor: [1, -(r1 + r2), r1 r2] for a pair and [1, -r]
+// (padded with a zero) for a leftover real root, which always comes last
+fn pair_roots(roots: &[Complex64]) -> Vec<(usize, [f64; 3])> {
+    let mut complex: Vec<Complex64> = roots
+        .iter()
+        .filter(|r| r.im > REAL_TOLERANCE)
+        .cloned()
+        .collect();
+    complex.sort_by(|a, b| a.re.total_cmp(&b.re));
+    let mut real: Vec<f64> = roots
+        .iter()
+        .filter(|r| r.im.abs() <= REAL_TOLERANCE)
+        .map(|r| r.re)
+        .collect();
+    real.sort_by(f64::total_cmp);
+
+    let mut factors: Vec<(usize, [f64; 3])> = complex
+        .iter()
+        .map(|r| (2, [1.0, -2.0 * r.re, r.norm_sqr()]))
+        .collect();
+    for chunk in real.chunks(2) {
+        match chunk {
+            [r1, r2] => factors.push((2, [1.0, -(r1 + r2), r1 * r2])),
+            [r] => factors.push((1, [1.0, -r, 0.0])),
+            _ => unreachable!(),
+        }
+    }
+    factors
+}
+
+// Converts digital zeros, poles and gain into second-order sections, applying the gain
+// to the first section. There must be no more zeros than poles; any surplus of poles
+// becomes a delay by shifting the numerator of each section within `z^-1` powers.
+fn zpk_to_sos(zeros: &[Complex64], poles: &[Complex64], gain: f64) -> Sos {
+    let numerators = pair_roots(zeros);
+    let denominators = pair_roots(poles);
+    let n_sections = numerators.len().max(denominators.len()).max(1);
+    let sections = (0..n_sections)
+        .map(|i| {
+            let (zero_degree, n) = numerators.get(i).copied().unwrap_or((0, [1.0, 0.0, 0.0]));
+            let (pole_degree, a) = denominators.get(i).copied().unwrap_or((0, [1.0, 0.0, 0.0]));
+            let k = if i == 0 { gain } else { 1.0 };
+            let mut b = [0.0; 3];
+            for (power, coefficient) in n.iter().take(zero_degree + 1).enumerate() {
+                b[power + pole_degree - zero_degree] = k * coefficient;
+            }
+            [b[0], b[1], b[2], a[0], a[1], a[2]]
+        })
+        .collect();
+    Sos { sections }
+}
+
+// --- Tests for filter design and application ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_butterworth_bandpass_response() {
+        let sos = butterworth_bandpass(4, 20.0, 60.0, 256.0).unwrap();
+        assert_eq!(sos.sections().len(), 4);
+        // -3 dB at the band edges, unity in the pass band, strong stop-band rejection
+        let half_power = 0.5_f64.sqrt();
+        assert!((sos.response(20.0, 256.0).norm() - half_power).abs() < 1e-6);
+        assert!((sos.response(60.0, 256.0).norm() - half_power).abs() < 1e-6);
+        assert!((sos.response(35.0, 256.0).norm() - 1.0).abs() < 1e-2);
+        assert!(sos.response(0.0, 256.0).norm() < 1e-10);
+        assert!(sos.response(120.0, 256.0).norm() < 1e-3);
+    }
+
+    #[test]
+    fn test_butterworth_lowpass_and_highpass_response() {
+        let half_power = 0.5_f64.sqrt();
+        for order in [1, 4, 5] {
+            let lowpass = butterworth_lowpass(order, 30.0, 256.0).unwrap();
+            assert_eq!(lowpass.sections().len(), order.div_ceil(2));
+            assert!((lowpass.response(0.0, 256.0).norm() - 1.0).abs() < 1e-10);
+            assert!((lowpass.response(30.0, 256.0).norm() - half_power).abs() < 1e-6);
+            assert!(lowpass.response(128.0, 256.0).norm() < 1e-10);
+
+            let highpass = butterworth_highpass(order, 30.0, 256.0).unwrap();
+            assert!(highpass.response(0.0, 256.0).norm() < 1e-10);
+            assert!((highpass.response(30.0, 256.0).norm() - half_power).abs() < 1e-6);
+            assert!((highpass.response(128.0, 256.0).norm() - 1.0).abs() < 1e-10);
+        }
+    }
+
+    // Expands second-order sections into transfer-function coefficients
+    fn expand(sos: &Sos) -> (Vec<f64>, Vec<f64>) {
+        let multiply = |p: &[f64], q: &[f64]| {
+            let mut product = vec![0.0; p.len() + q.len() - 1];
+            for (i, x) in p.iter().enumerate() {
+                for (j, y) in q.iter().enumerate() {
+                    product[i + j] += x * y;
+                }
+            }
+            product
+        };
+        sos.sections()
+            .iter()
+            .fold((vec![1.0], vec![1.0]), |(b, a), s| {
+                (multiply(&b, &s[..3]), multiply(&a, &s[3..]))
+            })
+    }
+
+    #[test]
+    fn test_coefficients_round_trip_through_zpk() {
+        // The four-fold zero at z = -1 limits the accuracy of the root finder to ~1e-5
+        let designed = butterworth_lowpass(4, 20.0, 256.0).unwrap();
+        let (b, a) = expand(&designed);
+        let converted = Sos::from_coefficients(&b, &a).unwrap();
+        for frequency in [0.0, 10.0, 20.0, 60.0, 127.0] {
+            let expected = designed.response(frequency, 256.0);
+            let actual = converted.response(frequency, 256.0);
+            assert!((expected - actual).norm() < 1e-4, "{frequency} Hz");
+        }
+    }
+
+    #[test]
+    fn test_zpk_from_coefficients_with_delay() {
+        // y[n] = x[n - 1] - 0.5 x[n - 2] + 0.25 y[n - 1]
+        let zpk = Zpk::from_coefficients(&[0.0, 1.0, -0.5], &[1.0, -0.25]).unwrap();
+        assert_eq!(zpk.gain(), 1.0);
+        assert_eq!(zpk.zeros().len(), 1);
+        assert!((zpk.zeros()[0].re - 0.5).abs() < 1e-12);
+        // The pole at 0.25 plus one pole at the origin for the surplus delay
+        let mut poles: Vec<f64> = zpk.poles().iter().map(|p| p.re).collect();
+        poles.sort_by(f64::total_cmp);
+        assert!(poles[0].abs() < 1e-12 && (poles[1] - 0.25).abs() < 1e-12);
+
+        let impulse = Array1::from_shape_fn(4, |i| if i == 0 { 1.0 } else { 0.0 });
+        let response = zpk.to_sos().filter(&impulse);
+        let expected = [0.0, 1.0, -0.25, -0.0625];
+        for (r, e) in response.iter().zip(expected.iter()) {
+            assert!((r - e).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_zpk_requires_conjugate_pairs() {
+        let result = Zpk::new(vec![Complex64::new(0.0, 1.0)], vec![], 1.0);
+        assert!(matches!(result, Err(FilterError::InvalidDesign(_))));
+        assert!(Sos::new(vec![[1.0, 0.0, 0.0, 0.0, 1.0, 0.0]]).is_err());
+    }
+
+    #[test]
+    fn test_fir_lowpass() {
+        let taps = fir_lowpass(101, 16.0, 128.0, Window::Kaiser(5.0)).unwrap();
+        assert_eq!(taps.len(), 101);
+        assert!((taps.sum() - 1.0).abs() < 1e-12);
+        for i in 0..50 {
+            assert!((taps[i] - taps[100 - i]).abs() < 1e-15);
+        }
+        let gain = |f: f64| {
+            taps.iter()
+                .enumerate()
+                .map(|(i, h)| h * Complex64::from_polar(1.0, -2.0 * PI * f * i as f64 / 128.0))
+                .sum::<Complex64>()
+                .norm()
+        };
+        assert!((gain(4.0) - 1.0).abs() < 1e-3);
+        assert!((gain(16.0) - 0.5).abs() < 1e-2);
+        assert!(gain(32.0) < 1e-3);
+        assert!(fir_lowpass(11, 80.0, 128.0, Window::Hann).is_err());
+    }
+
+    #[test]
+    fn test_filtfilt_has_zero_phase() {
+        let sos = butterworth_bandpass(2, 5.0, 20.0, 128.0).unwrap();
+        let data = Array1::from_shape_fn(1024, |i| (2.0 * PI * 10.0 * i as f64 / 128.0).sin());
+        let filtered = sos.filtfilt(&data);
+        let gain = sos.response(10.0, 128.0).norm_sqr();
+        for i in 200..824 {
+            assert!((filtered[i] - gain * data[i]).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_butterworth_bandpass_rejects_invalid_band() {
+        assert!(matches!(
+            butterworth_bandpass(4, 60.0, 20.0, 256.0),
+            Err(FilterError::InvalidDesign(_))
+        ));
+        assert!(butterworth_bandpass(4, 20.0, 128.0, 256.0).is_err());
+        assert!(butterworth_bandpass(0, 20.0, 60.0, 256.0).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::frequencyseries::core::FrequencySeries;
+use crate::signal::fft::{irfft, rfft};
+use crate::signal::filter::FilterError;
+use crate::timeseries::core::TimeSeriesBase;
+use crate::timeseries::spectral::interpolate;
+use astronomy::units::{HERTZ, Quantity, SECOND, Unit, UnitProduct};
+use ndarray::array;
+use rustfft::num_complex::Complex64;
+use std::f64::consts::PI;
+
+// Largest allowed deviation of the summed blend responses from one at any frequency
+const COMPLEMENTARY_TOLERANCE: f64 = 1e-6;
+
+/// Combines colocated sensors into a single super-sensor with complementary blend filters.
+///
+/// Each sensor is paired with the dimensionless complex frequency response of its blend
+/// filter, linearly interpolated onto the FFT frequencies of the data and held at its end
+/// values outside its range. The responses must sum to one at every frequency, so the
+/// blend passes the common motion unchanged while each sensor contributes only in the
+/// band where it is quietest.
+///
+/// The unit of the first sensor sets that of the result. A sensor whose unit is that unit
+/// per second, such as a velocity sensor blended with displacement sensors, is integrated
+/// in the frequency domain, with its 0 Hz content discarded. All sensors must share their
+/// `t0`, `dt` and length, and the filtering is circular over the data. The result carries
+/// the name and channel of the first sensor.
+pub fn blend_sensors(
+    sensors: &[(&TimeSeriesBase, &FrequencySeries)],
+) -> Result<TimeSeriesBase, FilterError> {
+    let Some(&(reference, _)) = sensors.first() else {
+        return Err(FilterError::InvalidDesign(
+            "Blending needs at least one sensor".to_string(),
+        ));
+    };
+    let dt = reference.dt_seconds()?;
+    let n = reference.value().len();
+    for (sensor, _) in sensors {
+        if sensor.value().len() != n
+            || sensor.dt_seconds()? != dt
+            || sensor.get_t0() != reference.get_t0()
+        {
+            return Err(FilterError::InvalidDesign(
+                "Blended sensors must share t0, dt and length".to_string(),
+            ));
+        }
+    }
+
+    let unit = reference.unit();
+    let rate_unit =
+        (Quantity::new(array![1.0], unit.clone()) / Quantity::new(array![1.0], SECOND))?.unit;
+    let dimensionless = Unit::new("", 1.0, UnitProduct::zero());
+    let df = 1.0 / (n as f64 * dt);
+    let mut blended = vec![Complex64::new(0.0, 0.0); n / 2 + 1];
+    let mut total_response = vec![Complex64::new(0.0, 0.0); n / 2 + 1];
+    for (sensor, blend) in sensors {
+        // Scale to the reference unit, noting whether the sensor needs integrating
+        let one = Quantity::new(array![1.0], sensor.unit().clone());
+        let (scale, integrate) = match one.to(unit) {
+            Ok(scaled) => (scaled.value[0], false),
+            Err(error) => match one.to(&rate_unit) {
+                Ok(scaled) => (scaled.value[0], true),
+                Err(_) => return Err(error.into()),
+            },
+        };
+        let frequencies = blend.get_frequencies().ok_or_else(|| {
+            FilterError::InvalidDesign("Blend filters must have a frequency axis".to_string())
+        })?;
+        let frequencies = frequencies.to(&HERTZ)?.value.to_vec();
+        let real = Quantity::new(blend.value().clone(), blend.unit().clone())
+            .to(&dimensionless)?
+            .value
+            .to_vec();
+        let imag = match blend.imag() {
+            Some(imag) => imag.to_vec(),
+            None => vec![0.0; real.len()],
+        };
+
+        let spectrum = rfft(sensor.value());
+        for (k, bin) in spectrum.iter().enumerate() {
+            let f = k as f64 * df;
+            let response = Complex64::new(
+                interpolate(f, &frequencies, &real),
+                interpolate(f, &frequencies, &imag),
+            );
+            total_response[k] += response;
+            let mut contribution = bin * response * scale;
+            if integrate {
+                contribution = if k == 0 {
+                    Complex64::new(0.0, 0.0)
+                } else {
+                    contribution / Complex64::new(0.0, 2.0 * PI * f)
+                };
+            }
+            blended[k] += contribution;
+        }
+    }
+    if let Some((k, total)) = total_response
+        .iter()
+        .enumerate()
+        .find(|(_, total)| (*total - 1.0).norm() > COMPLEMENTARY_TOLERANCE)
+    {
+        return Err(FilterError::InvalidDesign(format!(
+            "Blend filters are not complementary: they sum to {total} at {} Hz",
+            k as f64 * df
+        )));
+    }
+    Ok(reference.with_values(irfft(&blended, n))?)
+}
+
+// --- Tests for sensor fusion ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frequencyseries::core::FrequencySeriesBuilder;
+    use crate::timeseries::testing::{series, series_builder};
+    use astronomy::units::METRE;
+    use ndarray::Array1;
+
+    fn blend(values: Array1<f64>) -> FrequencySeries {
+        FrequencySeriesBuilder::new()
+            .value(values)
+            .f0(Quantity::new(array![0.0], HERTZ))
+            .df(Quantity::new(array![5.0], HERTZ))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_blend_displacement_and_velocity() {
+        let n = 2048;
+        let t = Array1::from_shape_fn(n, |i| i as f64 / 128.0);
+        let tone = |f: f64, a: f64| t.mapv(|t| a * (2.0 * PI * f * t).sin());
+        let rate = |f: f64, a: f64| t.mapv(|t| 2.0 * PI * f * a * (2.0 * PI * f * t).cos());
+        let truth = tone(0.5, 1.0) + tone(20.0, 0.1);
+
+        // The displacement sensor is noisy at high frequency, the velocity sensor drifts
+        let displacement = series(&truth + &tone(30.0, 0.5), 1_000_000_000.0, 1.0 / 128.0);
+        let velocity_unit = (Quantity::new(array![1.0], METRE)
+            / Quantity::new(array![1.0], SECOND))
+        .unwrap()
+        .unit;
+        let velocity = series_builder(
+            rate(0.5, 1.0) + rate(20.0, 0.1) + tone(0.125, 2.0),
+            1_000_000_000.0,
+            1.0 / 128.0,
+        )
+        .unit(velocity_unit)
+        .build()
+        .unwrap();
+        let low_pass = array![1.0, 1.0, 0.0, 0.0, 0.0];
+        let fused = blend_sensors(&[
+            (&displacement, &blend(low_pass.clone())),
+            (&velocity, &blend(1.0 - &low_pass)),
+        ])
+        .unwrap();
+
+        assert_eq!(fused.unit(), &METRE);
+        assert_eq!(fused.get_t0(), displacement.get_t0());
+        for (a, b) in fused.value().iter().zip(truth.iter()) {
+            assert!((a - b).abs() < 1e-9, "{a} {b}");
+        }
+    }
+
+    #[test]
+    fn test_blend_rejects_invalid_inputs() {
+        let data = series(Array1::zeros(64), 1_000_000_000.0, 1.0 / 128.0);
+        let half = blend(array![0.5, 0.5]);
+        assert!(blend_sensors(&[(&data, &half), (&data, &half)]).is_ok());
+        assert!(blend_sensors(&[(&data, &half)]).is_err());
+        assert!(blend_sensors(&[]).is_err());
+
+        let frequency = series_builder(Array1::zeros(64), 1_000_000_000.0, 1.0 / 128.0)
+            .unit(HERTZ)
+            .build()
+            .unwrap();
+        assert!(blend_sensors(&[(&data, &half), (&frequency, &half)]).is_err());
+        let short = series(Array1::zeros(32), 1_000_000_000.0, 1.0 / 128.0);
+        assert!(blend_sensors(&[(&data, &half), (&short, &half)]).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+use crate::signal::filter::FilterError;
+use crate::signal::linalg::solve;
+use crate::timeseries::core::TimeSeriesBase;
+use ndarray::{Array1, Array2, array};
+
+/// A linear Gaussian state-space model for [`filter`](Self::filter) and
+/// [`smooth`](Self::smooth).
+///
+/// From one sample to the next the state evolves as `x[k] = F x[k - 1] + w` and the samples
+/// of the measured channels are `z[k] = H x[k] + v`, with `w` and `v` zero-mean Gaussian
+/// noise of covariance `Q` and `R`. Measuring several channels against one state fuses
+/// them, weighting each by its noise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateSpaceModel {
+    // State transition F (d × d) and observation H (m × d) for d states and m channels
+    pub transition: Array2<f64>,
+    pub observation: Array2<f64>,
+
+    // Process noise Q (d × d) and measurement noise R (m × m) covariances
+    pub process_noise: Array2<f64>,
+    pub measurement_noise: Array2<f64>,
+
+    // Prior mean and covariance of the state at the first sample
+    pub initial_state: Array1<f64>,
+    pub initial_covariance: Array2<f64>,
+}
+
+/// State estimates from a [`StateSpaceModel`], one per sample.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateEstimate {
+    // Estimated state at each sample, one row per sample
+    pub states: Array2<f64>,
+    pub covariances: Vec<Array2<f64>>,
+
+    // The estimate of each measured channel, H x, with the units and timing of that channel
+    pub observations: Vec<TimeSeriesBase>,
+}
+
+impl StateSpaceModel {
+    /// A one-state random walk observed directly by one channel, the usual model for
+    /// tracking a slow drift.
+    ///
+    /// `process_variance` is the expected variance of the drift per sample and
+    /// `measurement_variance` that of the noise on the channel, both in the channel's units
+    /// squared. The prior is centred on zero with `initial_variance`, which should be large
+    /// unless the starting value is known.
+    pub fn random_walk(
+        process_variance: f64,
+        measurement_variance: f64,
+        initial_variance: f64,
+    ) -> Self {
+        StateSpaceModel {
+            transition: array![[1.0]],
+            observation: array![[1.0]],
+            process_noise: array![[process_variance]],
+            measurement_noise: array![[measurement_variance]],
+            initial_state: array![0.0],
+            initial_covariance: array![[initial_variance]],
+        }
+    }
+
+    /// Runs the Kalman filter forward over `measurements`, one series per row of the
+    /// observation matrix, all of the same length.
+    ///
+    /// Each estimate uses only the samples up to its own, so the filter is causal. A sample
+    /// time where any channel is NaN is treated as a gap and only the prediction is made.
+    pub fn filter(&self, measurements: &[&TimeSeriesBase]) -> Result<StateEstimate, FilterError> {
+        let passes = self.forward(measurements)?;
+        self.estimate(measurements, passes.filtered)
+    }
+
+    /// Runs the Kalman filter followed by a Rauch–Tung–Striebel backward pass, so that each
+    /// estimate uses all of `measurements`; see [`filter`](Self::filter).
+    pub fn smooth(&self, measurements: &[&TimeSeriesBase]) -> Result<StateEstimate, FilterError> {
+        let passes = self.forward(measurements)?;
+        let mut smoothed = passes.filtered.clone();
+        for k in (0..smoothed.len().saturating_sub(1)).rev() {
+            let (state, covariance) = &passes.filtered[k];
+            let (predicted_state, predicted_covariance) = &passes.predicted[k + 1];
+            // Smoother gain C = P F^T P_pred^-1, from P_pred C^T = F P
+            let gain = solve_matrix(predicted_covariance, &self.transition.dot(covariance))
+                .ok_or_else(singular)?
+                .reversed_axes();
+            let (next_state, next_covariance) = &smoothed[k + 1];
+            smoothed[k] = (
+                state + &gain.dot(&(next_state - predicted_state)),
+                covariance
+                    + &gain
+                        .dot(&(next_covariance - predicted_covariance))
+                        .dot(&gain.t()),
+            );
+        }
+        self.estimate(measurements, smoothed)
+    }
+
+    // Forward Kalman pass, keeping the predicted and filtered moments at every sample
+    fn forward(&self, measurements: &[&TimeSeriesBase]) -> Result<ForwardPass, FilterError> {
+        self.validate(measurements)?;
+        let n = measurements[0].value().len();
+        let identity = Array2::eye(self.transition.nrows());
+        let mut pass = ForwardPass {
+            predicted: Vec::with_capacity(n),
+            filtered: Vec::with_capacity(n),
+        };
+        let (mut state, mut covariance) =
+            (self.initial_state.clone(), self.initial_covariance.clone());
+        for k in 0..n {
+            if k > 0 {
+                state = self.transition.dot(&state);
+                covariance = self.transition.dot(&covariance).dot(&self.transition.t())
+                    + &self.process_noise;
+            }
+            pass.predicted.push((state.clone(), covariance.clone()));
+
+            let z = Array1::from_iter(measurements.iter().map(|m| m.value()[k]));
+            if z.iter().all(|x| x.is_finite()) {
+                let innovation = &z - &self.observation.dot(&state);
+                let innovation_covariance =
+                    self.observation.dot(&covariance).dot(&self.observation.t())
+                        + &self.measurement_noise;
+                // Kalman gain K = P H^T S^-1, from S K^T = H P
+                let gain = solve_matrix(&innovation_covariance, &self.observation.dot(&covariance))
+                    .ok_or_else(singular)?
+                    .reversed_axes();
+                state = &state + &gain.dot(&innovation);
+                // Joseph form, which stays symmetric and positive definite when a diffuse
+                // prior is collapsed by the first measurements
+                let reduction = &identity - &gain.dot(&self.observation);
+                covariance = reduction.dot(&covariance).dot(&reduction.t())
+                    + gain.dot(&self.measurement_noise).dot(&gain.t());
+            }
+            pass.filtered.push((state.clone(), covariance.clone()));
+        }
+        Ok(pass)
+    }
+
+    fn validate(&self, measurements: &[&TimeSeriesBase]) -> Result<(), FilterError> {
+        let d = self.initial_state.len();
+        let m = self.observation.nrows();
+        let shapes_match = d > 0
+            && self.transition.dim() == (d, d)
+            && self.observation.ncols() == d
+            && self.process_noise.dim() == (d, d)
+            && self.measurement_noise.dim() == (m, m)
+            && self.initial_covariance.dim() == (d, d);
+        if !shapes_match {
+            return Err(FilterError::InvalidDesign(format!(
+                "State-space matrices are inconsistent with {d} states and {m} channels"
+            )));
+        }
+        if measurements.len() != m
+            || measurements
+                .iter()
+                .any(|x| x.value().len() != measurements[0].value().len())
+        {
+            return Err(FilterError::InvalidDesign(format!(
+                "Expected {m} measured channels of equal length, got {}",
+                measurements.len()
+            )));
+        }
+        Ok(())
+    }
+
+    fn estimate(
+        &self,
+        measurements: &[&TimeSeriesBase],
+        moments: Vec<(Array1<f64>, Array2<f64>)>,
+    ) -> Result<StateEstimate, FilterError> {
+        let mut states = Array2::zeros((moments.len(), self.initial_state.len()));
+        for (mut row, (state, _)) in states.rows_mut().into_iter().zip(moments.iter()) {
+            row.assign(state);
+        }
+        let fitted = states.dot(&self.observation.t());
+        let observations = measurements
+            .iter()
+            .zip(fitted.columns())
+            .map(|(measurement, values)| measurement.with_values(values.to_owned()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(StateEstimate {
+            states,
+            covariances: moments
+                .into_iter()
+                .map(|(_, covariance)| covariance)
+                .collect(),
+            observations,
+        })
+    }
+}
+
+// Predicted and filtered (state, covariance) pairs of a forward pass
+struct ForwardPass {
+    predicted: Vec<(Array1<f64>, Array2<f64>)>,
+    filtered: Vec<(Array1<f64>, Array2<f64>)>,
+}
+
+fn singular() -> FilterError {
+    FilterError::InvalidDesign("Singular covariance in the Kalman recursion".to_string())
+}
+
+// Solves `a x = b` for a matrix right-hand side, one column at a time
+fn solve_matrix(a: &Array2<f64>, b: &Array2<f64>) -> Option<Array2<f64>> {
+    let mut x = Array2::zeros(b.dim());
+    for (mut column, rhs) in x.columns_mut().into_iter().zip(b.columns()) {
+        column.assign(&solve(a.clone(), rhs.to_owned())?);
+    }
+    Some(x)
+}
+
+// --- Tests for the Kalman filter ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::testing::series;
+    use astronomy::units::METRE;
+
+    #[test]
+    fn test_constant_level_is_the_mean() {
+        let values = array![1.2, 0.8, 1.1, f64::NAN, 0.9, 1.0, 1.3, 0.7];
+        let data = series(values.clone(), 0.0, 60.0);
+        let model = StateSpaceModel::random_walk(0.0, 0.04, 1e6);
+
+        let smoothed = model.smooth(&[&data]).unwrap();
+        let finite: Vec<f64> = values.iter().copied().filter(|x| x.is_finite()).collect();
+        let mean = finite.iter().sum::<f64>() / finite.len() as f64;
+        for level in smoothed.observations[0].value() {
+            assert!((level - mean).abs() < 1e-6, "{level}");
+        }
+        assert_eq!(smoothed.observations[0].unit(), &METRE);
+        let variance = smoothed.covariances[0][[0, 0]];
+        assert!((variance - 0.04 / 7.0).abs() < 1e-6);
+
+        // The filter only sees the past
+        let filtered = model.filter(&[&data]).unwrap();
+        assert!((filtered.states[[1, 0]] - 1.0).abs() < 1e-6);
+        assert!((filtered.states[[7, 0]] - mean).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fusion_weights_by_noise() {
+        let precise = series(array![2.0, 2.1, 1.9, 2.0], 0.0, 60.0);
+        let noisy = series(array![3.0, 1.0, 2.6, 1.8], 0.0, 60.0);
+        let model = StateSpaceModel {
+            transition: array![[1.0]],
+            observation: array![[1.0], [1.0]],
+            process_noise: array![[0.0]],
+            measurement_noise: array![[0.01, 0.0], [0.0, 0.04]],
+            initial_state: array![0.0],
+            initial_covariance: array![[1e6]],
+        };
+        let fused = model.smooth(&[&precise, &noisy]).unwrap();
+        // Inverse-variance weights of 4/5 and 1/5
+        let expected = (0.8 * 8.0 + 0.2 * 8.4) / 4.0;
+        assert!((fused.states[[0, 0]] - expected).abs() < 1e-6);
+        assert_eq!(fused.observations.len(), 2);
+
+        assert!(model.smooth(&[&precise]).is_err());
+        let bad = StateSpaceModel {
+            transition: array![[1.0, 0.0], [0.0, 1.0]],
+            ..StateSpaceModel::random_walk(0.0, 1.0, 1.0)
+        };
+        assert!(bad.filter(&[&precise]).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+use ndarray::{Array1, Array2};
+
+// Pivots smaller than this fraction of the largest matrix element mark the system as singular
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+
+// Solves the square system `a x = b` by Gaussian elimination with partial pivoting, returning
+// `None` if the matrix is (numerically) singular
+pub(crate) fn solve(mut a: Array2<f64>, mut b: Array1<f64>) -> Option<Array1<f64>> {
+    let n = b.len();
+    debug_assert_eq!(a.dim(), (n, n));
+    let scale = a.iter().fold(0.0_f64, |m, x| m.max(x.abs()));
+    if scale == 0.0 {
+        return None;
+    }
+    for k in 0..n {
+        let pivot = (k..n).max_by(|&i, &j| a[[i, k]].abs().total_cmp(&a[[j, k]].abs()))?;
+        if a[[pivot, k]].abs() <= SINGULAR_TOLERANCE * scale {
+            return None;
+        }
+        if pivot != k {
+            for j in 0..n {
+                a.swap([k, j], [pivot, j]);
+            }
+            b.swap(k, pivot);
+        }
+        for i in k + 1..n {
+            let factor = a[[i, k]] / a[[k, k]];
+            for j in k..n {
+                a[[i, j]] -= factor * a[[k, j]];
+            }
+            b[i] -= factor * b[k];
+        }
+    }
+    let mut x = Array1::<f64>::zeros(n);
+    for k in (0..n).rev() {
+        let tail: f64 = (k + 1..n).map(|j| a[[k, j]] * x[j]).sum();
+        x[k] = (b[k] - tail) / a[[k, k]];
+    }
+    Some(x)
+}
+
+// --- Tests for linear algebra helpers ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_solve() {
+        // Needs a row exchange: the leading element is zero
+        let a = array![[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]];
+        let x = solve(a, array![5.0, 3.0, 6.0]).unwrap();
+        for (found, expected) in x.iter().zip([1.4, 1.6, 1.8]) {
+            assert!((found - expected).abs() < 1e-12);
+        }
+        assert!(solve(array![[1.0, 2.0], [2.0, 4.0]], array![1.0, 2.0]).is_none());
+    }
+}
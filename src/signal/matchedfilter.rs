@@ -0,0 +1,183 @@
+use crate::frequencyseries::core::FrequencySeries;
+use crate::signal::fft::{ifft, rfft};
+use crate::signal::filter::FilterError;
+use crate::timeseries::core::TimeSeriesBase;
+use crate::timeseries::spectral::interpolate;
+use astronomy::units::{HERTZ, Quantity};
+use ndarray::array;
+use rustfft::num_complex::Complex64;
+
+/// Noise-weighted inner product `<a|b> = 4 Re ∫ ã(f) b̃*(f) / S(f) df` of two waveforms over
+/// the band `[flow, fhigh]`.
+///
+/// `a` and `b` must share their `dt` and length; their Fourier transforms are taken over
+/// the whole series, so waveforms should be zero-padded or tapered to their ends. The
+/// one-sided PSD `S` is linearly interpolated onto the FFT frequencies and must be in the
+/// unit of `a` times that of `b` per hertz, which makes the result dimensionless.
+pub fn inner_product(
+    a: &TimeSeriesBase,
+    b: &TimeSeriesBase,
+    psd: &FrequencySeries,
+    flow: &Quantity,
+    fhigh: &Quantity,
+) -> Result<f64, FilterError> {
+    let integrand = weighted_cross_spectrum(a, b, psd, flow, fhigh)?;
+    Ok(integrand.iter().map(|bin| bin.re).sum())
+}
+
+/// Match of two waveforms: their normalised inner product maximised over a relative time
+/// shift and phase, between 0 and 1.
+///
+/// The maximisation over time shifts is circular over the length of the data, so the
+/// waveforms should be padded by at least their relative offset. The inputs are as for
+/// [`inner_product`]; `match` itself is a Rust keyword.
+pub fn maximized_match(
+    a: &TimeSeriesBase,
+    b: &TimeSeriesBase,
+    psd: &FrequencySeries,
+    flow: &Quantity,
+    fhigh: &Quantity,
+) -> Result<f64, FilterError> {
+    let norm = |x| inner_product(x, x, psd, flow, fhigh);
+    let normalization = (norm(a)? * norm(b)?).sqrt();
+    if normalization == 0.0 {
+        return Err(FilterError::InvalidDesign(
+            "Cannot match a waveform with no power in the band".to_string(),
+        ));
+    }
+    // The complex overlap at every time shift, with the negative frequencies left empty
+    let n = a.value().len();
+    let mut spectrum = weighted_cross_spectrum(a, b, psd, flow, fhigh)?;
+    spectrum.resize(n, Complex64::new(0.0, 0.0));
+    let peak = ifft(&spectrum)
+        .iter()
+        .map(|z| z.norm() * n as f64)
+        .fold(0.0, f64::max);
+    Ok(peak / normalization)
+}
+
+// Bins of 4 ã(f) b̃*(f) / S(f) df over the non-negative FFT frequencies, zero outside
+// the band
+fn weighted_cross_spectrum(
+    a: &TimeSeriesBase,
+    b: &TimeSeriesBase,
+    psd: &FrequencySeries,
+    flow: &Quantity,
+    fhigh: &Quantity,
+) -> Result<Vec<Complex64>, FilterError> {
+    let dt = a.dt_seconds()?;
+    let n = a.value().len();
+    if n == 0 || b.value().len() != n || b.dt_seconds()? != dt {
+        return Err(FilterError::InvalidDesign(
+            "Waveforms must be non-empty and share dt and length".to_string(),
+        ));
+    }
+    let (flow, fhigh) = (flow.to(&HERTZ)?.value[0], fhigh.to(&HERTZ)?.value[0]);
+    let psd_unit = ((Quantity::new(array![1.0], a.unit().clone())
+        * Quantity::new(array![1.0], b.unit().clone()))
+        / Quantity::new(array![1.0], HERTZ))?
+    .unit;
+    let psd_values = Quantity::new(psd.value().clone(), psd.unit().clone())
+        .to(&psd_unit)?
+        .value
+        .to_vec();
+    let frequencies = psd.get_frequencies().ok_or_else(|| {
+        FilterError::InvalidDesign("The PSD must have a frequency axis".to_string())
+    })?;
+    let frequencies = frequencies.to(&HERTZ)?.value.to_vec();
+
+    let df = 1.0 / (n as f64 * dt);
+    let (spectrum_a, spectrum_b) = (rfft(a.value()), rfft(b.value()));
+    let mut integrand = vec![Complex64::new(0.0, 0.0); spectrum_a.len()];
+    for (k, bin) in integrand.iter_mut().enumerate() {
+        let f = k as f64 * df;
+        if f < flow || f > fhigh {
+            continue;
+        }
+        let density = interpolate(f, &frequencies, &psd_values);
+        if density <= 0.0 {
+            return Err(FilterError::InvalidDesign(format!(
+                "The PSD must be positive in the band, found {density} at {f} Hz"
+            )));
+        }
+        *bin = 4.0 * spectrum_a[k] * spectrum_b[k].conj() * dt * dt * df / density;
+    }
+    Ok(integrand)
+}
+
+// --- Tests for matched filtering ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frequencyseries::core::FrequencySeriesBuilder;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::{METRE, SECOND};
+    use ndarray::Array1;
+    use std::f64::consts::PI;
+
+    const RATE: f64 = 1024.0;
+
+    // A sine-Gaussian at frequency f centred on t0
+    fn waveform(t0: f64, f: f64, phase: f64) -> TimeSeriesBase {
+        TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(4096, |i| {
+                let t = i as f64 / RATE - t0;
+                (-(t * t) / (2.0 * 0.05 * 0.05)).exp() * (2.0 * PI * f * t + phase).cos()
+            }))
+            .unit(METRE)
+            .dt(Quantity::new(array![1.0 / RATE], SECOND))
+            .build()
+            .unwrap()
+    }
+
+    // White noise of unit variance per sample has the one-sided PSD 2 dt
+    fn white_psd() -> FrequencySeries {
+        let unit = ((Quantity::new(array![1.0], METRE) * Quantity::new(array![1.0], METRE))
+            / Quantity::new(array![1.0], HERTZ))
+        .unwrap()
+        .unit;
+        FrequencySeriesBuilder::new()
+            .value(array![2.0 / RATE, 2.0 / RATE])
+            .unit(unit)
+            .frequencies(Quantity::new(array![0.0, RATE / 2.0], HERTZ))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_inner_product_with_white_noise() {
+        let a = waveform(1.0, 100.0, 0.0);
+        let band = |f| Quantity::new(array![f], HERTZ);
+        let product = inner_product(&a, &a, &white_psd(), &band(0.0), &band(RATE)).unwrap();
+        let energy = a.value().mapv(|x| x * x).sum();
+        assert!((product / energy - 1.0).abs() < 1e-9, "{product} {energy}");
+
+        // Restricting the band away from the signal removes it
+        let outside = inner_product(&a, &a, &white_psd(), &band(300.0), &band(400.0)).unwrap();
+        assert!(outside < 1e-9 * energy);
+        let short = TimeSeriesBaseBuilder::new()
+            .value(Array1::zeros(16))
+            .unit(METRE)
+            .dt(Quantity::new(array![1.0 / RATE], SECOND))
+            .build()
+            .unwrap();
+        assert!(inner_product(&a, &short, &white_psd(), &band(0.0), &band(RATE)).is_err());
+    }
+
+    #[test]
+    fn test_match_maximizes_over_time_and_phase() {
+        let band = |f| Quantity::new(array![f], HERTZ);
+        let (flow, fhigh) = (band(20.0), band(400.0));
+        let a = waveform(1.0, 100.0, 0.0);
+        let shifted = waveform(2.25, 100.0, 1.2);
+        let matched = maximized_match(&a, &shifted, &white_psd(), &flow, &fhigh).unwrap();
+        assert!((matched - 1.0).abs() < 1e-6, "{matched}");
+        let overlap = inner_product(&a, &shifted, &white_psd(), &flow, &fhigh).unwrap();
+        assert!(overlap.abs() < 1e-3);
+
+        // A different frequency barely overlaps
+        let detuned = waveform(1.0, 160.0, 0.0);
+        let matched = maximized_match(&a, &detuned, &white_psd(), &flow, &fhigh).unwrap();
+        assert!(matched < 0.01, "{matched}");
+    }
+}
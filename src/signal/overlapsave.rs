@@ -0,0 +1,184 @@
+use crate::signal::fft::{irfft, rfft};
+use crate::signal::filter::FilterError;
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::units::SECOND;
+use ndarray::{Array1, s};
+use rustfft::num_complex::Complex64;
+
+// Offsets between chunks smaller than this fraction of a sample are taken as contiguous
+const SAMPLE_TOLERANCE: f64 = 1e-3;
+
+/// A causal FIR filter applied to a stream of contiguous chunks by the overlap-save method.
+///
+/// The output is `y[n] = sum_k h[k] x[n - k]`, as `scipy.signal.lfilter(taps, 1, x)` on the
+/// whole stream with the samples before the first chunk taken as zero. The filter keeps the
+/// last `taps - 1` input samples between calls, so each chunk is filtered exactly as if the
+/// stream had been held in memory, whatever the chunk sizes. Each block is transformed with
+/// an FFT of the next power of two at least four times the number of taps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlapSaveFilter {
+    taps: Array1<f64>,
+    // FFT length, and the transform of the zero-padded taps at that length
+    nfft: usize,
+    taps_spectrum: Vec<Complex64>,
+
+    // The last `taps - 1` input samples, oldest first
+    history: Array1<f64>,
+
+    // Sample interval and expected start time of the next chunk, once a chunk was filtered
+    dt: Option<f64>,
+    next_t0: Option<f64>,
+}
+
+impl OverlapSaveFilter {
+    /// Creates a streaming filter with the FIR coefficients `taps`, e.g. from
+    /// [`fir_lowpass`](crate::signal::filter::fir_lowpass).
+    pub fn new(taps: Array1<f64>) -> Result<Self, FilterError> {
+        if taps.is_empty() {
+            return Err(FilterError::InvalidDesign(
+                "An FIR filter needs at least one tap".to_string(),
+            ));
+        }
+        let nfft = (4 * taps.len()).next_power_of_two();
+        let mut padded = Array1::zeros(nfft);
+        padded.slice_mut(s![..taps.len()]).assign(&taps);
+        Ok(OverlapSaveFilter {
+            taps_spectrum: rfft(&padded),
+            history: Array1::zeros(taps.len() - 1),
+            taps,
+            nfft,
+            dt: None,
+            next_t0: None,
+        })
+    }
+    /// Returns the FIR coefficients.
+    pub fn taps(&self) -> &Array1<f64> {
+        &self.taps
+    }
+    /// Clears the filter state, so that the next chunk starts a new stream.
+    pub fn reset(&mut self) {
+        self.history.fill(0.0);
+        self.dt = None;
+        self.next_t0 = None;
+    }
+
+    /// Filters the next `chunk` of the stream, returning the output for its samples with
+    /// the chunk's time axis, unit and metadata.
+    ///
+    /// Every chunk after the first must have the same `dt` and start where the previous one
+    /// ended; otherwise the state no longer describes the samples preceding it and an error
+    /// is returned, leaving the state untouched. Call [`reset`](Self::reset) to start over.
+    pub fn process(&mut self, chunk: &TimeSeriesBase) -> Result<TimeSeriesBase, FilterError> {
+        let dt = chunk.dt_seconds()?;
+        let t0 = match chunk.get_t0() {
+            Some(t0) => t0.to(&SECOND)?.value[0],
+            None => 0.0,
+        };
+        if let (Some(expected_dt), Some(expected_t0)) = (self.dt, self.next_t0)
+            && ((dt - expected_dt).abs() > 1e-9 * expected_dt
+                || (t0 - expected_t0).abs() > SAMPLE_TOLERANCE * dt)
+        {
+            return Err(FilterError::InvalidDesign(format!(
+                "Chunk starting at {t0} s with dt = {dt} s does not continue the stream, \
+                 expected {expected_t0} s with dt = {expected_dt} s"
+            )));
+        }
+
+        let overlap = self.history.len();
+        let n = chunk.value().len();
+        let mut buffer = Array1::zeros(overlap + n);
+        buffer.slice_mut(s![..overlap]).assign(&self.history);
+        buffer.slice_mut(s![overlap..]).assign(chunk.value());
+
+        // Each block yields nfft - overlap outputs free of circular wrap-around
+        let step = self.nfft - overlap;
+        let mut output = Array1::zeros(n);
+        for start in (0..n).step_by(step) {
+            let length = step.min(n - start);
+            let mut block = Array1::zeros(self.nfft);
+            block
+                .slice_mut(s![..overlap + length])
+                .assign(&buffer.slice(s![start..start + overlap + length]));
+            let product: Vec<Complex64> = rfft(&block)
+                .iter()
+                .zip(self.taps_spectrum.iter())
+                .map(|(x, h)| x * h)
+                .collect();
+            let filtered = irfft(&product, self.nfft);
+            output
+                .slice_mut(s![start..start + length])
+                .assign(&filtered.slice(s![overlap..overlap + length]));
+        }
+
+        self.history = buffer.slice(s![n..]).to_owned();
+        self.dt = Some(dt);
+        self.next_t0 = Some(t0 + n as f64 * dt);
+        Ok(chunk.with_values(output)?)
+    }
+}
+
+// --- Tests for overlap-save filtering ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signal::filter::fir_lowpass;
+    use crate::signal::window::Window;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::{METRE, Quantity};
+    use ndarray::array;
+
+    fn chunk(values: Array1<f64>, t0: f64) -> TimeSeriesBase {
+        TimeSeriesBaseBuilder::new()
+            .value(values)
+            .unit(METRE)
+            .t0(t0)
+            .dt(Quantity::new(array![1.0 / 256.0], SECOND))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_overlap_save_matches_direct_filtering() {
+        let taps = fir_lowpass(101, 30.0, 256.0, Window::Hamming).unwrap();
+        let data = Array1::from_shape_fn(3000, |i| ((i * 7919) % 101) as f64 - 50.0);
+        let expected = Array1::from_shape_fn(data.len(), |n| {
+            (0..taps.len().min(n + 1))
+                .map(|k| taps[k] * data[n - k])
+                .sum::<f64>()
+        });
+
+        // Uneven chunks, some shorter than the filter and one spanning several blocks
+        let mut filter = OverlapSaveFilter::new(taps).unwrap();
+        let mut start = 0;
+        for length in [100, 1, 37, 1500, 1362] {
+            let t0 = 1_000_000_000.0 + start as f64 / 256.0;
+            let piece = chunk(data.slice(s![start..start + length]).to_owned(), t0);
+            let output = filter.process(&piece).unwrap();
+            assert_eq!(output.get_t0(), piece.get_t0());
+            assert_eq!(output.unit(), &METRE);
+            for (i, y) in output.value().iter().enumerate() {
+                assert!((y - expected[start + i]).abs() < 1e-9, "{} {y}", start + i);
+            }
+            start += length;
+        }
+    }
+
+    #[test]
+    fn test_overlap_save_requires_contiguous_chunks() {
+        let close = |output: TimeSeriesBase, expected: Array1<f64>| {
+            (output.value() - &expected).iter().all(|x| x.abs() < 1e-12)
+        };
+        let mut filter = OverlapSaveFilter::new(array![0.5, 0.5]).unwrap();
+        let first = filter.process(&chunk(array![2.0, 4.0], 0.0)).unwrap();
+        assert!(close(first, array![1.0, 3.0]));
+        // A gap of one sample breaks the stream
+        assert!(filter.process(&chunk(array![6.0], 3.0 / 256.0)).is_err());
+        let next = filter.process(&chunk(array![6.0], 2.0 / 256.0)).unwrap();
+        assert!(close(next, array![5.0]));
+
+        filter.reset();
+        let restarted = filter.process(&chunk(array![6.0], 100.0)).unwrap();
+        assert!(close(restarted, array![3.0]));
+        assert!(OverlapSaveFilter::new(Array1::zeros(0)).is_err());
+    }
+}
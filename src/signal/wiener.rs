@@ -0,0 +1,254 @@
+use crate::segments::core::Segment;
+use crate::signal::filter::FilterError;
+use crate::signal::linalg::solve;
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use astronomy::units::{Quantity, SECOND, Unit};
+use ndarray::{Array1, Array2, array};
+
+/// A multi-input Wiener filter predicting a target channel from witness channels.
+///
+/// The prediction is a sum of causal FIR filters, one per witness,
+/// `y[n] = sum_m sum_k h_m[k] x_m[n - k]`, whose taps minimise the mean squared error
+/// against the target over the training data. Subtracting the prediction removes the part of
+/// the target noise that is linearly coupled from the witnesses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WienerFilter {
+    // Taps of each witness filter, one row per witness
+    taps: Array2<f64>,
+    dt: f64,
+    unit: Unit,
+}
+
+impl WienerFilter {
+    /// Trains `ntaps`-tap filters from `witnesses` to `target`.
+    ///
+    /// All series must share the target's `t0`, `dt` and length. When `segments` are given
+    /// only target samples whose GPS time falls inside one of them are fitted, so that
+    /// glitches or lock losses can be excluded from training. The normal equations are
+    /// assembled from the witness cross-correlations over the training samples (the
+    /// block-Toeplitz Wiener–Hopf form) and solved directly.
+    pub fn train(
+        target: &TimeSeriesBase,
+        witnesses: &[&TimeSeriesBase],
+        ntaps: usize,
+        segments: Option<&[Segment]>,
+    ) -> Result<Self, FilterError> {
+        if ntaps == 0 || witnesses.is_empty() {
+            return Err(FilterError::InvalidDesign(
+                "A Wiener filter needs at least one witness and one tap".to_string(),
+            ));
+        }
+        let dt = target.dt_seconds()?;
+        let t0 = start_time(target)?;
+        let n = target.value().len();
+        for witness in witnesses {
+            if witness.value().len() != n
+                || witness.dt_seconds()? != dt
+                || start_time(witness)? != t0
+            {
+                return Err(FilterError::InvalidDesign(
+                    "Witnesses must share the t0, dt and length of the target".to_string(),
+                ));
+            }
+        }
+        // Samples with a full filter history that lie in the training segments
+        let training: Vec<usize> = (ntaps - 1..n)
+            .filter(|&i| match segments {
+                Some(segments) => {
+                    let time = t0 + i as f64 * dt;
+                    segments.iter().any(|s| s.start() <= time && time < s.end())
+                }
+                None => true,
+            })
+            .collect();
+        if training.len() < witnesses.len() * ntaps {
+            return Err(FilterError::InvalidDesign(format!(
+                "{} training samples cannot constrain {} taps",
+                training.len(),
+                witnesses.len() * ntaps
+            )));
+        }
+
+        let size = witnesses.len() * ntaps;
+        let mut correlation = Array2::<f64>::zeros((size, size));
+        let mut cross = Array1::<f64>::zeros(size);
+        for (a, x) in witnesses.iter().enumerate() {
+            let x = x.value();
+            for k in 0..ntaps {
+                cross[a * ntaps + k] = training.iter().map(|&i| target.value()[i] * x[i - k]).sum();
+            }
+            for (b, w) in witnesses.iter().enumerate() {
+                let w = w.value();
+                // Correlation of witness a at lag k with witness b at lag k + d depends on d only
+                for d in -(ntaps as isize - 1)..ntaps as isize {
+                    let lagged: f64 = training
+                        .iter()
+                        .filter_map(|&i| {
+                            let j = i.checked_add_signed(-d).filter(|&j| j < n)?;
+                            Some(x[i] * w[j])
+                        })
+                        .sum();
+                    for k in 0..ntaps {
+                        let l = k as isize + d;
+                        if (0..ntaps as isize).contains(&l) {
+                            correlation[[a * ntaps + k, b * ntaps + l as usize]] = lagged;
+                        }
+                    }
+                }
+            }
+        }
+        let solution = solve(correlation, cross).ok_or_else(|| {
+            FilterError::InvalidDesign(
+                "The witness correlation matrix is singular; the witnesses are degenerate"
+                    .to_string(),
+            )
+        })?;
+        Ok(WienerFilter {
+            taps: solution
+                .into_shape_with_order((witnesses.len(), ntaps))
+                .unwrap(),
+            dt,
+            unit: target.unit().clone(),
+        })
+    }
+
+    /// Returns the filter taps, one row per witness.
+    pub fn taps(&self) -> &Array2<f64> {
+        &self.taps
+    }
+
+    /// Predicts the target from `witnesses`, which must be in the training order and share a
+    /// length and the training `dt`. Samples before the start of the data are taken as zero.
+    pub fn predict(&self, witnesses: &[&TimeSeriesBase]) -> Result<TimeSeriesBase, FilterError> {
+        if witnesses.len() != self.taps.nrows() {
+            return Err(FilterError::InvalidDesign(format!(
+                "The filter was trained with {} witnesses but {} were given",
+                self.taps.nrows(),
+                witnesses.len()
+            )));
+        }
+        let n = witnesses[0].value().len();
+        for witness in witnesses {
+            if witness.value().len() != n || witness.dt_seconds()? != self.dt {
+                return Err(FilterError::InvalidDesign(
+                    "Witnesses must share a length and the training dt".to_string(),
+                ));
+            }
+        }
+        let mut prediction = Array1::<f64>::zeros(n);
+        for (taps, witness) in self.taps.outer_iter().zip(witnesses) {
+            let x = witness.value();
+            for (i, y) in prediction.iter_mut().enumerate() {
+                *y += taps
+                    .iter()
+                    .take(i + 1)
+                    .enumerate()
+                    .map(|(k, h)| h * x[i - k])
+                    .sum::<f64>();
+            }
+        }
+        Ok(TimeSeriesBaseBuilder::new()
+            .value(prediction)
+            .unit(self.unit.clone())
+            .t0(start_time(witnesses[0])?)
+            .dt(Quantity::new(array![self.dt], SECOND))
+            .build()?)
+    }
+
+    /// Subtracts the prediction from `witnesses` from `target`, returning the cleaned target.
+    pub fn subtract(
+        &self,
+        target: &TimeSeriesBase,
+        witnesses: &[&TimeSeriesBase],
+    ) -> Result<TimeSeriesBase, FilterError> {
+        let prediction = self.predict(witnesses)?;
+        if prediction.value().len() != target.value().len()
+            || start_time(target)? != start_time(witnesses[0])?
+        {
+            return Err(FilterError::InvalidDesign(
+                "Witnesses must share the t0 and length of the target".to_string(),
+            ));
+        }
+        let scale = Quantity::new(array![1.0], self.unit.clone())
+            .to(target.unit())?
+            .value[0];
+        Ok(target.with_values(target.value() - &(prediction.value() * scale))?)
+    }
+}
+
+// GPS start time of a series in seconds, zero when it has no t0
+fn start_time(ts: &TimeSeriesBase) -> Result<f64, FilterError> {
+    Ok(match ts.get_t0() {
+        Some(t0) => t0.to(&SECOND)?.value[0],
+        None => 0.0,
+    })
+}
+
+// --- Tests for Wiener filtering ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::testing::{series, uniform_noise};
+    use astronomy::units::METRE;
+
+    // Target coupled to two witnesses through short FIR paths, plus independent noise
+    fn coupled(n: usize) -> (Array1<f64>, Array1<f64>, Array1<f64>) {
+        let x1 = uniform_noise(n, 1);
+        let x2 = uniform_noise(n, 2);
+        let background = uniform_noise(n, 3) * 0.01;
+        let target = Array1::from_shape_fn(n, |i| {
+            let lagged = |x: &Array1<f64>, k: usize| if i >= k { x[i - k] } else { 0.0 };
+            0.5 * x1[i] - 0.25 * lagged(&x1, 1) + 2.0 * lagged(&x2, 2) + background[i]
+        });
+        (target, x1, x2)
+    }
+
+    #[test]
+    fn test_wiener_filter_recovers_coupling() {
+        let (target, x1, x2) = coupled(8192);
+        let (target, x1, x2) = (
+            series(target, 100.0, 1.0 / 64.0),
+            series(x1, 100.0, 1.0 / 64.0),
+            series(x2, 100.0, 1.0 / 64.0),
+        );
+        let wiener = WienerFilter::train(&target, &[&x1, &x2], 4, None).unwrap();
+        let expected = array![[0.5, -0.25, 0.0, 0.0], [0.0, 0.0, 2.0, 0.0]];
+        for (found, expected) in wiener.taps().iter().zip(expected.iter()) {
+            assert!((found - expected).abs() < 1e-2);
+        }
+
+        let cleaned = wiener.subtract(&target, &[&x1, &x2]).unwrap();
+        let rms = (cleaned.value().mapv(|x| x * x).mean().unwrap()).sqrt();
+        // Only the independent background, of RMS 0.01 / sqrt(3), should remain
+        assert!(rms < 0.01);
+        assert_eq!(cleaned.unit(), &METRE);
+    }
+
+    #[test]
+    fn test_wiener_filter_trains_on_segments() {
+        let (mut target, x1, x2) = coupled(8192);
+        // A loud glitch in the first half, uncorrelated with the witnesses
+        target
+            .slice_mut(ndarray::s![..4096])
+            .mapv_inplace(|x| x + 50.0);
+        let (target, x1, x2) = (
+            series(target, 100.0, 1.0 / 64.0),
+            series(x1, 100.0, 1.0 / 64.0),
+            series(x2, 100.0, 1.0 / 64.0),
+        );
+        let quiet = [Segment::new(164.0, 228.0)];
+        let wiener = WienerFilter::train(&target, &[&x1, &x2], 3, Some(&quiet)).unwrap();
+        assert!((wiener.taps()[[1, 2]] - 2.0).abs() < 1e-2);
+        assert!((wiener.taps()[[0, 0]] - 0.5).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_wiener_filter_rejects_mismatched_inputs() {
+        let target = series(uniform_noise(64, 1), 100.0, 1.0 / 64.0);
+        let short = series(uniform_noise(32, 2), 100.0, 1.0 / 64.0);
+        assert!(WienerFilter::train(&target, &[&short], 2, None).is_err());
+        assert!(WienerFilter::train(&target, &[], 2, None).is_err());
+        let degenerate = series(Array1::zeros(64), 100.0, 1.0 / 64.0);
+        assert!(WienerFilter::train(&target, &[&degenerate], 2, None).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use ndarray::{Array1, s};
+use std::f64::consts::PI;
+
+/// Window functions applied to data segments before spectral estimation.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Window {
+    /// No tapering (boxcar)
+    Rectangular,
+    /// Periodic Hann window, the default for PSD estimation
+    #[default]
+    Hann,
+    /// Hamming window
+    Hamming,
+    /// Blackman window
+    Blackman,
+    /// Tukey window: a flat top with cosine tapers over a fraction `alpha` of the window,
+    /// rectangular for `alpha <= 0` and Hann for `alpha >= 1`
+    Tukey(f64),
+    /// Kaiser window with shape parameter `beta`; larger values trade a wider main lobe for
+    /// lower side lobes
+    Kaiser(f64),
+    /// Flat-top window, whose flat main lobe gives accurate amplitudes of lines falling
+    /// between frequency bins
+    FlatTop,
+}
+
+// Cosine-series coefficients of the flat-top window, as in `scipy.signal.windows.flattop`
+const FLAT_TOP_COEFFICIENTS: [f64; 5] = [
+    0.215_578_95,
+    0.416_631_58,
+    0.277_263_158,
+    0.083_578_947,
+    0.006_947_368,
+];
+
+// Relative size of the last term at which the Bessel function series is truncated
+const BESSEL_TOLERANCE: f64 = 1e-17;
+
+impl Window {
+    /// Returns the `n`-point window as an array.
+    ///
+    /// Windows are periodic (the DFT-even form used by `scipy.signal.get_window`),
+    /// which is the appropriate choice for spectral analysis.
+    pub fn generate(&self, n: usize) -> Array1<f64> {
+        self.generate_symmetric(n + 1).slice_move(s![..n])
+    }
+
+    /// Returns the `n`-point symmetric window, the form used for FIR filter design.
+    pub fn generate_symmetric(&self, n: usize) -> Array1<f64> {
+        if n <= 1 {
+            return Array1::ones(n);
+        }
+        let span = (n - 1) as f64;
+        match self {
+            Window::Rectangular => Array1::ones(n),
+            Window::Hann => general_cosine(n, &[0.5, 0.5]),
+            Window::Hamming => general_cosine(n, &[0.54, 0.46]),
+            Window::Blackman => general_cosine(n, &[0.42, 0.5, 0.08]),
+            Window::FlatTop => general_cosine(n, &FLAT_TOP_COEFFICIENTS),
+            Window::Tukey(alpha) if *alpha <= 0.0 => Array1::ones(n),
+            Window::Tukey(alpha) => {
+                let alpha = alpha.min(1.0);
+                Array1::from_shape_fn(n, |i| {
+                    // Distance from the nearer end as a fraction of the window
+                    let x = (i as f64).min(span - i as f64) / span;
+                    if x < alpha / 2.0 {
+                        0.5 - 0.5 * (2.0 * PI * x / alpha).cos()
+                    } else {
+                        1.0
+                    }
+                })
+            }
+            Window::Kaiser(beta) => Array1::from_shape_fn(n, |i| {
+                let x = 2.0 * i as f64 / span - 1.0;
+                bessel_i0(beta * (1.0 - x * x).max(0.0).sqrt()) / bessel_i0(*beta)
+            }),
+        }
+    }
+}
+
+// Symmetric window `sum_k (-1)^k a_k cos(2 pi k i / (n - 1))`, for n >= 2
+fn general_cosine(n: usize, coefficients: &[f64]) -> Array1<f64> {
+    let span = (n - 1) as f64;
+    Array1::from_shape_fn(n, |i| {
+        coefficients
+            .iter()
+            .enumerate()
+            .map(|(k, a)| {
+                let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+                sign * a * (2.0 * PI * k as f64 * i as f64 / span).cos()
+            })
+            .sum()
+    })
+}
+
+// Modified Bessel function of the first kind of order zero, from its power series
+fn bessel_i0(x: f64) -> f64 {
+    let quarter_square = x * x / 4.0;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut k = 1.0;
+    while term > BESSEL_TOLERANCE * sum {
+        term *= quarter_square / (k * k);
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
+
+// --- Tests for window functions ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hann_window() {
+        let window = Window::Hann.generate(4);
+        let expected = [0.0, 0.5, 1.0, 0.5];
+        for (w, e) in window.iter().zip(expected.iter()) {
+            assert!((w - e).abs() < 1e-12);
+        }
+        assert_eq!(Window::default(), Window::Hann);
+    }
+
+    #[test]
+    fn test_kaiser_window() {
+        // I0(1) from Abramowitz & Stegun table 9.8
+        assert!((bessel_i0(1.0) - 1.266_065_878).abs() < 1e-9);
+        let window = Window::Kaiser(5.0).generate_symmetric(5);
+        let expected = [
+            0.036_710_892,
+            0.552_851_77,
+            1.0,
+            0.552_851_77,
+            0.036_710_892,
+        ];
+        for (w, e) in window.iter().zip(expected.iter()) {
+            assert!((w - e).abs() < 1e-6, "{w} vs {e}");
+        }
+        assert_eq!(Window::Kaiser(0.0).generate(3), Array1::from(vec![1.0; 3]));
+    }
+
+    #[test]
+    fn test_cosine_windows() {
+        let cases = [
+            (Window::Hamming, [0.08, 0.54, 1.0, 0.54, 0.08]),
+            (Window::Blackman, [0.0, 0.34, 1.0, 0.34, 0.0]),
+            (
+                Window::FlatTop,
+                [
+                    -0.000_421_051,
+                    -0.054_736_84,
+                    1.000_000_003,
+                    -0.054_736_84,
+                    -0.000_421_051,
+                ],
+            ),
+        ];
+        for (window, expected) in cases {
+            for (w, e) in window.generate_symmetric(5).iter().zip(expected.iter()) {
+                assert!((w - e).abs() < 1e-9, "{window:?}: {w} vs {e}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_tukey_window() {
+        let window = Window::Tukey(0.5).generate_symmetric(9);
+        let expected = [0.0, 0.5, 1.0, 1.0, 1.0, 1.0, 1.0, 0.5, 0.0];
+        for (w, e) in window.iter().zip(expected.iter()) {
+            assert!((w - e).abs() < 1e-12);
+        }
+        assert_eq!(Window::Tukey(0.0).generate(4), Array1::from(vec![1.0; 4]));
+        let hann = Window::Hann.generate(8);
+        let tukey = Window::Tukey(1.0).generate(8);
+        assert!(
+            hann.iter()
+                .zip(tukey.iter())
+                .all(|(h, t)| (h - t).abs() < 1e-12)
+        );
+    }
+
+    #[test]
+    fn test_rectangular_window() {
+        assert_eq!(Window::Rectangular.generate(3), Array1::from(vec![1.0; 3]));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::signal::filter::FilterError;
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::time::Time;
+use astronomy::units::{HERTZ, Quantity, QuantityError, SECOND};
+use ndarray::{array, s};
+
+/// Which sample rate [`align_with`](TimeSeriesBase::align_with) brings both series to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlignRate {
+    /// The lower of the two rates, downsampling the faster series
+    #[default]
+    Lower,
+    /// The higher of the two rates, upsampling the slower series
+    Higher,
+}
+
+// -- Alignment of pairs of TimeSeriesBase
+impl TimeSeriesBase {
+    /// Returns these data and `other` on a common time grid: resampled to the rate chosen by
+    /// `rate` and cropped to the span covered by both.
+    ///
+    /// Resampling uses [`resample`](Self::resample) and keeps each series' `t0`, so if the
+    /// sample times of the two are offset by a fraction of a sample the second series keeps
+    /// that offset; both results then have the same rate and length and start within one
+    /// sample of each other. Units and metadata are kept. Series that do not overlap in time
+    /// are a [`QuantityError::MismatchError`].
+    pub fn align_with(
+        &self,
+        other: &TimeSeriesBase,
+        rate: AlignRate,
+    ) -> Result<(TimeSeriesBase, TimeSeriesBase), FilterError> {
+        let (rate_a, rate_b) = (1.0 / self.dt_seconds()?, 1.0 / other.dt_seconds()?);
+        let target = match rate {
+            AlignRate::Lower => rate_a.min(rate_b),
+            AlignRate::Higher => rate_a.max(rate_b),
+        };
+        let to_rate = |ts: &TimeSeriesBase, current: f64| {
+            if (current - target).abs() > 1e-9 * target {
+                ts.resample(&Quantity::new(array![target], HERTZ))
+            } else {
+                Ok(ts.clone())
+            }
+        };
+        let (a, b) = (to_rate(self, rate_a)?, to_rate(other, rate_b)?);
+
+        let span = |ts: &TimeSeriesBase| -> Result<(f64, f64), FilterError> {
+            let t0 = match ts.get_t0() {
+                Some(t0) => t0.to(&SECOND)?.value[0],
+                None => 0.0,
+            };
+            Ok((t0, t0 + ts.value().len() as f64 / target))
+        };
+        let ((start_a, end_a), (start_b, end_b)) = (span(&a)?, span(&b)?);
+        let (start, end) = (start_a.max(start_b), end_a.min(end_b));
+        if start >= end {
+            return Err(QuantityError::MismatchError(format!(
+                "The series do not overlap: [{start_a}, {end_a}) and [{start_b}, {end_b})"
+            ))
+            .into());
+        }
+        let (start, end) = (Time::from_gps_seconds(start), Time::from_gps_seconds(end));
+        let (a, b) = (a.crop(start, end)?, b.crop(start, end)?);
+
+        // A fractional offset between the grids can leave one series a sample longer
+        let n = a.value().len().min(b.value().len());
+        let truncate = |ts: TimeSeriesBase| -> Result<TimeSeriesBase, FilterError> {
+            if ts.value().len() == n {
+                Ok(ts)
+            } else {
+                Ok(ts.with_values(ts.value().slice(s![..n]).to_owned())?)
+            }
+        };
+        Ok((truncate(a)?, truncate(b)?))
+    }
+}
+
+// --- Tests for alignment ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::testing::series;
+    use ndarray::Array1;
+
+    #[test]
+    fn test_align_with() {
+        // 16 Hz over [100, 104) and 64 Hz over [102, 110)
+        let slow = series(Array1::from_shape_fn(64, |i| i as f64), 100.0, 1.0 / 16.0);
+        let fast = series(Array1::from_shape_fn(512, |i| i as f64), 102.0, 1.0 / 64.0);
+
+        let (a, b) = slow.align_with(&fast, AlignRate::Lower).unwrap();
+        assert_eq!(a.value().len(), 32);
+        assert_eq!(b.value().len(), 32);
+        assert_eq!(a.get_t0().unwrap().value[0], 102.0);
+        assert_eq!(b.get_t0().unwrap().value[0], 102.0);
+        assert_eq!(b.get_sample_rate().unwrap().value[0], 16.0);
+        assert_eq!(a.value()[0], 32.0);
+
+        let (a, b) = slow.align_with(&fast, AlignRate::Higher).unwrap();
+        assert_eq!(a.get_sample_rate().unwrap().value[0], 64.0);
+        assert_eq!((a.value().len(), b.value().len()), (128, 128));
+        assert_eq!(b.value()[0], 0.0);
+
+        let later = series(Array1::from_shape_fn(16, |i| i as f64), 200.0, 1.0 / 16.0);
+        assert!(matches!(
+            slow.align_with(&later, AlignRate::default()),
+            Err(FilterError::QuantityError(QuantityError::MismatchError(_)))
+        ));
+    }
+}
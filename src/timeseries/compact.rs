@@ -0,0 +1,151 @@
+use crate::detector::channel::Channel;
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use astronomy::units::{Quantity, QuantityError, SECOND, Unit};
+use ndarray::{Array1, array, s};
+use std::ops::Range;
+
+/// Regularly sampled data held at single precision, for storing long stretches of raw strain
+/// at half the memory of a [`TimeSeriesBase`].
+///
+/// Processing stays in double precision: the samples are widened to `f64` when a whole
+/// series or a range of samples is converted back with [`to_timeseries`](Self::to_timeseries)
+/// or [`to_timeseries_range`](Self::to_timeseries_range). Rounding to `f32` keeps about
+/// seven significant digits, ample for data whose noise floor sits far above that
+/// precision, but the start time is kept in `f64` so GPS times lose nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactTimeSeries {
+    values: Array1<f32>,
+    unit: Unit,
+    name: Option<String>,
+    channel: Option<Channel>,
+
+    // Start time and sample interval, in seconds
+    t0: f64,
+    dt: f64,
+}
+
+impl CompactTimeSeries {
+    /// Returns the single-precision samples.
+    pub fn value(&self) -> &Array1<f32> {
+        &self.values
+    }
+    pub fn unit(&self) -> &Unit {
+        &self.unit
+    }
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    pub fn get_channel(&self) -> Option<&Channel> {
+        self.channel.as_ref()
+    }
+    /// Returns the start time in seconds.
+    pub fn get_t0(&self) -> Quantity {
+        Quantity::new(array![self.t0], SECOND)
+    }
+    /// Returns the sample interval in seconds.
+    pub fn get_dt(&self) -> Quantity {
+        Quantity::new(array![self.dt], SECOND)
+    }
+    /// Returns the number of samples.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    /// Returns true if the series holds no samples.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    /// Returns the approximate memory footprint in bytes of the samples and metadata.
+    pub fn mem_size(&self) -> usize {
+        size_of::<Self>()
+            + self.values.len() * size_of::<f32>()
+            + self.name.as_ref().map_or(0, String::capacity)
+    }
+
+    /// Widens the whole series to a double-precision [`TimeSeriesBase`].
+    pub fn to_timeseries(&self) -> Result<TimeSeriesBase, QuantityError> {
+        self.to_timeseries_range(0..self.len())
+    }
+    /// Widens the samples in `range` to a double-precision [`TimeSeriesBase`] starting at
+    /// the time of the first of them, so long data can be processed one stretch at a time.
+    pub fn to_timeseries_range(
+        &self,
+        range: Range<usize>,
+    ) -> Result<TimeSeriesBase, QuantityError> {
+        if range.start > range.end || range.end > self.len() {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "Sample range {range:?} is out of bounds for {} samples",
+                self.len()
+            )));
+        }
+        let mut builder = TimeSeriesBaseBuilder::new()
+            .value(self.values.slice(s![range.clone()]).mapv(f64::from))
+            .unit(self.unit.clone())
+            .t0(self.t0 + range.start as f64 * self.dt)
+            .dt(self.get_dt());
+        if let Some(name) = &self.name {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = &self.channel {
+            builder = builder.source_channel(channel.clone());
+        }
+        builder.build()
+    }
+}
+
+// -- Single-precision storage for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Rounds the samples to single precision, keeping the unit, name, channel and time axis;
+    /// see [`CompactTimeSeries`]. The series must be regularly sampled.
+    pub fn to_compact(&self) -> Result<CompactTimeSeries, QuantityError> {
+        let dt = self.dt_seconds()?;
+        let t0 = match self.get_t0() {
+            Some(t0) => t0.to(&SECOND)?.value[0],
+            None => 0.0,
+        };
+        Ok(CompactTimeSeries {
+            values: self.value().mapv(|x| x as f32),
+            unit: self.unit().clone(),
+            name: self.get_name().map(str::to_string),
+            channel: self.get_channel().cloned(),
+            t0,
+            dt,
+        })
+    }
+}
+
+// --- Tests for single-precision storage ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use astronomy::units::METRE;
+
+    #[test]
+    fn test_compact_round_trip() {
+        let values = Array1::from_shape_fn(4096, |i| (i as f64 * 0.01).sin() * 1e-21);
+        let series = TimeSeriesBaseBuilder::new()
+            .value(values.clone())
+            .unit(METRE)
+            .name("strain")
+            .t0(1_000_000_000.0)
+            .dt(Quantity::new(array![1.0 / 4096.0], SECOND))
+            .build()
+            .unwrap();
+        let compact = series.to_compact().unwrap();
+        assert_eq!(compact.len(), 4096);
+        assert!(compact.mem_size() < series.mem_size() / 2 + 256);
+
+        let restored = compact.to_timeseries().unwrap();
+        assert_eq!(restored.unit(), &METRE);
+        assert_eq!(restored.get_name(), Some("strain"));
+        assert_eq!(restored.get_t0(), series.get_t0());
+        for (x, y) in restored.value().iter().zip(values.iter()) {
+            assert!((x - y).abs() <= 1e-7 * y.abs(), "{x} {y}");
+        }
+
+        // A later stretch starts at the time of its first sample
+        let stretch = compact.to_timeseries_range(1024..2048).unwrap();
+        assert_eq!(stretch.value().len(), 1024);
+        assert_eq!(stretch.get_t0().unwrap().value[0], 1_000_000_000.25);
+        assert!(compact.to_timeseries_range(4000..4100).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use crate::signal::fft::{irfft, rfft};
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use astronomy::units::{QuantityError, SECOND};
+use ndarray::{Array1, s};
+
+// Kernels longer than this are applied through the FFT rather than directly
+const DIRECT_KERNEL_LENGTH: usize = 64;
+
+/// Which part of the full convolution [`convolve`](TimeSeriesBase::convolve) returns, as
+/// in `numpy.convolve`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConvolveMode {
+    /// Every sample the kernel overlaps, `n + m - 1` in all
+    Full,
+    /// The `n` samples centred on the data, keeping its time axis
+    #[default]
+    Same,
+    /// Only the `n - m + 1` samples where the kernel lies wholly within the data
+    Valid,
+}
+
+// -- Convolution for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Convolves the data with the dimensionless FIR `kernel`, keeping the unit and
+    /// metadata.
+    ///
+    /// The kernel is centred on each output sample, its middle (the earlier of the two for
+    /// an even length) landing on the sample at the same time, so the `Same` mode keeps
+    /// `t0` and the other modes move it by whole samples. Kernels of more than 64 taps are
+    /// applied through a zero-padded FFT, with identical results up to rounding. The data
+    /// must be regularly sampled, and `Valid` needs a kernel no longer than the data.
+    pub fn convolve(
+        &self,
+        kernel: &Array1<f64>,
+        mode: ConvolveMode,
+    ) -> Result<TimeSeriesBase, QuantityError> {
+        let (n, m) = (self.value().len(), kernel.len());
+        if n == 0 || m == 0 {
+            return Err(QuantityError::InvalidQuantity(
+                "Cannot convolve with empty data or an empty kernel".to_string(),
+            ));
+        }
+        let dt = self.dt_seconds()?;
+        let full = if m > DIRECT_KERNEL_LENGTH {
+            fft_convolve(self.value(), kernel)
+        } else {
+            direct_convolve(self.value(), kernel)
+        };
+
+        // Index into the full convolution of the first sample kept
+        let centre = (m - 1) / 2;
+        let first = match mode {
+            ConvolveMode::Full => 0,
+            ConvolveMode::Same => centre,
+            ConvolveMode::Valid => m - 1,
+        };
+        let length = match mode {
+            ConvolveMode::Full => n + m - 1,
+            ConvolveMode::Same => n,
+            ConvolveMode::Valid if m <= n => n - m + 1,
+            ConvolveMode::Valid => {
+                return Err(QuantityError::InvalidQuantity(format!(
+                    "A valid convolution needs a kernel ({m} taps) no longer than the data ({n})"
+                )));
+            }
+        };
+        let values = full.slice(s![first..first + length]).to_owned();
+        if mode == ConvolveMode::Same {
+            return self.with_values(values);
+        }
+        let t0 = match self.get_t0() {
+            Some(t0) => t0.to(&SECOND)?.value[0],
+            None => 0.0,
+        };
+        TimeSeriesBaseBuilder::like(self)
+            .value(values)
+            .t0(t0 + (first as f64 - centre as f64) * dt)
+            .build()
+    }
+}
+
+fn direct_convolve(data: &Array1<f64>, kernel: &Array1<f64>) -> Array1<f64> {
+    let mut full = Array1::zeros(data.len() + kernel.len() - 1);
+    for (j, &tap) in kernel.iter().enumerate() {
+        full.slice_mut(s![j..j + data.len()]).scaled_add(tap, data);
+    }
+    full
+}
+
+fn fft_convolve(data: &Array1<f64>, kernel: &Array1<f64>) -> Array1<f64> {
+    let length = data.len() + kernel.len() - 1;
+    let nfft = length.next_power_of_two();
+    let padded = |values: &Array1<f64>| {
+        let mut padded = Array1::zeros(nfft);
+        padded.slice_mut(s![..values.len()]).assign(values);
+        rfft(&padded)
+    };
+    let product: Vec<_> = padded(data)
+        .iter()
+        .zip(padded(kernel).iter())
+        .map(|(a, b)| a * b)
+        .collect();
+    irfft(&product, nfft).slice(s![..length]).to_owned()
+}
+
+// --- Tests for convolution ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::testing::series_builder;
+    use astronomy::units::METRE;
+    use ndarray::array;
+
+    #[test]
+    fn test_convolve_modes() {
+        let ts = series_builder(array![1.0, 2.0, 3.0, 4.0], 10.0, 0.5)
+            .name("X1:TEST")
+            .build()
+            .unwrap();
+        let kernel = array![1.0, 0.0, -1.0];
+
+        let full = ts.convolve(&kernel, ConvolveMode::Full).unwrap();
+        assert_eq!(full.value(), &array![1.0, 2.0, 2.0, 2.0, -3.0, -4.0]);
+        assert_eq!(full.get_t0().unwrap().value[0], 9.5);
+        let same = ts.convolve(&kernel, ConvolveMode::Same).unwrap();
+        assert_eq!(same.value(), &array![2.0, 2.0, 2.0, -3.0]);
+        assert_eq!(same.get_t0(), ts.get_t0());
+        assert_eq!(same.get_name(), Some("X1:TEST"));
+        assert_eq!(same.unit(), &METRE);
+        let valid = ts.convolve(&kernel, ConvolveMode::Valid).unwrap();
+        assert_eq!(valid.value(), &array![2.0, 2.0]);
+        assert_eq!(valid.get_t0().unwrap().value[0], 10.5);
+
+        assert!(ts.convolve(&Array1::ones(5), ConvolveMode::Valid).is_err());
+        assert!(ts.convolve(&array![], ConvolveMode::Same).is_err());
+    }
+
+    #[test]
+    fn test_long_kernels_use_fft() {
+        let ts = series_builder(
+            Array1::from_shape_fn(500, |i| ((i * 37) % 11) as f64 - 5.0),
+            10.0,
+            0.5,
+        )
+        .name("X1:TEST")
+        .build()
+        .unwrap();
+        let kernel = Array1::from_shape_fn(101, |i| (i as f64 * 0.1).sin());
+        let via_fft = ts.convolve(&kernel, ConvolveMode::Full).unwrap();
+        let direct = direct_convolve(ts.value(), &kernel);
+        for (a, b) in via_fft.value().iter().zip(direct.iter()) {
+            assert!((a - b).abs() < 1e-9, "{a} {b}");
+        }
+    }
+}
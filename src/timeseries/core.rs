@@ -4,7 +4,10 @@ use crate::detector::channel::Channel;
 use crate::types::series::{Series, SeriesBuilder};
 use astronomy::time::Time;
 use astronomy::units::{HERTZ, Quantity, QuantityError, SECOND, Unit, UnitProduct};
-use ndarray::{Array1, array};
+use ndarray::{Array1, array, s};
+
+// Fraction of a sample by which crop boundaries may miss sample times, absorbing rounding
+const CROP_TOLERANCE: f64 = 1e-6;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TimeSeriesBase {
@@ -24,9 +27,12 @@ pub struct TimeSeriesBase {
 /// maps them to the underlying `SeriesBuilder`'s `x0`, `dx`, `xindex` fields.
 pub struct TimeSeriesBaseBuilder {
     value: Option<Array1<f64>>,
+    imag: Option<Array1<f64>>,
     unit: Option<Unit>,
     name: Option<String>,
     channel: Option<Channel>,
+    // Whether the unit of the channel defaults and cross-checks that of the data
+    check_channel_unit: bool,
     // Time-domain specific fields
     t0: Option<Time>,              // GPS epoch, directly using astronomy::Time
     dt: Option<Quantity>,          // time between samples
@@ -41,9 +47,11 @@ impl TimeSeriesBaseBuilder {
     pub fn new() -> Self {
         TimeSeriesBaseBuilder {
             value: None,
+            imag: None,
             unit: None,
             name: None,
             channel: None,
+            check_channel_unit: true,
             t0: None,
             dt: None,
             sample_rate: None,
@@ -52,11 +60,37 @@ impl TimeSeriesBaseBuilder {
         }
     }
 
+    /// Starts a builder carrying the unit, name, channel and time axis of `other`, for
+    /// derived series such as filtered copies or residuals.
+    ///
+    /// Only the values need to be set before building; any setter overrides what was
+    /// copied, and the copied channel is not checked against a new unit. A series with an
+    /// explicit time index passes it on, so the new values must match its length.
+    pub fn like(other: &TimeSeriesBase) -> Self {
+        let mut builder = TimeSeriesBaseBuilder::new().unit(other.unit().clone());
+        builder.name = other.get_name().map(str::to_string);
+        builder.channel = other.get_channel().cloned();
+        builder.check_channel_unit = false;
+        if let Some(dt_quantity) = other.get_dt() {
+            // t0 is always held in seconds
+            builder._raw_t0_float = other.get_t0().map(|t0_quantity| t0_quantity.value[0]);
+            builder.dt = Some(dt_quantity.clone());
+        } else {
+            builder.times = other.get_times().cloned();
+        }
+        builder
+    }
+
     // Builder setters for the TimeSeriesBaseBuilder
     pub fn value(mut self, value: Array1<f64>) -> Self {
         self.value = Some(value);
         self
     }
+    /// Sets the imaginary part of the data, making this a complex series.
+    pub fn imag(mut self, imag: Array1<f64>) -> Self {
+        self.imag = Some(imag);
+        self
+    }
     pub fn unit(mut self, unit: Unit) -> Self {
         self.unit = Some(unit);
         self
@@ -65,8 +99,17 @@ impl TimeSeriesBaseBuilder {
         self.name = Some(name.into());
         self
     }
+    /// Sets the channel the data were recorded on. Without an explicit unit the series takes
+    /// the unit of the channel; with one, the two must agree.
     pub fn channel(mut self, channel: Channel) -> Self {
         self.channel = Some(channel);
+        self.check_channel_unit = true;
+        self
+    }
+    // Sets the channel that derived data were computed from, leaving their unit independent
+    pub(crate) fn source_channel(mut self, channel: Channel) -> Self {
+        self.channel = Some(channel);
+        self.check_channel_unit = false;
         self
     }
     /// Sets the GPS epoch time (t0) for the time series.
@@ -101,15 +144,22 @@ impl TimeSeriesBaseBuilder {
         let value = self.value.ok_or_else(|| {
             QuantityError::InvalidQuantity("Value is required to build TimeSeriesBase".to_string())
         })?;
-        let mut series_builder = SeriesBuilder::new().value(value).unit(
-            self.unit
-                .unwrap_or_else(|| Unit::new("", 1.0, UnitProduct::zero())),
-        );
+        let mut series_builder = SeriesBuilder::new().value(value);
+        if let Some(imag) = self.imag {
+            series_builder = series_builder.imag(imag);
+        }
+        if let Some(unit) = self.unit {
+            series_builder = series_builder.unit(unit);
+        }
         if let Some(name) = self.name {
             series_builder = series_builder.name(name);
         }
         if let Some(channel) = self.channel {
-            series_builder = series_builder.channel(channel);
+            series_builder = if self.check_channel_unit {
+                series_builder.channel(channel)
+            } else {
+                series_builder.source_channel(channel)
+            };
         }
         if let Some(times_quantity) = self.times {
             // If times are provided, use them directly
@@ -177,6 +227,14 @@ impl TimeSeriesBase {
     pub fn value(&self) -> &Array1<f64> {
         self.series_data.value()
     }
+    /// Returns the imaginary part of the data, if this is a complex series.
+    pub fn imag(&self) -> Option<&Array1<f64>> {
+        self.series_data.imag()
+    }
+    /// Returns true if this series carries an imaginary part.
+    pub fn is_complex(&self) -> bool {
+        self.series_data.is_complex()
+    }
     pub fn unit(&self) -> &Unit {
         self.series_data.unit()
     }
@@ -186,6 +244,29 @@ impl TimeSeriesBase {
     pub fn get_channel(&self) -> Option<&Channel> {
         self.series_data.get_channel()
     }
+    pub fn mean(&self) -> Result<Quantity, QuantityError> {
+        self.series_data.mean()
+    }
+    pub fn median(&self) -> Result<Quantity, QuantityError> {
+        self.series_data.median()
+    }
+    pub fn std(&self) -> Result<Quantity, QuantityError> {
+        self.series_data.std()
+    }
+    pub fn min(&self) -> Result<Quantity, QuantityError> {
+        self.series_data.min()
+    }
+    pub fn max(&self) -> Result<Quantity, QuantityError> {
+        self.series_data.max()
+    }
+    pub fn abs_max(&self) -> Result<Quantity, QuantityError> {
+        self.series_data.abs_max()
+    }
+    /// Returns the approximate memory footprint in bytes of the samples, time axis and
+    /// metadata.
+    pub fn mem_size(&self) -> usize {
+        self.series_data.mem_size()
+    }
     pub fn get_t0(&self) -> Option<&Quantity> {
         self.series_data.get_x0()
     }
@@ -210,6 +291,155 @@ impl TimeSeriesBase {
                 .expect("Failed to convert Quantity to Hertz.")
         })
     }
+    // Starts a SeriesBuilder for new sample values carrying this series' unit, name, channel
+    // and epoch, leaving the time axis to the caller
+    fn series_builder(&self, values: Array1<f64>) -> SeriesBuilder {
+        let mut series_builder = SeriesBuilder::new().value(values).unit(self.unit().clone());
+        if let Some(name) = self.get_name() {
+            series_builder = series_builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            series_builder = series_builder.source_channel(channel.clone());
+        }
+        if let Some(epoch) = self.series_data.get_epoch() {
+            series_builder = series_builder.epoch(epoch);
+        }
+        series_builder
+    }
+    // Rebuilds the series around new sample values, keeping the unit, name, channel and time
+    // axis; values must keep the original length when the series has an explicit time index
+    pub(crate) fn with_values(&self, values: Array1<f64>) -> Result<TimeSeriesBase, QuantityError> {
+        self.with_values_in(values, self.unit().clone())
+    }
+    // As with_values, for sample values in a different unit
+    pub(crate) fn with_values_in(
+        &self,
+        values: Array1<f64>,
+        unit: Unit,
+    ) -> Result<TimeSeriesBase, QuantityError> {
+        let mut series_builder = self.series_builder(values).unit(unit);
+        if let Some(dt_quantity) = self.get_dt() {
+            if let Some(t0_quantity) = self.get_t0() {
+                series_builder = series_builder.x0(t0_quantity.clone());
+            }
+            series_builder = series_builder.dx(dt_quantity.clone());
+        } else if let Some(times_quantity) = self.get_times() {
+            series_builder = series_builder.xindex(times_quantity.clone());
+        }
+        Ok(TimeSeriesBase::new_internal(series_builder.build()?))
+    }
+    /// Returns the samples in the GPS span `[start, end)`, with `t0` moved to the first
+    /// sample kept.
+    ///
+    /// The span must lie within the data, which for regularly sampled series extend to
+    /// `t0 + n dt` and for an explicit time index to the last sample time.
+    pub fn crop(&self, start: Time, end: Time) -> Result<TimeSeriesBase, QuantityError> {
+        let (start, end) = (start.as_gps_seconds_f64(), end.as_gps_seconds_f64());
+        if start >= end {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "Crop start ({start}) must be before its end ({end})"
+            )));
+        }
+        let times = self.get_times().ok_or_else(|| {
+            QuantityError::InvalidQuantity("Cannot crop a series without a time axis".to_string())
+        })?;
+        let times = times.to(&SECOND)?.value;
+        let n = times.len();
+        let (data_start, data_end, tolerance) = match self.get_dt() {
+            Some(dt_quantity) => {
+                let dt = dt_quantity.to(&SECOND)?.value[0];
+                let t0 = times.first().copied().unwrap_or(0.0);
+                (t0, t0 + n as f64 * dt, CROP_TOLERANCE * dt)
+            }
+            None if n > 0 => (times[0], times[n - 1], 0.0),
+            None => (0.0, 0.0, 0.0),
+        };
+        if start < data_start - tolerance || end > data_end + tolerance {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "Crop span [{start}, {end}) is outside the data span [{data_start}, {data_end})"
+            )));
+        }
+        // Sample times carry rounding from t0 + i dt, so boundaries are matched with a tolerance
+        let first = times
+            .iter()
+            .position(|&t| t >= start - tolerance)
+            .unwrap_or(n);
+        let last = times
+            .iter()
+            .position(|&t| t >= end - tolerance)
+            .unwrap_or(n);
+
+        let values = self.value().slice(s![first..last]).to_owned();
+        let mut series_builder = self.series_builder(values);
+        series_builder = match (self.get_t0(), self.get_dt()) {
+            (Some(_), Some(dt_quantity)) => {
+                let t0 = if first < n { times[first] } else { end };
+                series_builder
+                    .x0(Quantity::new(array![t0], SECOND))
+                    .dx(dt_quantity.clone())
+            }
+            _ => series_builder.xindex(Quantity::new(
+                times.slice(s![first..last]).to_owned(),
+                SECOND,
+            )),
+        };
+        Ok(TimeSeriesBase::new_internal(series_builder.build()?))
+    }
+    /// Returns a copy of the series moved in time by `delta`, which may be negative; the
+    /// sample values are untouched. `delta` must be a scalar time quantity.
+    pub fn shift(&self, delta: &Quantity) -> Result<TimeSeriesBase, QuantityError> {
+        if delta.value.len() != 1 {
+            return Err(QuantityError::InvalidQuantity(
+                "The time shift must be a scalar quantity.".to_string(),
+            ));
+        }
+        let delta = delta.to(&SECOND)?.value[0];
+        let mut series_builder = self.series_builder(self.value().clone());
+        if let Some(t0_quantity) = self.get_t0() {
+            let t0 = t0_quantity.to(&SECOND)?.value[0];
+            series_builder = series_builder.x0(Quantity::new(array![t0 + delta], SECOND));
+            if let Some(dt_quantity) = self.get_dt() {
+                series_builder = series_builder.dx(dt_quantity.clone());
+            }
+        } else if let Some(times_quantity) = self.get_times() {
+            let times = times_quantity.to(&SECOND)?.value;
+            series_builder = series_builder.xindex(Quantity::new(times + delta, SECOND));
+        } else if let Some(dt_quantity) = self.get_dt() {
+            series_builder = series_builder.dx(dt_quantity.clone());
+        }
+        Ok(TimeSeriesBase::new_internal(series_builder.build()?))
+    }
+    /// Extends the series by `before` and `after` samples of `value`, moving `t0` back by
+    /// `before` samples. Only regularly sampled series can be padded.
+    pub fn pad(
+        &self,
+        before: usize,
+        after: usize,
+        value: f64,
+    ) -> Result<TimeSeriesBase, QuantityError> {
+        let (Some(t0_quantity), Some(dt_quantity)) = (self.get_t0(), self.get_dt()) else {
+            return Err(QuantityError::InvalidQuantity(
+                "Padding requires a series with t0 and dt".to_string(),
+            ));
+        };
+        let t0 = t0_quantity.to(&SECOND)?.value[0];
+        let dt = dt_quantity.to(&SECOND)?.value[0];
+        let n = self.value().len();
+        let mut values = Array1::from_elem(before + n + after, value);
+        values
+            .slice_mut(s![before..before + n])
+            .assign(self.value());
+
+        let series_builder = self
+            .series_builder(values)
+            .x0(Quantity::new(array![t0 - before as f64 * dt], SECOND))
+            .dx(dt_quantity.clone());
+        Ok(TimeSeriesBase::new_internal(series_builder.build()?))
+    }
+    /// Extends the series with zeros, see [`pad`](Self::pad).
+    pub fn zero_pad(&self, before: usize, after: usize) -> Result<TimeSeriesBase, QuantityError> {
+        self.pad(before, after, 0.0)
+    }
     pub fn duration(&self) -> Option<Quantity> {
         self.get_times().map(|times_quantity| {
             let values = &times_quantity.value;
@@ -267,8 +497,9 @@ impl Sub for TimeSeriesBase {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cw::heterodyne::{SourceParameters, heterodyne};
     use crate::detector::channel::Channel;
-    use astronomy::units::{HERTZ, JOULE, METRE, SECOND};
+    use astronomy::units::{CENTIMETRE, HERTZ, JOULE, METRE, SECOND};
     use ndarray::array;
 
     #[test]
@@ -423,4 +654,264 @@ mod tests {
         assert_eq!(sum_ts.get_epoch(), ts1.get_epoch()); // Epoch from LHS
         assert_eq!(sum_ts.get_dt().unwrap(), ts1.get_dt().unwrap()); // dt from LHS
     }
+
+    #[test]
+    fn test_timeseriesbase_mem_size() {
+        let build = |n: usize| {
+            TimeSeriesBaseBuilder::new()
+                .value(Array1::zeros(n))
+                .t0(0.0)
+                .dt(Quantity::new(array![1.0], SECOND))
+                .build()
+                .unwrap()
+        };
+        // Each sample costs one value and one cached time stamp
+        let per_sample = 2 * std::mem::size_of::<f64>();
+        assert_eq!(
+            build(2000).mem_size() - build(1000).mem_size(),
+            1000 * per_sample
+        );
+        assert!(build(1000).mem_size() > 1000 * per_sample);
+    }
+
+    #[test]
+    fn test_timeseriesbase_crop() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(16, |i| i as f64))
+            .unit(METRE)
+            .t0(1_000_000_000.0)
+            .dt(Quantity::new(array![0.25], SECOND))
+            .name("X1:TEST")
+            .build()
+            .unwrap();
+        let cropped = ts
+            .crop(
+                Time::from_gps_seconds(1_000_000_001.0),
+                Time::from_gps_seconds(1_000_000_002.5),
+            )
+            .unwrap();
+        assert_eq!(cropped.value(), &array![4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        assert_eq!(cropped.get_t0().unwrap().value[0], 1_000_000_001.0);
+        assert_eq!(cropped.get_times().unwrap().value[5], 1_000_000_002.25);
+        assert_eq!(cropped.get_dt(), ts.get_dt());
+        assert_eq!(cropped.get_name(), Some("X1:TEST"));
+
+        // The full span is allowed, anything beyond it is not
+        let whole = ts.crop(
+            Time::from_gps_seconds(1_000_000_000.0),
+            Time::from_gps_seconds(1_000_000_004.0),
+        );
+        assert_eq!(whole.unwrap().value(), ts.value());
+        for (start, end) in [
+            (999_999_999.0, 1_000_000_001.0),
+            (1_000_000_003.0, 1_000_000_005.0),
+            (1_000_000_002.0, 1_000_000_001.0),
+        ] {
+            assert!(
+                ts.crop(Time::from_gps_seconds(start), Time::from_gps_seconds(end))
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_timeseriesbase_crop_explicit_times() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, 2.0, 3.0, 4.0])
+            .times(Quantity::new(array![10.0, 11.0, 13.0, 17.0], SECOND))
+            .build()
+            .unwrap();
+        let cropped = ts
+            .crop(Time::from_gps_seconds(10.5), Time::from_gps_seconds(17.0))
+            .unwrap();
+        assert_eq!(cropped.value(), &array![2.0, 3.0]);
+        assert_eq!(cropped.get_times().unwrap().value, array![11.0, 13.0]);
+    }
+
+    #[test]
+    fn test_timeseriesbase_shift() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, 2.0, 3.0])
+            .t0(100.0)
+            .dt(Quantity::new(array![0.5], SECOND))
+            .build()
+            .unwrap();
+        let later = ts.shift(&Quantity::new(array![2.0], SECOND)).unwrap();
+        assert_eq!(later.value(), ts.value());
+        assert_eq!(later.get_t0().unwrap().value[0], 102.0);
+        assert_eq!(
+            later.get_times().unwrap().value,
+            array![102.0, 102.5, 103.0]
+        );
+
+        let earlier = ts.shift(&Quantity::new(array![-0.25], SECOND)).unwrap();
+        assert_eq!(earlier.get_t0().unwrap().value[0], 99.75);
+        assert_eq!(earlier.get_dt(), ts.get_dt());
+
+        assert!(matches!(
+            ts.shift(&Quantity::new(array![1.0], METRE)),
+            Err(QuantityError::IncompatibleUnits { .. })
+        ));
+    }
+
+    #[test]
+    fn test_timeseriesbase_pad() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, 2.0, 3.0])
+            .unit(METRE)
+            .t0(10.0)
+            .dt(Quantity::new(array![0.5], SECOND))
+            .build()
+            .unwrap();
+        let padded = ts.pad(2, 3, -1.0).unwrap();
+        assert_eq!(
+            padded.value(),
+            &array![-1.0, -1.0, 1.0, 2.0, 3.0, -1.0, -1.0, -1.0]
+        );
+        assert_eq!(padded.get_t0().unwrap().value[0], 9.0);
+        assert_eq!(padded.get_times().unwrap().value[2], 10.0);
+        assert_eq!(padded.unit(), &METRE);
+
+        let zeros = ts.zero_pad(0, 5).unwrap();
+        assert_eq!(zeros.value().len(), 8);
+        assert_eq!(zeros.value()[7], 0.0);
+        assert_eq!(zeros.get_t0(), ts.get_t0());
+
+        let irregular = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, 2.0])
+            .times(Quantity::new(array![0.0, 3.0], SECOND))
+            .build()
+            .unwrap();
+        assert!(irregular.zero_pad(1, 1).is_err());
+    }
+
+    #[test]
+    fn test_timeseriesbase_unit_from_channel() {
+        let channel =
+            Channel::new("H1:PEM-SEIS_X", None, Some(METRE), None, None, None, None).unwrap();
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, 2.0, 3.0])
+            .channel(channel.clone())
+            .build()
+            .unwrap();
+        assert_eq!(ts.unit(), &METRE);
+
+        let agreeing = TimeSeriesBaseBuilder::new()
+            .value(array![1.0])
+            .unit(METRE)
+            .channel(channel.clone())
+            .build();
+        assert!(agreeing.is_ok());
+        let disagreeing = TimeSeriesBaseBuilder::new()
+            .value(array![1.0])
+            .unit(JOULE)
+            .channel(channel.clone())
+            .build();
+        assert!(matches!(disagreeing, Err(QuantityError::MismatchError(_))));
+
+        // Units are compared by dimensions and scale, not by name, so an unnamed m·s/s agrees
+        // with metres while centimetres do not
+        let metre_seconds = Quantity::new(array![1.0], METRE) * Quantity::new(array![1.0], SECOND);
+        let computed = (metre_seconds / Quantity::new(array![1.0], SECOND))
+            .unwrap()
+            .unit;
+        assert_ne!(computed.name, METRE.name);
+        let computed_agrees = TimeSeriesBaseBuilder::new()
+            .value(array![1.0])
+            .unit(computed.clone())
+            .channel(channel.clone())
+            .build()
+            .unwrap();
+        assert_eq!(computed_agrees.unit(), &computed);
+        let centimetres = TimeSeriesBaseBuilder::new()
+            .value(array![1.0])
+            .unit(CENTIMETRE)
+            .channel(channel)
+            .build();
+        assert!(matches!(centimetres, Err(QuantityError::MismatchError(_))));
+
+        // Derived data keep the channel whatever their unit
+        let square = (Quantity::new(array![1.0], METRE) * Quantity::new(array![1.0], METRE)).unit;
+        let power = ts.map_with_unit(|x| x * x, square.clone()).unwrap();
+        assert_eq!(power.unit(), &square);
+        assert_eq!(power.get_channel(), ts.get_channel());
+    }
+
+    #[test]
+    fn test_timeseriesbase_builder_like() {
+        let channel =
+            Channel::new("L1:GDS-CALIB_STRAIN", None, None, None, None, None, None).unwrap();
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, 2.0, 3.0])
+            .unit(METRE)
+            .name("Strain")
+            .channel(channel)
+            .t0(1126259446.0)
+            .sample_rate(Quantity::new(array![16.0], HERTZ))
+            .build()
+            .unwrap();
+
+        let residual = TimeSeriesBaseBuilder::like(&ts)
+            .value(array![0.5, 0.0, -0.5, 0.0])
+            .build()
+            .unwrap();
+        assert_eq!(residual.unit(), &METRE);
+        assert_eq!(residual.get_name(), Some("Strain"));
+        assert_eq!(residual.get_channel(), ts.get_channel());
+        assert_eq!(residual.get_t0(), ts.get_t0());
+        assert_eq!(residual.get_dt(), ts.get_dt());
+
+        let renamed = TimeSeriesBaseBuilder::like(&ts)
+            .value(array![0.0])
+            .name("Residual")
+            .unit(JOULE)
+            .build()
+            .unwrap();
+        assert_eq!(renamed.get_name(), Some("Residual"));
+        assert_eq!(renamed.unit(), &JOULE);
+
+        let irregular = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, 2.0])
+            .times(Quantity::new(array![0.0, 3.0], SECOND))
+            .build()
+            .unwrap();
+        let copy = TimeSeriesBaseBuilder::like(&irregular)
+            .value(array![4.0, 5.0])
+            .build()
+            .unwrap();
+        assert_eq!(copy.get_times(), irregular.get_times());
+    }
+
+    #[test]
+    fn test_derived_products_keep_source_channel() {
+        // Data whose unit no longer matches the unit of the channel they came from
+        let channel =
+            Channel::new("H1:STRAIN", Some(64.0), Some(METRE), None, None, None, None).unwrap();
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(64 * 120, |i| (i as f64 * 0.3).sin()))
+            .unit(METRE)
+            .t0(1_000_000_000.0)
+            .dt(Quantity::new(array![1.0 / 64.0], SECOND))
+            .channel(channel.clone())
+            .build()
+            .unwrap()
+            .map_with_unit(|x| x * x, JOULE)
+            .unwrap();
+
+        let spectrum = ts.fft().unwrap();
+        assert_eq!(spectrum.get_channel(), Some(&channel));
+        let rms = ts.rms(&Quantity::new(array![1.0], SECOND)).unwrap();
+        assert_eq!(rms.get_channel(), Some(&channel));
+        let resampled = ts.resample(&Quantity::new(array![32.0], HERTZ)).unwrap();
+        assert_eq!(resampled.get_channel(), Some(&channel));
+        let source = SourceParameters {
+            ra: 1.2,
+            dec: 0.4,
+            frequency: 10.0,
+            frequency_derivative: 0.0,
+            reference_epoch: Time::from_gps_seconds(1_000_000_000.0),
+        };
+        let bk = heterodyne(&ts, &source).unwrap();
+        assert_eq!(bk.get_channel(), Some(&channel));
+    }
 }
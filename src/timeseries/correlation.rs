@@ -0,0 +1,107 @@
+use crate::signal::fft::{irfft, rfft};
+use crate::timeseries::core::TimeSeriesBase;
+use crate::types::series::{Series, SeriesBuilder};
+use astronomy::units::{Quantity, QuantityError, SECOND, Unit, UnitProduct};
+use ndarray::{Array1, array, s};
+
+// -- Correlation functions for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Computes the normalized auto-correlation function of the data for lags from zero up to
+    /// `max_lag`, returned as a dimensionless `Series` indexed by lag time in seconds.
+    ///
+    /// The mean is removed and the biased estimator `sum_i x[i] x[i + k] / sum_i x[i]²` is
+    /// evaluated with zero-padded FFTs, so the value at zero lag is one.
+    pub fn autocorrelation(&self, max_lag: &Quantity) -> Result<Series, QuantityError> {
+        if max_lag.value.len() != 1 || max_lag.value[0] < 0.0 {
+            return Err(QuantityError::InvalidQuantity(
+                "max_lag must be a non-negative scalar quantity.".to_string(),
+            ));
+        }
+        let dt = self.dt_seconds()?;
+        let n = self.value().len();
+        let lags = (max_lag.to(&SECOND)?.value[0] / dt).round() as usize;
+        if lags >= n {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "max_lag ({lags} samples) must be shorter than the data ({n} samples)"
+            )));
+        }
+        let mean = self.value().mean().unwrap_or(0.0);
+        // Padding to at least 2n keeps the circular correlation free of wrap-around
+        let padded_length = (2 * n).next_power_of_two();
+        let mut padded = Array1::<f64>::zeros(padded_length);
+        padded
+            .slice_mut(s![..n])
+            .assign(&self.value().mapv(|x| x - mean));
+        let power: Vec<_> = rfft(&padded).iter().map(|x| x * x.conj()).collect();
+        let correlation = irfft(&power, padded_length);
+        if correlation[0] <= 0.0 {
+            return Err(QuantityError::InvalidQuantity(
+                "The auto-correlation of constant data is undefined".to_string(),
+            ));
+        }
+        let acf = correlation.slice(s![..=lags]).mapv(|r| r / correlation[0]);
+
+        let mut builder = SeriesBuilder::new()
+            .value(acf)
+            .unit(Unit::new("", 1.0, UnitProduct::zero()))
+            .x0(Quantity::new(array![0.0], SECOND))
+            .dx(Quantity::new(array![dt], SECOND));
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        builder.build()
+    }
+}
+
+// --- Tests for correlation functions ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::testing::series_builder;
+
+    #[test]
+    fn test_autocorrelation_matches_direct_sum() {
+        let values = array![1.0, 3.0, -2.0, 0.5, 4.0, -1.0, 2.0, 0.0];
+        let acf = series_builder(values.clone(), 0.0, 0.5)
+            .name("X1:ACF")
+            .build()
+            .unwrap()
+            .autocorrelation(&Quantity::new(array![1.5], SECOND))
+            .unwrap();
+        assert_eq!(acf.value().len(), 4);
+        assert_eq!(acf.get_xindex().unwrap().value, array![0.0, 0.5, 1.0, 1.5]);
+        assert_eq!(acf.get_name(), Some("X1:ACF"));
+
+        let mean = values.mean().unwrap();
+        let x = values.mapv(|v| v - mean);
+        let direct = |k: usize| (0..x.len() - k).map(|i| x[i] * x[i + k]).sum::<f64>();
+        for k in 0..4 {
+            assert!((acf.value()[k] - direct(k) / direct(0)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_autocorrelation_of_alternating_signal() {
+        let values = Array1::from_shape_fn(1000, |i| if i % 2 == 0 { 1.0 } else { -1.0 });
+        let acf = series_builder(values, 0.0, 0.5)
+            .name("X1:ACF")
+            .build()
+            .unwrap()
+            .autocorrelation(&Quantity::new(array![1.0], SECOND))
+            .unwrap();
+        // Biased estimator: lag k is scaled by (n - k) / n
+        assert!((acf.value()[1] + 0.999).abs() < 1e-9);
+        assert!((acf.value()[2] - 0.998).abs() < 1e-9);
+        assert!(
+            series_builder(Array1::ones(8), 0.0, 0.5)
+                .name("X1:ACF")
+                .build()
+                .unwrap()
+                .autocorrelation(&Quantity::new(array![1.0], SECOND))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use crate::frequencyseries::core::{FrequencySeries, FrequencySeriesBuilder};
+use crate::signal::window::Window;
+use crate::timeseries::core::TimeSeriesBase;
+use crate::timeseries::spectral::{seconds, welch_transforms};
+use crate::types::provenance::Provenance;
+use astronomy::units::{HERTZ, Quantity, QuantityError, SECOND, Unit, UnitProduct};
+use ndarray::{Array1, array};
+use rustfft::num_complex::Complex64;
+
+// Welch-averaged one-sided spectral densities of a pair of series, density-scaled like
+// `psd`, with the length in samples of the transforms they came from
+struct CrossSpectra {
+    pxx: Array1<f64>,
+    pyy: Array1<f64>,
+    pxy: Vec<Complex64>,
+    nfft: usize,
+}
+
+// -- Cross-spectral methods for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Estimates the magnitude-squared coherence `|Pxy|² / (Pxx Pyy)` between these data and
+    /// `other`, a dimensionless `FrequencySeries` between 0 and 1.
+    ///
+    /// The spectral densities are Welch averages over Hann-windowed segments of `fftlength`
+    /// overlapping by `overlap`; with a single segment the coherence is identically one.
+    /// Both series must share their start time, sample spacing and length. Bins where either
+    /// series has no power are set to zero.
+    pub fn coherence(
+        &self,
+        other: &TimeSeriesBase,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+    ) -> Result<FrequencySeries, QuantityError> {
+        let spectra = self.cross_spectra(other, fftlength, overlap)?;
+        let coherence = Array1::from_shape_fn(spectra.pxx.len(), |k| {
+            let power = spectra.pxx[k] * spectra.pyy[k];
+            if power > 0.0 {
+                spectra.pxy[k].norm_sqr() / power
+            } else {
+                0.0
+            }
+        });
+        let provenance = self
+            .cross_provenance(other)?
+            .parameter("fftlength", seconds(fftlength)?)
+            .parameter("overlap", seconds(overlap)?)
+            .parameter("window", format!("{:?}", Window::Hann))
+            .step("coherence");
+        Ok(self
+            .spectrum_from_values(
+                coherence,
+                spectra.nfft,
+                Unit::new("", 1.0, UnitProduct::zero()),
+            )?
+            .with_provenance(provenance))
+    }
+
+    /// Estimates the one-sided cross-spectral density `Pxy = 2 conj(X(f)) Y(f) / (fs Σw²)` of
+    /// these data (x) and `other` (y) as a complex `FrequencySeries`.
+    ///
+    /// The estimate is a Welch average over Hann-windowed, mean-removed segments of
+    /// `fftlength` overlapping by `overlap`, normalized like [`psd`](Self::psd) so that the
+    /// cross-spectral density of a series with itself is its PSD. The unit is the product of
+    /// the two units per Hz. Both series must share their start time, sample spacing and
+    /// length.
+    pub fn csd(
+        &self,
+        other: &TimeSeriesBase,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+    ) -> Result<FrequencySeries, QuantityError> {
+        let spectra = self.cross_spectra(other, fftlength, overlap)?;
+        let unit = ((Quantity::new(array![1.0], self.unit().clone())
+            * Quantity::new(array![1.0], other.unit().clone()))
+            / Quantity::new(array![1.0], HERTZ))?
+        .unit;
+        let mut builder = FrequencySeriesBuilder::new()
+            .value(spectra.pxy.iter().map(|p| p.re).collect())
+            .imag(spectra.pxy.iter().map(|p| p.im).collect())
+            .unit(unit)
+            .f0(Quantity::new(array![0.0], HERTZ))
+            .df(self.frequency_resolution(spectra.nfft)?);
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        if let Some(epoch) = self.get_epoch() {
+            builder = builder.epoch(epoch);
+        }
+        let provenance = self
+            .cross_provenance(other)?
+            .parameter("fftlength", seconds(fftlength)?)
+            .parameter("overlap", seconds(overlap)?)
+            .parameter("window", format!("{:?}", Window::Hann))
+            .step("csd");
+        builder.provenance(provenance).build()
+    }
+
+    /// Estimates the transfer function `H = Pxy / Pxx` from these data (the input x) to
+    /// `other` (the output y) as a complex `FrequencySeries`, whose
+    /// [`abs`](FrequencySeries::abs) and [`phase`](FrequencySeries::phase) give the
+    /// magnitude and phase response.
+    ///
+    /// The spectral densities are those of [`csd`](Self::csd). This is the H1 estimator,
+    /// unbiased by noise on the output but biased low by noise on the input; check the
+    /// [`coherence`](Self::coherence) to judge where it can be trusted. The unit is that of
+    /// `other` per unit of these data, and bins where the input has no power are set to
+    /// zero.
+    pub fn transfer_function(
+        &self,
+        other: &TimeSeriesBase,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+    ) -> Result<FrequencySeries, QuantityError> {
+        let spectra = self.cross_spectra(other, fftlength, overlap)?;
+        let response: Vec<Complex64> = spectra
+            .pxy
+            .iter()
+            .zip(spectra.pxx.iter())
+            .map(|(&pxy, &pxx)| {
+                if pxx > 0.0 {
+                    pxy / pxx
+                } else {
+                    Complex64::new(0.0, 0.0)
+                }
+            })
+            .collect();
+        let unit = (Quantity::new(array![1.0], other.unit().clone())
+            / Quantity::new(array![1.0], self.unit().clone()))?
+        .unit;
+        let mut builder = FrequencySeriesBuilder::new()
+            .value(response.iter().map(|h| h.re).collect())
+            .imag(response.iter().map(|h| h.im).collect())
+            .unit(unit)
+            .f0(Quantity::new(array![0.0], HERTZ))
+            .df(self.frequency_resolution(spectra.nfft)?);
+        if let Some(name) = other.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = other.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        if let Some(epoch) = self.get_epoch() {
+            builder = builder.epoch(epoch);
+        }
+        let provenance = self
+            .cross_provenance(other)?
+            .parameter("fftlength", seconds(fftlength)?)
+            .parameter("overlap", seconds(overlap)?)
+            .parameter("window", format!("{:?}", Window::Hann))
+            .step("transfer_function");
+        builder.provenance(provenance).build()
+    }
+
+    // Welch-averaged auto- and cross-spectral densities of these data and `other`
+    fn cross_spectra(
+        &self,
+        other: &TimeSeriesBase,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+    ) -> Result<CrossSpectra, QuantityError> {
+        let dt = self.dt_seconds()?;
+        let start_time = |ts: &TimeSeriesBase| -> Result<f64, QuantityError> {
+            Ok(match ts.get_t0() {
+                Some(t0) => t0.to(&SECOND)?.value[0],
+                None => 0.0,
+            })
+        };
+        if (other.dt_seconds()? - dt).abs() > 1e-9 * dt
+            || other.value().len() != self.value().len()
+            || start_time(other)? != start_time(self)?
+        {
+            return Err(QuantityError::MismatchError(format!(
+                "Cross spectra need series sharing t0, dt and length, got {} and {} samples \
+                 from {} s and {} s",
+                self.value().len(),
+                other.value().len(),
+                start_time(self)?,
+                start_time(other)?
+            )));
+        }
+        let (nfft, step) = self.welch_segmentation(fftlength, overlap)?;
+        let transforms = |ts: &TimeSeriesBase| {
+            welch_transforms(ts.value().view(), nfft, step, Window::Hann, 1.0 / dt)
+        };
+        let (x, y) = (transforms(self), transforms(other));
+
+        let bins = nfft / 2 + 1;
+        let mut spectra = CrossSpectra {
+            pxx: Array1::zeros(bins),
+            pyy: Array1::zeros(bins),
+            pxy: vec![Complex64::new(0.0, 0.0); bins],
+            nfft,
+        };
+        let n_segments = x.len() as f64;
+        for (x, y) in x.iter().zip(y.iter()) {
+            for k in 0..bins {
+                spectra.pxx[k] += x[k].norm_sqr() / n_segments;
+                spectra.pyy[k] += y[k].norm_sqr() / n_segments;
+                spectra.pxy[k] += x[k].conj() * y[k] / n_segments;
+            }
+        }
+        Ok(spectra)
+    }
+
+    // Starts a provenance record naming both inputs of a cross-spectral estimate
+    fn cross_provenance(&self, other: &TimeSeriesBase) -> Result<Provenance, QuantityError> {
+        let provenance = self.provenance()?;
+        Ok(other
+            .provenance()?
+            .get_sources()
+            .iter()
+            .fold(provenance, |provenance, source| provenance.source(source)))
+    }
+}
+
+// --- Tests for cross-spectral methods ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signal::window::Window;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use crate::timeseries::spectral::PsdMethod;
+    use crate::timeseries::testing::{series_builder, uniform_noise};
+    use astronomy::units::{METRE, SECOND};
+    use ndarray::s;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_coherence() {
+        let (fftlength, overlap) = (
+            Quantity::new(array![1.0], SECOND),
+            Quantity::new(array![0.5], SECOND),
+        );
+        let x = uniform_noise(64 * 256, 1);
+        let witness = series_builder(x.clone(), 0.0, 1.0 / 64.0)
+            .name("X1:WITNESS")
+            .build()
+            .unwrap();
+
+        // A scaled copy is fully coherent
+        let copy = series_builder(&x * 2.0, 0.0, 1.0 / 64.0)
+            .name("X1:COPY")
+            .build()
+            .unwrap();
+        let coherence = witness.coherence(&copy, &fftlength, &overlap).unwrap();
+        assert_eq!(coherence.value().len(), 33);
+        assert!(coherence.value().iter().all(|c| (c - 1.0).abs() < 1e-9));
+        assert_eq!(
+            coherence.get_provenance().unwrap().get_sources(),
+            ["X1:WITNESS", "X1:COPY"]
+        );
+
+        // Adding independent noise of equal power halves it
+        let mixed = series_builder(&x + &uniform_noise(64 * 256, 2), 0.0, 1.0 / 64.0)
+            .name("X1:MIXED")
+            .build()
+            .unwrap();
+        let coherence = witness.coherence(&mixed, &fftlength, &overlap).unwrap();
+        let mean = coherence.value().slice(s![1..]).mean().unwrap();
+        assert!((mean - 0.5).abs() < 0.05, "{mean}");
+
+        let short = series_builder(uniform_noise(128, 3), 0.0, 1.0 / 64.0)
+            .name("X1:SHORT")
+            .build()
+            .unwrap();
+        assert!(witness.coherence(&short, &fftlength, &overlap).is_err());
+        // The same samples recorded a second later are not cross-correlated
+        let later = TimeSeriesBaseBuilder::like(&copy)
+            .value(copy.value().clone())
+            .t0(1.0)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            witness.coherence(&later, &fftlength, &overlap),
+            Err(QuantityError::MismatchError(_))
+        ));
+    }
+
+    #[test]
+    fn test_csd() {
+        let (fftlength, overlap) = (
+            Quantity::new(array![1.0], SECOND),
+            Quantity::new(array![0.5], SECOND),
+        );
+        let x = series_builder(uniform_noise(64 * 64, 4), 0.0, 1.0 / 64.0)
+            .name("X1:X")
+            .build()
+            .unwrap();
+        let y = TimeSeriesBaseBuilder::new()
+            .value(uniform_noise(64 * 64, 5))
+            .unit(SECOND)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / 64.0], SECOND))
+            .build()
+            .unwrap();
+
+        // With itself the CSD is the real PSD
+        let auto = x.csd(&x, &fftlength, &overlap).unwrap();
+        let psd = x
+            .psd(&fftlength, &overlap, Window::Hann, PsdMethod::Welch)
+            .unwrap();
+        assert_eq!(auto.unit(), psd.unit());
+        for (a, b) in auto.value().iter().zip(psd.value().iter()) {
+            assert!((a - b).abs() < 1e-12 * b.abs().max(1.0));
+        }
+        assert!(auto.imag().unwrap().iter().all(|v| v.abs() < 1e-15));
+
+        // Swapping the series conjugates the estimate
+        let xy = x.csd(&y, &fftlength, &overlap).unwrap();
+        let yx = y.csd(&x, &fftlength, &overlap).unwrap();
+        assert!(xy.is_complex());
+        let (xy_imag, yx_imag) = (xy.imag().unwrap(), yx.imag().unwrap());
+        for k in 0..xy.value().len() {
+            assert!((xy.value()[k] - yx.value()[k]).abs() < 1e-15);
+            assert!((xy_imag[k] + yx_imag[k]).abs() < 1e-15);
+        }
+        let expected = ((Quantity::new(array![1.0], METRE) * Quantity::new(array![1.0], SECOND))
+            / Quantity::new(array![1.0], HERTZ))
+        .unwrap()
+        .unit;
+        assert_eq!(xy.unit(), &expected);
+        assert_eq!(xy.get_provenance().unwrap().get_processing(), ["csd"]);
+    }
+
+    #[test]
+    fn test_transfer_function_of_delay_and_gain() {
+        let (fftlength, overlap) = (
+            Quantity::new(array![1.0], SECOND),
+            Quantity::new(array![0.5], SECOND),
+        );
+        // The output is the input scaled by 3 and delayed by two samples
+        let x = uniform_noise(64 * 64 + 2, 6);
+        let input = series_builder(x.slice(s![2..]).to_owned(), 0.0, 1.0 / 64.0)
+            .name("X1:EXC")
+            .build()
+            .unwrap();
+        let output = TimeSeriesBaseBuilder::new()
+            .value(x.slice(s![..64 * 64]).mapv(|v| 3.0 * v))
+            .unit(SECOND)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / 64.0], SECOND))
+            .name("X1:RESP")
+            .build()
+            .unwrap();
+        let response = input
+            .transfer_function(&output, &fftlength, &overlap)
+            .unwrap();
+        assert_eq!(response.get_name(), Some("X1:RESP"));
+        let expected = (Quantity::new(array![1.0], SECOND) / Quantity::new(array![1.0], METRE))
+            .unwrap()
+            .unit;
+        assert_eq!(response.unit(), &expected);
+
+        // Away from the ends of the band the window edges barely bias the estimate
+        let (magnitude, phase) = (response.abs(), response.phase());
+        for k in 1..16 {
+            let delay = -2.0 * PI * k as f64 * 2.0 / 64.0;
+            assert!((magnitude[k] - 3.0).abs() < 0.3, "{k} {}", magnitude[k]);
+            assert!((phase[k] - delay).abs() < 0.1, "{k} {}", phase[k]);
+        }
+    }
+}
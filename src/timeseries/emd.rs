@@ -0,0 +1,253 @@
+use crate::signal::fft::{ifft, rfft};
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use astronomy::units::{HERTZ, Quantity, QuantityError, SECOND};
+use ndarray::{Array1, array};
+use rustfft::num_complex::Complex64;
+use std::f64::consts::PI;
+
+// Sifting of a mode stops once an iteration changes it by less than this fraction of its
+// energy (the standard-deviation criterion of Huang et al. 1998), or after MAX_SIFTS passes
+const SIFT_TOLERANCE: f64 = 0.05;
+const MAX_SIFTS: usize = 50;
+
+/// The result of an [`emd`](TimeSeriesBase::emd) decomposition.
+///
+/// The modes and the residual add back up to the input data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmpiricalModes {
+    // Intrinsic mode functions, from the fastest oscillation to the slowest
+    pub imfs: Vec<TimeSeriesBase>,
+
+    // Trend left after the last mode, with too few extrema to sift further
+    pub residual: TimeSeriesBase,
+}
+
+impl EmpiricalModes {
+    /// Returns the [`instantaneous_frequency`](TimeSeriesBase::instantaneous_frequency) of
+    /// each mode, completing the Hilbert–Huang transform.
+    pub fn instantaneous_frequencies(&self) -> Result<Vec<TimeSeriesBase>, QuantityError> {
+        self.imfs
+            .iter()
+            .map(TimeSeriesBase::instantaneous_frequency)
+            .collect()
+    }
+}
+
+// -- Empirical mode decomposition for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Splits the data into at most `max_imfs` intrinsic mode functions by empirical mode
+    /// decomposition.
+    ///
+    /// Each mode is sifted by repeatedly subtracting the mean of the natural cubic spline
+    /// envelopes through the local maxima and minima, with the envelopes held at the value
+    /// of the outermost extremum out to the ends of the data. Decomposition stops early once
+    /// the remainder has fewer than two maxima or minima. Unlike Fourier methods this makes
+    /// no assumption of stationarity, at the cost of some mode mixing and end effects.
+    pub fn emd(&self, max_imfs: usize) -> Result<EmpiricalModes, QuantityError> {
+        let mut remainder = self.value().clone();
+        let mut imfs = Vec::new();
+        while imfs.len() < max_imfs {
+            let Some(imf) = sift(&remainder) else {
+                break;
+            };
+            remainder -= &imf;
+            imfs.push(self.with_values(imf)?);
+        }
+        Ok(EmpiricalModes {
+            imfs,
+            residual: self.with_values(remainder)?,
+        })
+    }
+
+    /// Estimates the instantaneous frequency of the data, in Hz, from the phase of its
+    /// analytic signal.
+    ///
+    /// The analytic signal is formed with an FFT-based Hilbert transform and its unwrapped
+    /// phase is differentiated by central differences. The estimate is only meaningful for
+    /// narrow-band data such as a single intrinsic mode function.
+    pub fn instantaneous_frequency(&self) -> Result<TimeSeriesBase, QuantityError> {
+        let dt = self.dt_seconds()?;
+        let n = self.value().len();
+        if n < 2 {
+            return Err(QuantityError::InvalidQuantity(
+                "At least two samples are needed to estimate a frequency".to_string(),
+            ));
+        }
+        let phase: Vec<f64> = analytic_signal(self.value())
+            .iter()
+            .map(|z| z.arg())
+            .collect();
+        let frequency = Array1::from_shape_fn(n, |i| {
+            let (before, after) = (i.saturating_sub(1), (i + 1).min(n - 1));
+            let step = (phase[after] - phase[before] + PI).rem_euclid(2.0 * PI) - PI;
+            step / (2.0 * PI * (after - before) as f64 * dt)
+        });
+        let mut builder = TimeSeriesBaseBuilder::new()
+            .value(frequency)
+            .unit(HERTZ)
+            .dt(Quantity::new(array![dt], SECOND));
+        if let Some(t0) = self.get_t0() {
+            builder = builder.t0(t0.to(&SECOND)?.value[0]);
+        }
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        builder.build()
+    }
+}
+
+// Sifts one intrinsic mode function out of `data`, or returns None if it has too few
+// extrema to define both envelopes
+fn sift(data: &Array1<f64>) -> Option<Array1<f64>> {
+    let mut mode = data.clone();
+    for pass in 0..MAX_SIFTS {
+        let (maxima, minima) = extrema(&mode);
+        if maxima.len() < 2 || minima.len() < 2 {
+            return (pass > 0).then_some(mode);
+        }
+        let upper = envelope(&mode, &maxima);
+        let lower = envelope(&mode, &minima);
+        let mean = (upper + lower) / 2.0;
+        let change = mean.mapv(|x| x * x).sum() / mode.mapv(|x| x * x).sum();
+        mode -= &mean;
+        if change < SIFT_TOLERANCE {
+            break;
+        }
+    }
+    Some(mode)
+}
+
+// Returns the indices of the interior local maxima and minima; the first sample of a
+// plateau counts as the extremum
+fn extrema(data: &Array1<f64>) -> (Vec<usize>, Vec<usize>) {
+    let (mut maxima, mut minima) = (Vec::new(), Vec::new());
+    for i in 1..data.len().saturating_sub(1) {
+        if data[i] > data[i - 1] && data[i] >= data[i + 1] {
+            maxima.push(i);
+        } else if data[i] < data[i - 1] && data[i] <= data[i + 1] {
+            minima.push(i);
+        }
+    }
+    (maxima, minima)
+}
+
+// Natural cubic spline through `data` at the given interior extrema, evaluated at every
+// sample; the ends are pinned to the value of the nearest extremum
+fn envelope(data: &Array1<f64>, indices: &[usize]) -> Array1<f64> {
+    let n = data.len();
+    let mut knots: Vec<(f64, f64)> = vec![(0.0, data[indices[0]])];
+    knots.extend(indices.iter().map(|&i| (i as f64, data[i])));
+    knots.push(((n - 1) as f64, data[indices[indices.len() - 1]]));
+
+    // Second derivatives at the knots, from the tridiagonal system solved by the Thomas
+    // algorithm with zero curvature at both ends
+    let m = knots.len();
+    let h: Vec<f64> = knots.windows(2).map(|w| w[1].0 - w[0].0).collect();
+    let mut curvature = vec![0.0; m];
+    let (mut diagonal, mut rhs) = (vec![0.0; m], vec![0.0; m]);
+    for i in 1..m - 1 {
+        let slope_change =
+            (knots[i + 1].1 - knots[i].1) / h[i] - (knots[i].1 - knots[i - 1].1) / h[i - 1];
+        diagonal[i] = 2.0 * (h[i - 1] + h[i]);
+        rhs[i] = 6.0 * slope_change;
+        if i > 1 {
+            let factor = h[i - 1] / diagonal[i - 1];
+            diagonal[i] -= factor * h[i - 1];
+            rhs[i] -= factor * rhs[i - 1];
+        }
+    }
+    for i in (1..m - 1).rev() {
+        curvature[i] = (rhs[i] - h[i] * curvature[i + 1]) / diagonal[i];
+    }
+
+    let mut interval = 0;
+    Array1::from_shape_fn(n, |t| {
+        let t = t as f64;
+        while interval < m - 2 && t > knots[interval + 1].0 {
+            interval += 1;
+        }
+        let ((x0, y0), (x1, y1)) = (knots[interval], knots[interval + 1]);
+        let width = h[interval];
+        let (a, b) = ((x1 - t) / width, (t - x0) / width);
+        a * y0
+            + b * y1
+            + ((a.powi(3) - a) * curvature[interval] + (b.powi(3) - b) * curvature[interval + 1])
+                * width
+                * width
+                / 6.0
+    })
+}
+
+// Analytic signal x + i H[x], by suppressing the negative frequencies of the data
+fn analytic_signal(data: &Array1<f64>) -> Vec<Complex64> {
+    let n = data.len();
+    let mut spectrum = rfft(data);
+    spectrum.resize(n, Complex64::new(0.0, 0.0));
+    for (k, value) in spectrum.iter_mut().enumerate().skip(1) {
+        // The Nyquist bin of even-length data is shared by both halves and is kept as is
+        if 2 * k < n {
+            *value *= 2.0;
+        }
+    }
+    ifft(&spectrum)
+}
+
+// --- Tests for empirical mode decomposition ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::testing::series;
+    use astronomy::units::METRE;
+    use ndarray::s;
+
+    #[test]
+    fn test_emd_separates_tones() {
+        let rate = 512.0;
+        let fast = Array1::from_shape_fn(1024, |i| (2.0 * PI * 40.0 * i as f64 / rate).sin());
+        let slow = Array1::from_shape_fn(1024, |i| 0.5 * (2.0 * PI * 4.0 * i as f64 / rate).sin());
+        let ts = series(&fast + &slow, 0.0, 1.0 / rate);
+        let modes = ts.emd(4).unwrap();
+        assert!(!modes.imfs.is_empty() && modes.imfs.len() <= 4);
+        assert_eq!(modes.imfs[0].unit(), &METRE);
+
+        // The decomposition is complete
+        let total = modes
+            .imfs
+            .iter()
+            .fold(modes.residual.value().clone(), |sum, imf| sum + imf.value());
+        for (a, b) in total.iter().zip(ts.value().iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        // The first mode is the fast tone, away from the end effects
+        let centre = s![256..768];
+        let error = (modes.imfs[0].value().slice(centre).to_owned() - fast.slice(centre))
+            .mapv(f64::abs)
+            .fold(0.0, |a: f64, &b| a.max(b));
+        assert!(error < 0.05, "{error}");
+        let frequencies = modes.instantaneous_frequencies().unwrap();
+        assert_eq!(frequencies[0].unit(), &HERTZ);
+        let mean = frequencies[0].value().slice(centre).mean().unwrap();
+        assert!((mean - 40.0).abs() < 0.5, "{mean}");
+    }
+
+    #[test]
+    fn test_instantaneous_frequency_of_tone() {
+        let rate = 256.0;
+        let tone = Array1::from_shape_fn(256, |i| 2.0 * (2.0 * PI * 10.0 * i as f64 / rate).cos());
+        let frequency = series(tone, 0.0, 1.0 / rate)
+            .instantaneous_frequency()
+            .unwrap();
+        for f in frequency.value().iter() {
+            assert!((f - 10.0).abs() < 1e-9, "{f}");
+        }
+        // A monotonic series has no modes
+        let modes = series(Array1::linspace(0.0, 1.0, 16), 0.0, 1.0)
+            .emd(3)
+            .unwrap();
+        assert!(modes.imfs.is_empty());
+    }
+}
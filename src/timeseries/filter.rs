@@ -0,0 +1,206 @@
+use crate::signal::filter::{
+    DigitalFilter, FilterError, butterworth_bandpass, butterworth_highpass, butterworth_lowpass,
+};
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::units::{HERTZ, Quantity, QuantityError};
+
+// -- Filtering methods for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Applies a digital filter causally, as `scipy.signal.sosfilt` would, starting from
+    /// rest. The filter is assumed to be designed for the sample rate of these data.
+    pub fn filter(&self, filter: &impl DigitalFilter) -> Result<TimeSeriesBase, FilterError> {
+        Ok(self.with_values(filter.to_sos().filter(self.value()))?)
+    }
+
+    /// Applies a digital filter forward and backward for zero phase shift, see
+    /// [`Sos::filtfilt`](crate::signal::filter::Sos::filtfilt).
+    pub fn filtfilt(&self, filter: &impl DigitalFilter) -> Result<TimeSeriesBase, FilterError> {
+        Ok(self.with_values(filter.to_sos().filtfilt(self.value()))?)
+    }
+
+    /// Band-pass filters the data between `flow` and `fhigh` with a Butterworth filter of the
+    /// given `order`, applied forward and backward for zero phase shift.
+    ///
+    /// The forward-backward pass squares the magnitude response, so the band edges are
+    /// attenuated by 6 dB rather than 3 dB. Unit, name, channel and time axis are kept.
+    pub fn bandpass(
+        &self,
+        flow: &Quantity,
+        fhigh: &Quantity,
+        order: usize,
+    ) -> Result<TimeSeriesBase, FilterError> {
+        let sample_rate = 1.0 / self.dt_seconds()?;
+        let sos = butterworth_bandpass(
+            order,
+            scalar_hertz(flow)?,
+            scalar_hertz(fhigh)?,
+            sample_rate,
+        )?;
+        Ok(self.with_values(sos.filtfilt(self.value()))?)
+    }
+
+    /// Suppresses content above `frequency` with a zero-phase Butterworth low-pass filter of the
+    /// given `order`, see [`bandpass`](Self::bandpass).
+    ///
+    /// Returns `FilterError::InvalidDesign` if `frequency` is not below the Nyquist frequency
+    /// `1 / (2 dt)`.
+    pub fn lowpass(
+        &self,
+        frequency: &Quantity,
+        order: usize,
+    ) -> Result<TimeSeriesBase, FilterError> {
+        let sample_rate = 1.0 / self.dt_seconds()?;
+        let sos = butterworth_lowpass(order, scalar_hertz(frequency)?, sample_rate)?;
+        Ok(self.with_values(sos.filtfilt(self.value()))?)
+    }
+
+    /// Suppresses content below `frequency` with a zero-phase Butterworth high-pass filter of
+    /// the given `order`, see [`bandpass`](Self::bandpass).
+    ///
+    /// Returns `FilterError::InvalidDesign` if `frequency` is not below the Nyquist frequency
+    /// `1 / (2 dt)`.
+    pub fn highpass(
+        &self,
+        frequency: &Quantity,
+        order: usize,
+    ) -> Result<TimeSeriesBase, FilterError> {
+        let sample_rate = 1.0 / self.dt_seconds()?;
+        let sos = butterworth_highpass(order, scalar_hertz(frequency)?, sample_rate)?;
+        Ok(self.with_values(sos.filtfilt(self.value()))?)
+    }
+}
+
+// Value in Hz of a scalar frequency quantity
+pub(crate) fn scalar_hertz(frequency: &Quantity) -> Result<f64, QuantityError> {
+    if frequency.value.len() != 1 {
+        return Err(QuantityError::InvalidQuantity(
+            "Filter frequencies must be scalar quantities.".to_string(),
+        ));
+    }
+    Ok(frequency.to(&HERTZ)?.value[0])
+}
+
+// --- Tests for the filtering methods ---
+#[cfg(test)]
+mod tests {
+    use crate::detector::channel::Channel;
+    use crate::signal::filter::{FilterError, Zpk};
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::{HERTZ, METRE, Quantity, SECOND};
+    use ndarray::{Array1, array};
+    use rustfft::num_complex::Complex64;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_bandpass_isolates_band_and_keeps_metadata() {
+        let rate = 256.0;
+        let tone = |frequency: f64, i: usize| (2.0 * PI * frequency * i as f64 / rate).sin();
+        let values = Array1::from_shape_fn(2048, |i| tone(2.0, i) + tone(40.0, i) + tone(110.0, i));
+        let channel = Channel::new("H1:TEST", Some(rate), None, None, None, None, None).unwrap();
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(values)
+            .unit(METRE)
+            .t0(1000.0)
+            .dt(Quantity::new(array![1.0 / rate], SECOND))
+            .name("Tones")
+            .channel(channel)
+            .build()
+            .unwrap();
+
+        let filtered = ts
+            .bandpass(
+                &Quantity::new(array![20.0], HERTZ),
+                &Quantity::new(array![70.0], HERTZ),
+                8,
+            )
+            .unwrap();
+
+        assert_eq!(filtered.unit(), &METRE);
+        assert_eq!(filtered.get_name(), Some("Tones"));
+        assert_eq!(filtered.get_epoch(), ts.get_epoch());
+        assert_eq!(filtered.get_dt(), ts.get_dt());
+        assert_eq!(filtered.get_channel().unwrap().get_name(), "H1:TEST");
+        // Only the 40 Hz tone survives, without a phase shift
+        for i in 256..1792 {
+            assert!((filtered.value()[i] - tone(40.0, i)).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_lowpass_and_highpass_split_tones() {
+        let rate = 128.0;
+        let slow = |i: usize| (2.0 * PI * 2.0 * i as f64 / rate).sin();
+        let fast = |i: usize| 0.5 * (2.0 * PI * 40.0 * i as f64 / rate).cos();
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(1024, |i| slow(i) + fast(i)))
+            .unit(METRE)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / rate], SECOND))
+            .build()
+            .unwrap();
+        let cutoff = Quantity::new(array![10.0], HERTZ);
+
+        let low = ts.lowpass(&cutoff, 6).unwrap();
+        let high = ts.highpass(&cutoff, 6).unwrap();
+
+        assert_eq!(low.unit(), &METRE);
+        for i in 128..896 {
+            assert!((low.value()[i] - slow(i)).abs() < 1e-2);
+            assert!((high.value()[i] - fast(i)).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_filter_with_user_defined_zpk() {
+        // Two-sample moving average: a zero at z = -1 and a pole at the origin
+        let average = Zpk::new(
+            vec![Complex64::new(-1.0, 0.0)],
+            vec![Complex64::new(0.0, 0.0)],
+            0.5,
+        )
+        .unwrap();
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![2.0, 4.0, 6.0, 8.0])
+            .unit(METRE)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0], SECOND))
+            .build()
+            .unwrap();
+
+        let filtered = ts.filter(&average).unwrap();
+
+        assert_eq!(filtered.value(), &array![1.0, 3.0, 5.0, 7.0]);
+        assert_eq!(filtered.unit(), &METRE);
+    }
+
+    #[test]
+    fn test_lowpass_rejects_cutoff_above_nyquist() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::zeros(64))
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / 64.0], SECOND))
+            .build()
+            .unwrap();
+        match ts.lowpass(&Quantity::new(array![40.0], HERTZ), 4) {
+            Err(FilterError::InvalidDesign(message)) => assert!(message.contains("Nyquist")),
+            other => panic!("expected an invalid design, got {other:?}"),
+        }
+        assert!(ts.highpass(&Quantity::new(array![32.0], HERTZ), 4).is_err());
+    }
+
+    #[test]
+    fn test_bandpass_rejects_band_above_nyquist() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::zeros(64))
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / 64.0], SECOND))
+            .build()
+            .unwrap();
+        let result = ts.bandpass(
+            &Quantity::new(array![10.0], HERTZ),
+            &Quantity::new(array![40.0], HERTZ),
+            4,
+        );
+        assert!(matches!(result, Err(FilterError::InvalidDesign(_))));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::signal::filter::FilterError;
+use crate::timeseries::core::TimeSeriesBase;
+use crate::timeseries::filter::scalar_hertz;
+use astronomy::units::{HERTZ, Quantity};
+use ndarray::array;
+
+// Each band is analysed at the lowest power-of-two decimation of the input that keeps the
+// Nyquist frequency above this multiple of its upper edge, clear of the anti-aliasing roll-off
+const NYQUIST_MARGIN: f64 = 8.0;
+
+/// One band of a [`constant_q_bands`](TimeSeriesBase::constant_q_bands) filter bank.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterBand {
+    // Centre and edge frequencies of the band, in Hz
+    pub center: f64,
+    pub low: f64,
+    pub high: f64,
+
+    // Band-limited data, at the decimated sample rate used for this band
+    pub data: TimeSeriesBase,
+}
+
+// -- Constant-Q filter banks for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Splits the data into fractional-octave bands with a time-domain filter bank.
+    ///
+    /// Band centres run up from `fmin` in steps of `2^(1 / bands_per_octave)` while they do
+    /// not exceed `fmax`, and every band spans `centre * 2^(±1 / (2 bands_per_octave))`,
+    /// giving a constant Q; `bands_per_octave` is 1 for octave and 3 for third-octave bands.
+    /// Working down from the highest band, the data are repeatedly halved in rate with
+    /// [`decimate`](Self::decimate) and each band is isolated with a zero-phase Butterworth
+    /// [`bandpass`](Self::bandpass) of the given `order` at the lowest rate still well above
+    /// its upper edge. This is far cheaper than a Q-transform for long-duration monitoring.
+    /// Bands are returned in increasing frequency.
+    pub fn constant_q_bands(
+        &self,
+        fmin: &Quantity,
+        fmax: &Quantity,
+        bands_per_octave: usize,
+        order: usize,
+    ) -> Result<Vec<FilterBand>, FilterError> {
+        let (fmin, fmax) = (scalar_hertz(fmin)?, scalar_hertz(fmax)?);
+        if bands_per_octave == 0 || fmin <= 0.0 || fmax < fmin {
+            return Err(FilterError::InvalidDesign(format!(
+                "Cannot build {bands_per_octave} bands per octave between {fmin} Hz and \
+                 {fmax} Hz"
+            )));
+        }
+        let ratio = 2.0_f64.powf(1.0 / bands_per_octave as f64);
+        let half_width = ratio.sqrt();
+        let count = ((fmax / fmin).ln() / ratio.ln() + 1e-9).floor() as i32 + 1;
+        let centers: Vec<f64> = (0..count).map(|k| fmin * ratio.powi(k)).collect();
+
+        let mut current = self.clone();
+        let mut rate = 1.0 / self.dt_seconds()?;
+        let mut bands = Vec::with_capacity(centers.len());
+        for &center in centers.iter().rev() {
+            let (low, high) = (center / half_width, center * half_width);
+            while rate / 2.0 >= NYQUIST_MARGIN * high && current.value().len() >= 2 {
+                current = current.decimate(2)?;
+                rate /= 2.0;
+            }
+            let data = current.bandpass(
+                &Quantity::new(array![low], HERTZ),
+                &Quantity::new(array![high], HERTZ),
+                order,
+            )?;
+            bands.push(FilterBand {
+                center,
+                low,
+                high,
+                data,
+            });
+        }
+        bands.reverse();
+        Ok(bands)
+    }
+}
+
+// --- Tests for constant-Q filter banks ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::{METRE, SECOND};
+    use ndarray::{Array1, s};
+    use std::f64::consts::PI;
+
+    // RMS over the central half of the data, away from filter edge effects
+    fn central_rms(ts: &TimeSeriesBase) -> f64 {
+        let n = ts.value().len();
+        let middle = ts.value().slice(s![n / 4..3 * n / 4]);
+        middle.mapv(|x| x * x).mean().unwrap().sqrt()
+    }
+
+    #[test]
+    fn test_octave_bands_separate_tones() {
+        let rate = 1024.0;
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(16384, |i| {
+                let t = i as f64 / rate;
+                (2.0 * PI * 10.0 * t).sin() + (2.0 * PI * 80.0 * t).sin()
+            }))
+            .unit(METRE)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / rate], SECOND))
+            .build()
+            .unwrap();
+        let bands = ts
+            .constant_q_bands(
+                &Quantity::new(array![5.0], HERTZ),
+                &Quantity::new(array![160.0], HERTZ),
+                1,
+                4,
+            )
+            .unwrap();
+
+        let centers: Vec<f64> = bands.iter().map(|b| b.center).collect();
+        assert_eq!(centers, vec![5.0, 10.0, 20.0, 40.0, 80.0, 160.0]);
+        assert!((bands[1].high / bands[1].low - 2.0).abs() < 1e-12);
+        // Lower bands run at lower rates, but always well above their upper edge
+        assert_eq!(bands[1].data.get_sample_rate().unwrap().value[0], 128.0);
+        assert_eq!(bands[5].data.get_sample_rate().unwrap().value[0], 1024.0);
+        assert_eq!(bands[1].data.unit(), &METRE);
+
+        let rms: Vec<f64> = bands.iter().map(|b| central_rms(&b.data)).collect();
+        assert!((rms[1] - 0.5_f64.sqrt()).abs() < 0.05, "{rms:?}");
+        assert!((rms[4] - 0.5_f64.sqrt()).abs() < 0.05, "{rms:?}");
+        assert!(rms[2] < 0.1 && rms[3] < 0.1, "{rms:?}");
+    }
+
+    #[test]
+    fn test_constant_q_bands_rejects_invalid_range() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::zeros(64))
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / 64.0], SECOND))
+            .build()
+            .unwrap();
+        let f = |value: f64| Quantity::new(array![value], HERTZ);
+        assert!(ts.constant_q_bands(&f(10.0), &f(5.0), 1, 4).is_err());
+        assert!(ts.constant_q_bands(&f(1.0), &f(4.0), 0, 4).is_err());
+        // The top band would reach above the Nyquist frequency
+        assert!(ts.constant_q_bands(&f(1.0), &f(32.0), 1, 4).is_err());
+    }
+}
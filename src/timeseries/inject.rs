@@ -0,0 +1,104 @@
+use crate::signal::filter::FilterError;
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::units::{HERTZ, Quantity, SECOND};
+use ndarray::{array, s};
+
+// -- Signal injection for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Adds `signal` into the data at its GPS position, returning the result.
+    ///
+    /// The signal is converted to the unit of the data and, if its sample rate differs, first
+    /// [`resampled`](Self::resample) to the rate of the data. Its first sample is added at
+    /// the data sample nearest its `t0`, so the two are aligned to within half a sample.
+    /// Any part of the signal outside the span of the data is dropped, and a signal entirely
+    /// outside it leaves the data unchanged.
+    pub fn inject(&self, signal: &TimeSeriesBase) -> Result<TimeSeriesBase, FilterError> {
+        let dt = self.dt_seconds()?;
+        let resampled;
+        let signal = if (signal.dt_seconds()? - dt).abs() > 1e-9 * dt {
+            resampled = signal.resample(&Quantity::new(array![1.0 / dt], HERTZ))?;
+            &resampled
+        } else {
+            signal
+        };
+        let scale = Quantity::new(array![1.0], signal.unit().clone())
+            .to(self.unit())?
+            .value[0];
+        let start_time = |ts: &TimeSeriesBase| -> Result<f64, FilterError> {
+            Ok(match ts.get_t0() {
+                Some(t0) => t0.to(&SECOND)?.value[0],
+                None => 0.0,
+            })
+        };
+        let offset = ((start_time(signal)? - start_time(self)?) / dt).round() as i64;
+
+        // Overlap of the signal with the data, in samples of the data
+        let n = self.value().len() as i64;
+        let first = offset.clamp(0, n);
+        let last = (offset + signal.value().len() as i64).clamp(0, n);
+        let mut values = self.value().clone();
+        if first < last {
+            let signal_range = (first - offset) as usize..(last - offset) as usize;
+            let mut target = values.slice_mut(s![first as usize..last as usize]);
+            target.scaled_add(scale, &signal.value().slice(s![signal_range]));
+        }
+        Ok(self.with_values(values)?)
+    }
+}
+
+// --- Tests for signal injection ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use crate::timeseries::testing::series;
+    use astronomy::units::CENTIMETRE;
+    use ndarray::Array1;
+
+    #[test]
+    fn test_inject_at_gps_offset() {
+        let data = series(Array1::ones(16), 100.0, 1.0 / 4.0);
+        let signal = TimeSeriesBaseBuilder::new()
+            .value(array![100.0, 200.0, 300.0])
+            .unit(CENTIMETRE)
+            .t0(101.01)
+            .dt(Quantity::new(array![0.25], SECOND))
+            .build()
+            .unwrap();
+        let injected = data.inject(&signal).unwrap();
+        let mut expected = Array1::ones(16);
+        expected.slice_mut(s![4..7]).assign(&array![2.0, 3.0, 4.0]);
+        assert_eq!(injected.value(), &expected);
+        assert_eq!(injected.get_t0(), data.get_t0());
+
+        // Only the overlapping part of the signal is added
+        let early = series(array![5.0, 5.0, 5.0], 99.5, 1.0 / 4.0);
+        let injected = data.inject(&early).unwrap();
+        assert_eq!(injected.value().slice(s![..2]), array![6.0, 1.0]);
+        let outside = series(array![5.0], 200.0, 1.0 / 4.0);
+        assert_eq!(data.inject(&outside).unwrap().value(), data.value());
+    }
+
+    #[test]
+    fn test_inject_resamples_signal() {
+        let data = series(Array1::zeros(64), 0.0, 1.0 / 16.0);
+        let signal = series(Array1::ones(128), 1.0, 1.0 / 64.0);
+        let injected = data.inject(&signal).unwrap();
+        // Away from the filter edges the resampled constant is preserved
+        for (i, x) in injected.value().iter().enumerate() {
+            match i {
+                ..16 => assert_eq!(*x, 0.0),
+                28..36 => assert!((x - 1.0).abs() < 1e-2, "{i} {x}"),
+                _ => {}
+            }
+        }
+        let incompatible = TimeSeriesBaseBuilder::new()
+            .value(Array1::ones(4))
+            .unit(SECOND)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / 16.0], SECOND))
+            .build()
+            .unwrap();
+        assert!(data.inject(&incompatible).is_err());
+    }
+}
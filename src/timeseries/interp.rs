@@ -0,0 +1,143 @@
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::time::Time;
+use astronomy::units::{Quantity, QuantityError, SECOND};
+use ndarray::array;
+
+/// How [`value_at`](TimeSeriesBase::value_at) estimates the data between samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interp {
+    /// The value of the closest sample
+    Nearest,
+    /// Straight-line interpolation between the two neighbouring samples
+    #[default]
+    Linear,
+    /// The cubic through the two samples either side, shifted inwards at the ends
+    Cubic,
+}
+
+// -- Interpolated sampling of TimeSeriesBase
+impl TimeSeriesBase {
+    /// Returns the data at GPS time `t`, interpolated between samples with `method`, in the
+    /// unit of the data.
+    ///
+    /// `t` must lie between the first and last sample times, and cubic interpolation needs
+    /// at least four samples. Both regularly sampled series and those with an explicit time
+    /// index are supported; at a sample time every method returns that sample.
+    pub fn value_at(&self, t: Time, method: Interp) -> Result<Quantity, QuantityError> {
+        let times = self.get_times().ok_or_else(|| {
+            QuantityError::InvalidQuantity(
+                "Cannot interpolate a series without a time axis".to_string(),
+            )
+        })?;
+        let times = times.to(&SECOND)?.value.to_vec();
+        let values = self.value();
+        let n = times.len();
+        let at = t.as_gps_seconds_f64();
+        if n == 0 || at < times[0] || at > times[n - 1] {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "Time {at} is outside the span of the data"
+            )));
+        }
+
+        // Index of the sample at or before `at`, kept below the last so `upper` exists
+        let lower = times
+            .partition_point(|&time| time <= at)
+            .saturating_sub(1)
+            .min(n.saturating_sub(2));
+        let upper = (lower + 1).min(n - 1);
+        let value = match method {
+            Interp::Nearest => {
+                if upper != lower && at - times[lower] > times[upper] - at {
+                    values[upper]
+                } else {
+                    values[lower]
+                }
+            }
+            Interp::Linear => {
+                if upper == lower {
+                    values[lower]
+                } else {
+                    let fraction = (at - times[lower]) / (times[upper] - times[lower]);
+                    values[lower] + fraction * (values[upper] - values[lower])
+                }
+            }
+            Interp::Cubic => {
+                if n < 4 {
+                    return Err(QuantityError::InvalidQuantity(format!(
+                        "Cubic interpolation needs at least 4 samples, found {n}"
+                    )));
+                }
+                // Lagrange polynomial through four consecutive samples
+                let first = lower.saturating_sub(1).min(n - 4);
+                (first..first + 4)
+                    .map(|i| {
+                        let basis: f64 = (first..first + 4)
+                            .filter(|&j| j != i)
+                            .map(|j| (at - times[j]) / (times[i] - times[j]))
+                            .product();
+                        values[i] * basis
+                    })
+                    .sum()
+            }
+        };
+        Ok(Quantity::new(array![value], self.unit().clone()))
+    }
+}
+
+// --- Tests for interpolated sampling ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::METRE;
+    use ndarray::Array1;
+
+    #[test]
+    fn test_value_at() {
+        // Samples of x³ - x at t = 100 + x for x = 0..6
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(6, |i| {
+                let x = i as f64;
+                x * x * x - x
+            }))
+            .unit(METRE)
+            .t0(100.0)
+            .dt(Quantity::new(array![1.0], SECOND))
+            .build()
+            .unwrap();
+        let at = |t: f64, method| ts.value_at(Time::from_gps_seconds(t), method).unwrap();
+
+        assert_eq!(at(102.4, Interp::Nearest).value[0], 6.0);
+        assert_eq!(at(102.6, Interp::Nearest).value[0], 24.0);
+        assert_eq!(
+            at(102.5, Interp::Linear),
+            Quantity::new(array![15.0], METRE)
+        );
+        assert_eq!(at(105.0, Interp::Linear).value[0], 120.0);
+        // The cubic is reproduced exactly, including next to the ends
+        for t in [100.25, 102.5, 104.75] {
+            let x = t - 100.0;
+            assert!((at(t, Interp::Cubic).value[0] - (x * x * x - x)).abs() < 1e-9);
+        }
+        for method in [Interp::Nearest, Interp::Linear, Interp::Cubic] {
+            assert_eq!(at(103.0, method).value[0], 24.0);
+            assert!(ts.value_at(Time::from_gps_seconds(99.9), method).is_err());
+            assert!(ts.value_at(Time::from_gps_seconds(105.1), method).is_err());
+        }
+
+        let irregular = TimeSeriesBaseBuilder::new()
+            .value(array![0.0, 10.0, 40.0])
+            .times(Quantity::new(array![0.0, 1.0, 4.0], SECOND))
+            .build()
+            .unwrap();
+        let value = irregular
+            .value_at(Time::from_gps_seconds(2.5), Interp::Linear)
+            .unwrap();
+        assert_eq!(value.value[0], 25.0);
+        assert!(
+            irregular
+                .value_at(Time::from_gps_seconds(2.5), Interp::Cubic)
+                .is_err()
+        );
+    }
+}
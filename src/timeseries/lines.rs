@@ -0,0 +1,247 @@
+use crate::signal::linalg::solve;
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use astronomy::units::{HERTZ, Quantity, QuantityError, SECOND, Unit, UnitProduct};
+use ndarray::{Array1, Array2, array, s};
+use std::f64::consts::PI;
+
+/// The result of [`subtract_lines`](TimeSeriesBase::subtract_lines): the cleaned data and
+/// the line model removed from them, so that `cleaned + model` restores the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineSubtraction {
+    pub cleaned: TimeSeriesBase,
+    pub model: TimeSeriesBase,
+}
+
+/// The result of [`track_line`](TimeSeriesBase::track_line): the amplitude and phase of a
+/// line over time, one sample per stride.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineTrack {
+    // Amplitude in the unit of the data, and phase in radians in (-pi, pi]
+    pub amplitude: TimeSeriesBase,
+    pub phase: TimeSeriesBase,
+}
+
+// -- Spectral line subtraction and tracking for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Removes persistent sinusoidal lines at the given `frequencies` by least-squares fitting.
+    ///
+    /// The amplitude and phase of every line are fitted jointly, so closely spaced lines do
+    /// not bias each other. With a `stride` the fit is repeated independently over
+    /// consecutive blocks of that duration, which follows lines whose amplitude or phase
+    /// wanders slowly; without one a single fit spans the whole series.
+    pub fn subtract_lines(
+        &self,
+        frequencies: &Quantity,
+        stride: Option<&Quantity>,
+    ) -> Result<LineSubtraction, QuantityError> {
+        let dt = self.dt_seconds()?;
+        let frequencies = frequencies.to(&HERTZ)?.value;
+        let nyquist = 0.5 / dt;
+        if frequencies.is_empty() || frequencies.iter().any(|&f| f <= 0.0 || f >= nyquist) {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "Line frequencies must be non-empty and lie in (0, {nyquist}) Hz"
+            )));
+        }
+        let n = self.value().len();
+        let block = match stride {
+            Some(stride) => {
+                if stride.value.len() != 1 || stride.value[0] <= 0.0 {
+                    return Err(QuantityError::InvalidQuantity(
+                        "stride must be a positive scalar quantity.".to_string(),
+                    ));
+                }
+                ((stride.to(&SECOND)?.value[0] / dt).round() as usize).max(1)
+            }
+            None => n.max(1),
+        };
+
+        let mut model = Array1::<f64>::zeros(n);
+        for start in (0..n).step_by(block) {
+            let end = (start + block).min(n);
+            // Cosine and sine of each line at each sample, timed from the start of the data
+            let basis = Array2::from_shape_fn((end - start, 2 * frequencies.len()), |(i, k)| {
+                let phase = 2.0 * PI * frequencies[k / 2] * (start + i) as f64 * dt;
+                if k % 2 == 0 { phase.cos() } else { phase.sin() }
+            });
+            let data = self.value().slice(s![start..end]);
+            let coefficients =
+                solve(basis.t().dot(&basis), basis.t().dot(&data)).ok_or_else(|| {
+                    QuantityError::InvalidQuantity(format!(
+                        "Lines cannot be separated over the {} samples starting at index {start}",
+                        end - start
+                    ))
+                })?;
+            model
+                .slice_mut(s![start..end])
+                .assign(&basis.dot(&coefficients));
+        }
+
+        let cleaned = self.value() - &model;
+        Ok(LineSubtraction {
+            cleaned: self.with_values(cleaned)?,
+            model: self.with_values(model)?,
+        })
+    }
+
+    /// Demodulates the line at `frequency`, returning its amplitude and phase over each
+    /// consecutive `stride` of the data, e.g. to monitor the stability of a calibration line.
+    ///
+    /// Over every stride the data are fitted by least squares with `A cos(2 pi f t + phi)`,
+    /// with `t` timed from the start of the data, so a stable line has a constant phase and
+    /// strides need not span whole cycles. Each sample of the result is stamped at the start
+    /// of its stride, and a trailing partial stride is dropped. The stride must hold at least
+    /// two samples.
+    pub fn track_line(
+        &self,
+        frequency: &Quantity,
+        stride: &Quantity,
+    ) -> Result<LineTrack, QuantityError> {
+        if frequency.value.len() != 1 || stride.value.len() != 1 {
+            return Err(QuantityError::InvalidQuantity(
+                "frequency and stride must be scalar quantities.".to_string(),
+            ));
+        }
+        let dt = self.dt_seconds()?;
+        let frequency = frequency.to(&HERTZ)?.value[0];
+        let nyquist = 0.5 / dt;
+        if frequency <= 0.0 || frequency >= nyquist {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "The line frequency must lie in (0, {nyquist}) Hz"
+            )));
+        }
+        let block = (stride.to(&SECOND)?.value[0] / dt).round() as usize;
+        let count = self.value().len() / block.max(1);
+        if block < 2 || count == 0 {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "The stride must hold at least two samples and fit within the {} samples",
+                self.value().len()
+            )));
+        }
+
+        let mut amplitude = Array1::zeros(count);
+        let mut phase = Array1::zeros(count);
+        for j in 0..count {
+            let start = j * block;
+            let basis = Array2::from_shape_fn((block, 2), |(i, k)| {
+                let phase = 2.0 * PI * frequency * (start + i) as f64 * dt;
+                if k == 0 { phase.cos() } else { phase.sin() }
+            });
+            let data = self.value().slice(s![start..start + block]);
+            let coefficients =
+                solve(basis.t().dot(&basis), basis.t().dot(&data)).ok_or_else(|| {
+                    QuantityError::InvalidQuantity(format!(
+                        "The line cannot be fitted over the stride starting at index {start}"
+                    ))
+                })?;
+            // a cos + b sin = A cos(x + phi) with A = hypot(a, b) and phi = atan2(-b, a)
+            amplitude[j] = coefficients[0].hypot(coefficients[1]);
+            phase[j] = (-coefficients[1]).atan2(coefficients[0]);
+        }
+        let build = |values: Array1<f64>, unit: Unit| {
+            TimeSeriesBaseBuilder::like(self)
+                .value(values)
+                .unit(unit)
+                .dt(Quantity::new(array![block as f64 * dt], SECOND))
+                .build()
+        };
+        Ok(LineTrack {
+            amplitude: build(amplitude, self.unit().clone())?,
+            phase: build(phase, Unit::new("rad", 1.0, UnitProduct::zero()))?,
+        })
+    }
+}
+
+// --- Tests for line subtraction ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use ndarray::array;
+
+    fn sampled(n: usize, dt: f64, f: impl Fn(f64) -> f64) -> TimeSeriesBase {
+        TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(n, |i| f(i as f64 * dt)))
+            .t0(1_000_000_000.0)
+            .dt(Quantity::new(array![dt], SECOND))
+            .name("X1:STRAIN")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_subtract_lines() {
+        let signal = |t: f64| 0.3 * (2.0 * PI * 7.0 * t).sin();
+        let ts = sampled(2048, 1.0 / 512.0, |t| {
+            signal(t) + 2.0 * (2.0 * PI * 60.0 * t + 0.4).cos() + 1.5 * (2.0 * PI * 120.5 * t).sin()
+        });
+        let frequencies = Quantity::new(array![60.0, 120.5], HERTZ);
+        let result = ts.subtract_lines(&frequencies, None).unwrap();
+        for (i, x) in result.cleaned.value().iter().enumerate() {
+            assert!((x - signal(i as f64 / 512.0)).abs() < 1e-9);
+        }
+        assert_eq!(result.model.get_name(), Some("X1:STRAIN"));
+        assert_eq!(result.cleaned.get_t0(), ts.get_t0());
+    }
+
+    #[test]
+    fn test_subtract_lines_tracks_amplitude_per_stride() {
+        // The line doubles in amplitude half way through, which one global fit cannot follow
+        let ts = sampled(1024, 1.0 / 256.0, |t| {
+            let amplitude = if t < 2.0 { 1.0 } else { 2.0 };
+            amplitude * (2.0 * PI * 50.0 * t).sin()
+        });
+        let frequencies = Quantity::new(array![50.0], HERTZ);
+        let stride = Quantity::new(array![2.0], SECOND);
+        let result = ts.subtract_lines(&frequencies, Some(&stride)).unwrap();
+        assert!(result.cleaned.value().iter().all(|x| x.abs() < 1e-9));
+
+        let global = ts.subtract_lines(&frequencies, None).unwrap();
+        assert!(global.cleaned.value().iter().any(|x| x.abs() > 0.1));
+    }
+
+    #[test]
+    fn test_track_line() {
+        // A calibration line whose amplitude ramps up and whose phase steps after 3 s
+        let ts = sampled(2048, 1.0 / 256.0, |t| {
+            let phase = if t < 3.0 { 0.5 } else { -2.0 };
+            (1.0 + 0.1 * t.floor()) * (2.0 * PI * 33.3 * t + phase).cos()
+        });
+        let track = ts
+            .track_line(
+                &Quantity::new(array![33.3], HERTZ),
+                &Quantity::new(array![1.0], SECOND),
+            )
+            .unwrap();
+        assert_eq!(track.amplitude.value().len(), 8);
+        assert_eq!(track.phase.get_t0(), ts.get_t0());
+        assert_eq!(track.phase.get_dt().unwrap().value[0], 1.0);
+        assert_eq!(track.amplitude.get_name(), Some("X1:STRAIN"));
+        assert_eq!(track.phase.unit().dimensions, UnitProduct::zero());
+        for j in 0..8 {
+            let expected = if j < 3 { 0.5 } else { -2.0 };
+            assert!((track.amplitude.value()[j] - (1.0 + 0.1 * j as f64)).abs() < 1e-9);
+            assert!((track.phase.value()[j] - expected).abs() < 1e-9);
+        }
+
+        // Strides longer than the data and frequencies above Nyquist are rejected
+        let frequency = Quantity::new(array![33.3], HERTZ);
+        let stride = Quantity::new(array![1.0], SECOND);
+        assert!(
+            ts.track_line(&frequency, &Quantity::new(array![10.0], SECOND))
+                .is_err()
+        );
+        assert!(
+            ts.track_line(&Quantity::new(array![200.0], HERTZ), &stride)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_subtract_lines_rejects_invalid_frequencies() {
+        let ts = sampled(64, 1.0 / 16.0, |t| t);
+        let above_nyquist = Quantity::new(array![8.0], HERTZ);
+        assert!(ts.subtract_lines(&above_nyquist, None).is_err());
+        let duplicated = Quantity::new(array![3.0, 3.0], HERTZ);
+        assert!(ts.subtract_lines(&duplicated, None).is_err());
+    }
+}
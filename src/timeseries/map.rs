@@ -0,0 +1,95 @@
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::units::{QuantityError, Unit};
+use ndarray::Array1;
+
+// -- Element-wise and whole-series transforms for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Applies `f` to every sample, keeping the unit, name, channel and time axis.
+    pub fn map<F: Fn(f64) -> f64>(&self, f: F) -> Result<TimeSeriesBase, QuantityError> {
+        self.with_values(self.value().mapv(f))
+    }
+
+    /// Applies `f` to every sample and labels the result with `unit`, keeping the name,
+    /// channel and time axis.
+    ///
+    /// Use this for transforms that change the dimensions of the data, such as squaring a
+    /// strain series; `f` is responsible for any scaling the new unit implies.
+    pub fn map_with_unit<F: Fn(f64) -> f64>(
+        &self,
+        f: F,
+        unit: Unit,
+    ) -> Result<TimeSeriesBase, QuantityError> {
+        self.with_values_in(self.value().mapv(f), unit)
+    }
+
+    /// Applies `f` to the whole array of samples at once, keeping the unit, name, channel
+    /// and time axis.
+    ///
+    /// This suits transforms where each output sample depends on its neighbours, such as
+    /// running filters. `f` must return as many samples as it is given.
+    pub fn apply_along_time<F: FnOnce(&Array1<f64>) -> Array1<f64>>(
+        &self,
+        f: F,
+    ) -> Result<TimeSeriesBase, QuantityError> {
+        let values = f(self.value());
+        if values.len() != self.value().len() {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "apply_along_time must keep the number of samples: {} became {}",
+                self.value().len(),
+                values.len()
+            )));
+        }
+        self.with_values(values)
+    }
+}
+
+// --- Tests for map and apply ---
+#[cfg(test)]
+mod tests {
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::{METRE, Quantity, SECOND};
+    use ndarray::{Zip, array, s};
+
+    #[test]
+    fn test_map_keeps_metadata() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, -2.0, 3.0, -4.0])
+            .unit(METRE)
+            .name("X1:TEST")
+            .t0(100.0)
+            .dt(Quantity::new(array![0.5], SECOND))
+            .build()
+            .unwrap();
+
+        let doubled = ts.map(|x| 2.0 * x).unwrap();
+        assert_eq!(doubled.value(), &array![2.0, -4.0, 6.0, -8.0]);
+        assert_eq!(doubled.unit(), &METRE);
+        assert_eq!(doubled.get_name(), Some("X1:TEST"));
+        assert_eq!(doubled.get_t0(), ts.get_t0());
+        assert_eq!(doubled.get_dt(), ts.get_dt());
+
+        let square = (Quantity::new(array![1.0], METRE) * Quantity::new(array![1.0], METRE)).unit;
+        let power = ts.map_with_unit(|x| x * x, square.clone()).unwrap();
+        assert_eq!(power.value(), &array![1.0, 4.0, 9.0, 16.0]);
+        assert_eq!(power.unit(), &square);
+        assert_eq!(power.get_t0(), ts.get_t0());
+
+        // A running difference, with the first sample kept
+        let differenced = ts
+            .apply_along_time(|x| {
+                let mut out = x.clone();
+                Zip::from(out.slice_mut(s![1..]))
+                    .and(x.slice(s![1..]))
+                    .and(x.slice(s![..-1]))
+                    .for_each(|o, &a, &b| *o = a - b);
+                out
+            })
+            .unwrap();
+        assert_eq!(differenced.value(), &array![1.0, -3.0, 5.0, -7.0]);
+        assert_eq!(differenced.get_name(), Some("X1:TEST"));
+        assert!(
+            ts.apply_along_time(|x| x.slice(s![1..]).to_owned())
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::units::{Quantity, QuantityError, SECOND};
+
+/// A local maximum found by [`find_peaks`](TimeSeriesBase::find_peaks).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Peak {
+    // Sample index, GPS time in seconds and value, in the unit of the data
+    pub index: usize,
+    pub time: f64,
+    pub amplitude: f64,
+}
+
+// -- Peak finding for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Finds the local maxima of the data that reach `threshold`, keeping only the highest
+    /// within any `min_separation` of time.
+    ///
+    /// A peak is a sample greater than its predecessor and not less than its successor; the
+    /// first sample of a flat top counts, and the end samples never do. Peaks are taken in
+    /// decreasing amplitude, each discarding the weaker peaks closer to it than
+    /// `min_separation`, as in `scipy.signal.find_peaks`. The threshold is converted to the
+    /// unit of the data, so pass the absolute value of the data to find troughs as well.
+    /// Peaks are returned in time order.
+    pub fn find_peaks(
+        &self,
+        threshold: &Quantity,
+        min_separation: &Quantity,
+    ) -> Result<Vec<Peak>, QuantityError> {
+        if threshold.value.len() != 1 || min_separation.value.len() != 1 {
+            return Err(QuantityError::InvalidQuantity(
+                "threshold and min_separation must be scalar quantities.".to_string(),
+            ));
+        }
+        let threshold = threshold.to(self.unit())?.value[0];
+        let separation = min_separation.to(&SECOND)?.value[0];
+        let times = self.get_times().ok_or_else(|| {
+            QuantityError::InvalidQuantity(
+                "Cannot find peaks in a series without a time axis".to_string(),
+            )
+        })?;
+        let times = times.to(&SECOND)?.value;
+
+        let x = self.value();
+        let mut candidates: Vec<Peak> = (1..x.len().saturating_sub(1))
+            .filter(|&i| x[i] > x[i - 1] && x[i] >= x[i + 1] && x[i] >= threshold)
+            .map(|i| Peak {
+                index: i,
+                time: times[i],
+                amplitude: x[i],
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.amplitude.total_cmp(&a.amplitude));
+        let mut peaks: Vec<Peak> = Vec::new();
+        for candidate in candidates {
+            if peaks
+                .iter()
+                .all(|peak| (peak.time - candidate.time).abs() >= separation)
+            {
+                peaks.push(candidate);
+            }
+        }
+        peaks.sort_by_key(|peak| peak.index);
+        Ok(peaks)
+    }
+}
+
+// --- Tests for peak finding ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::{CENTIMETRE, METRE};
+    use ndarray::array;
+
+    #[test]
+    fn test_find_peaks() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![
+                5.0, 1.0, 3.0, 2.0, 4.0, 4.0, 0.0, 2.5, 1.0, 0.5, 6.0
+            ])
+            .unit(METRE)
+            .t0(100.0)
+            .dt(Quantity::new(array![0.5], SECOND))
+            .build()
+            .unwrap();
+        let peaks = ts
+            .find_peaks(
+                &Quantity::new(array![200.0], CENTIMETRE),
+                &Quantity::new(array![0.0], SECOND),
+            )
+            .unwrap();
+        let found: Vec<(usize, f64)> = peaks.iter().map(|p| (p.index, p.amplitude)).collect();
+        assert_eq!(found, vec![(2, 3.0), (4, 4.0), (7, 2.5)]);
+        assert_eq!(peaks[1].time, 102.0);
+
+        // The strongest peak suppresses its weaker neighbours
+        let peaks = ts
+            .find_peaks(
+                &Quantity::new(array![2.0], METRE),
+                &Quantity::new(array![1.5], SECOND),
+            )
+            .unwrap();
+        let found: Vec<usize> = peaks.iter().map(|p| p.index).collect();
+        assert_eq!(found, vec![4, 7]);
+        assert!(
+            ts.find_peaks(
+                &Quantity::new(array![2.0], SECOND),
+                &Quantity::new(array![1.0], SECOND)
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use crate::signal::filter::{FilterError, fir_lowpass};
+use crate::signal::window::Window;
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use astronomy::units::{HERTZ, Quantity, SECOND};
+use ndarray::{Array1, array};
+
+// Half-length of the anti-aliasing filter in units of the larger resampling factor, and the
+// Kaiser window shape, both as in `scipy.signal.resample_poly`
+const FILTER_HALF_LENGTH: usize = 10;
+const KAISER_BETA: f64 = 5.0;
+
+// Largest denominator tried when expressing the rate ratio as a fraction
+const MAX_FACTOR: u64 = 1 << 16;
+
+// Relative accuracy required of that fraction
+const RATIO_TOLERANCE: f64 = 1e-12;
+
+// -- Resampling for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Resamples the data to a new sample `rate` by polyphase filtering.
+    ///
+    /// The ratio of the new to the old rate is expressed as a reduced fraction `up / down`;
+    /// the data are upsampled by `up`, low-passed at the lower of the two Nyquist frequencies
+    /// with a Kaiser-windowed FIR filter, and downsampled by `down`, as in
+    /// `scipy.signal.resample_poly`. Integer decimation and interpolation are the special
+    /// cases `up = 1` and `down = 1`. The filter is compensated for its delay, so the output
+    /// keeps `t0`; `dt` and the times are recomputed for the new rate.
+    pub fn resample(&self, rate: &Quantity) -> Result<TimeSeriesBase, FilterError> {
+        if rate.value.len() != 1 {
+            return Err(FilterError::InvalidDesign(
+                "The sample rate must be a scalar quantity".to_string(),
+            ));
+        }
+        let new_rate = rate.to(&HERTZ)?.value[0];
+        let old_rate = 1.0 / self.dt_seconds()?;
+        if new_rate <= 0.0 {
+            return Err(FilterError::InvalidDesign(format!(
+                "The sample rate ({new_rate} Hz) must be positive"
+            )));
+        }
+        let (up, down) = rational_ratio(new_rate / old_rate).ok_or_else(|| {
+            FilterError::InvalidDesign(format!(
+                "Cannot express the resampling ratio {new_rate} Hz / {old_rate} Hz as a \
+                 fraction with factors up to {MAX_FACTOR}"
+            ))
+        })?;
+        self.polyphase(up, down)
+    }
+
+    /// Downsamples the data by an integer `factor`, low-passing them first to prevent
+    /// aliasing.
+    ///
+    /// This is [`resample`](Self::resample) with `up = 1`: the anti-aliasing filter is a
+    /// zero-delay Kaiser-windowed FIR cutting off at the new Nyquist frequency. A factor of
+    /// one returns a copy of the data.
+    pub fn decimate(&self, factor: usize) -> Result<TimeSeriesBase, FilterError> {
+        if factor == 0 {
+            return Err(FilterError::InvalidDesign(
+                "The decimation factor must be at least 1".to_string(),
+            ));
+        }
+        self.polyphase(1, factor)
+    }
+
+    // Resamples by the rational factor up / down with the polyphase FIR scheme above
+    fn polyphase(&self, up: usize, down: usize) -> Result<TimeSeriesBase, FilterError> {
+        let old_rate = 1.0 / self.dt_seconds()?;
+        let new_rate = old_rate * up as f64 / down as f64;
+        let values = if up == down {
+            self.value().clone()
+        } else {
+            let factor = up.max(down);
+            let taps = fir_lowpass(
+                2 * FILTER_HALF_LENGTH * factor + 1,
+                old_rate * up as f64 / (2.0 * factor as f64),
+                old_rate * up as f64,
+                Window::Kaiser(KAISER_BETA),
+            )?;
+            upfirdn(&(taps * up as f64), self.value(), up, down)
+        };
+
+        let mut builder = TimeSeriesBaseBuilder::new()
+            .value(values)
+            .unit(self.unit().clone())
+            .dt(Quantity::new(array![1.0 / new_rate], SECOND));
+        if let Some(t0) = self.get_t0() {
+            builder = builder.t0(t0.to(&SECOND)?.value[0]);
+        }
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        Ok(builder.build()?)
+    }
+}
+
+// Best fraction up / down approximating `ratio`, from its continued-fraction expansion
+fn rational_ratio(ratio: f64) -> Option<(usize, usize)> {
+    let (mut h_prev, mut h) = (0_u64, 1_u64);
+    let (mut k_prev, mut k) = (1_u64, 0_u64);
+    let mut remainder = ratio;
+    loop {
+        let term = remainder.floor();
+        let next_h = (term as u64).checked_mul(h)?.checked_add(h_prev)?;
+        let next_k = (term as u64).checked_mul(k)?.checked_add(k_prev)?;
+        if next_h > MAX_FACTOR || next_k > MAX_FACTOR {
+            return None;
+        }
+        (h_prev, h, k_prev, k) = (h, next_h, k, next_k);
+        if (h as f64 / k as f64 - ratio).abs() <= RATIO_TOLERANCE * ratio {
+            return Some((h as usize, k as usize));
+        }
+        remainder = 1.0 / (remainder - term);
+    }
+}
+
+// Upsamples `data` by zero insertion, applies the FIR filter `taps` and keeps every
+// `down`-th sample, shifted by the filter delay so the output is aligned with the input
+fn upfirdn(taps: &Array1<f64>, data: &Array1<f64>, up: usize, down: usize) -> Array1<f64> {
+    let delay = (taps.len() - 1) / 2;
+    let n_out = (data.len() * up).div_ceil(down);
+    Array1::from_shape_fn(n_out, |m| {
+        // Output sample m sits at position m down + delay of the filtered upsampled stream
+        let position = m * down + delay;
+        let first = position.saturating_sub(taps.len() - 1).div_ceil(up);
+        let last = (position / up).min(data.len() - 1);
+        (first..=last)
+            .map(|j| data[j] * taps[position - j * up])
+            .sum()
+    })
+}
+
+// --- Tests for resampling ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use astronomy::units::METRE;
+    use std::f64::consts::PI;
+
+    fn tones(rate: f64, n: usize, frequencies: &[f64]) -> TimeSeriesBase {
+        TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(n, |i| {
+                frequencies
+                    .iter()
+                    .map(|f| (2.0 * PI * f * i as f64 / rate).sin())
+                    .sum()
+            }))
+            .unit(METRE)
+            .t0(1000.0)
+            .dt(Quantity::new(array![1.0 / rate], SECOND))
+            .name("Tones")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_integer_decimation_removes_aliases() {
+        let ts = tones(256.0, 2048, &[5.0, 100.0]);
+        let resampled = ts.resample(&Quantity::new(array![64.0], HERTZ)).unwrap();
+
+        assert_eq!(resampled.value().len(), 512);
+        assert_eq!(resampled.get_dt().unwrap().value[0], 1.0 / 64.0);
+        assert_eq!(resampled.get_sample_rate().unwrap().value[0], 64.0);
+        assert_eq!(resampled.get_t0(), ts.get_t0());
+        assert_eq!(resampled.unit(), &METRE);
+        assert_eq!(resampled.get_name(), Some("Tones"));
+        // The 100 Hz tone would alias to 28 Hz without the anti-aliasing filter
+        for m in 64..448 {
+            let expected = (2.0 * PI * 5.0 * m as f64 / 64.0).sin();
+            assert!((resampled.value()[m] - expected).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_rational_resampling() {
+        let ts = tones(64.0, 640, &[3.0]);
+        let resampled = ts.resample(&Quantity::new(array![96.0], HERTZ)).unwrap();
+        assert_eq!(resampled.value().len(), 960);
+        for m in 96..864 {
+            let expected = (2.0 * PI * 3.0 * m as f64 / 96.0).sin();
+            assert!((resampled.value()[m] - expected).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_decimate() {
+        let ts = tones(1024.0, 4096, &[10.0, 300.0]);
+        let decimated = ts.decimate(16).unwrap();
+        assert_eq!(decimated.value().len(), 256);
+        assert_eq!(decimated.get_sample_rate().unwrap().value[0], 64.0);
+        assert_eq!(
+            decimated,
+            ts.resample(&Quantity::new(array![64.0], HERTZ)).unwrap()
+        );
+        for m in 32..224 {
+            let expected = (2.0 * PI * 10.0 * m as f64 / 64.0).sin();
+            assert!((decimated.value()[m] - expected).abs() < 1e-2);
+        }
+        assert_eq!(ts.decimate(1).unwrap().value(), ts.value());
+        assert!(matches!(ts.decimate(0), Err(FilterError::InvalidDesign(_))));
+    }
+
+    #[test]
+    fn test_rational_ratio() {
+        assert_eq!(rational_ratio(0.25), Some((1, 4)));
+        assert_eq!(rational_ratio(1.5), Some((3, 2)));
+        assert_eq!(rational_ratio(16384.0 / 44100.0), Some((4096, 11025)));
+        assert_eq!(rational_ratio(PI), None);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::units::{Quantity, QuantityError, SECOND};
+use ndarray::Array1;
+
+/// Moving-window statistics of a series, made by [`rolling`](TimeSeriesBase::rolling).
+///
+/// Each statistic is taken over the samples within half a window either side of each
+/// sample, and is returned on the time axis of the data. Near the ends the window is
+/// truncated to the samples available, so every output sample is defined.
+#[derive(Debug, Clone, Copy)]
+pub struct Rolling<'a> {
+    series: &'a TimeSeriesBase,
+    // Samples either side of the centre of each window
+    half_width: usize,
+}
+
+// -- Rolling statistics for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Returns moving-window statistics over windows of duration `window`, rounded to an
+    /// odd number of samples centred on each sample.
+    pub fn rolling(&self, window: &Quantity) -> Result<Rolling<'_>, QuantityError> {
+        if window.value.len() != 1 {
+            return Err(QuantityError::InvalidQuantity(
+                "window must be a scalar quantity.".to_string(),
+            ));
+        }
+        let samples = (window.to(&SECOND)?.value[0] / self.dt_seconds()?).round();
+        if samples < 1.0 {
+            return Err(QuantityError::InvalidQuantity(
+                "The rolling window must span at least one sample".to_string(),
+            ));
+        }
+        Ok(Rolling {
+            series: self,
+            half_width: samples as usize / 2,
+        })
+    }
+}
+
+impl Rolling<'_> {
+    /// Returns the moving mean, in the unit of the data.
+    pub fn mean(&self) -> Result<TimeSeriesBase, QuantityError> {
+        let sums = self.window_sums(|x| x);
+        self.series
+            .with_values(self.per_window(|i, count| sums(i) / count))
+    }
+
+    /// Returns the moving population standard deviation, in the unit of the data.
+    pub fn std(&self) -> Result<TimeSeriesBase, QuantityError> {
+        // Sums of the data about their overall mean, limiting cancellation in Σx² - (Σx)²/n
+        let offset = self.series.value().mean().unwrap_or(0.0);
+        let sums = self.window_sums(|x| x - offset);
+        let squares = self.window_sums(|x| (x - offset) * (x - offset));
+        self.series.with_values(self.per_window(|i, count| {
+            let mean = sums(i) / count;
+            (squares(i) / count - mean * mean).max(0.0).sqrt()
+        }))
+    }
+
+    /// Returns the moving median, in the unit of the data.
+    pub fn median(&self) -> Result<TimeSeriesBase, QuantityError> {
+        let data = self.series.value();
+        let n = data.len();
+        // The window kept sorted as it slides, one sample in and one out at a time
+        let mut window: Vec<f64> = Vec::with_capacity(2 * self.half_width + 1);
+        let (mut start, mut end) = (0, 0);
+        let values = Array1::from_shape_fn(n, |i| {
+            let (first, last) = self.bounds(i, n);
+            while end < last {
+                let position = window.partition_point(|&x| x.total_cmp(&data[end]).is_lt());
+                window.insert(position, data[end]);
+                end += 1;
+            }
+            while start < first {
+                let position = window.partition_point(|&x| x.total_cmp(&data[start]).is_lt());
+                window.remove(position);
+                start += 1;
+            }
+            let middle = window.len() / 2;
+            if window.len().is_multiple_of(2) {
+                0.5 * (window[middle - 1] + window[middle])
+            } else {
+                window[middle]
+            }
+        });
+        self.series.with_values(values)
+    }
+
+    // Range `[first, last)` of the window centred on sample `i` of `n`
+    fn bounds(&self, i: usize, n: usize) -> (usize, usize) {
+        (
+            i.saturating_sub(self.half_width),
+            (i + self.half_width + 1).min(n),
+        )
+    }
+
+    // Evaluates `statistic(i, count)` for each sample `i` with `count` samples in its window
+    fn per_window(&self, statistic: impl Fn(usize, f64) -> f64) -> Array1<f64> {
+        let n = self.series.value().len();
+        Array1::from_shape_fn(n, |i| {
+            let (first, last) = self.bounds(i, n);
+            statistic(i, (last - first) as f64)
+        })
+    }
+
+    // Returns a lookup of the sum of `f` over the window of each sample, from prefix sums
+    fn window_sums(&self, f: impl Fn(f64) -> f64) -> impl Fn(usize) -> f64 + '_ {
+        let data = self.series.value();
+        let mut prefix = Vec::with_capacity(data.len() + 1);
+        prefix.push(0.0);
+        for &x in data {
+            prefix.push(prefix[prefix.len() - 1] + f(x));
+        }
+        move |i| {
+            let (first, last) = self.bounds(i, data.len());
+            prefix[last] - prefix[first]
+        }
+    }
+}
+
+// --- Tests for rolling statistics ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::METRE;
+    use ndarray::array;
+
+    #[test]
+    fn test_rolling_statistics() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, 5.0, 2.0, 8.0, 3.0, 3.0])
+            .unit(METRE)
+            .t0(100.0)
+            .dt(Quantity::new(array![0.5], SECOND))
+            .build()
+            .unwrap();
+        // 1.5 s is three samples: the sample and one either side
+        let rolling = ts.rolling(&Quantity::new(array![1.5], SECOND)).unwrap();
+
+        let mean = rolling.mean().unwrap();
+        let expected = [3.0, 8.0 / 3.0, 5.0, 13.0 / 3.0, 14.0 / 3.0, 3.0];
+        for (a, b) in mean.value().iter().zip(expected) {
+            assert!((a - b).abs() < 1e-12, "{a} {b}");
+        }
+        assert_eq!(mean.get_t0(), ts.get_t0());
+        assert_eq!(mean.unit(), &METRE);
+
+        let median = rolling.median().unwrap();
+        assert_eq!(median.value(), &array![3.0, 2.0, 5.0, 3.0, 3.0, 3.0]);
+
+        let std = rolling.std().unwrap();
+        assert!((std.value()[0] - 2.0).abs() < 1e-12);
+        assert!((std.value()[3] - 62.0_f64.sqrt() / 3.0).abs() < 1e-12);
+        assert!(std.value()[5].abs() < 1e-12);
+
+        // A single-sample window reproduces the data
+        let single = ts.rolling(&Quantity::new(array![0.5], SECOND)).unwrap();
+        assert_eq!(single.median().unwrap().value(), ts.value());
+        assert!(ts.rolling(&Quantity::new(array![0.1], SECOND)).is_err());
+    }
+}
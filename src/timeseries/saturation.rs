@@ -0,0 +1,120 @@
+use crate::segments::core::Segment;
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::units::{Quantity, QuantityError, SECOND};
+
+/// Options controlling how railed intervals are identified by
+/// [`find_saturations`](TimeSeriesBase::find_saturations).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SaturationOptions {
+    // Fraction of the limit by which |x| must fall below the limit to end a saturation,
+    // so that noise around the rail does not split one event into many
+    pub hysteresis: f64,
+
+    // Shortest railed interval to report, in seconds
+    pub min_duration: f64,
+}
+
+// -- Saturation detection for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Finds the intervals during which the data are railed at `limit`, i.e. `|x| >= limit`.
+    ///
+    /// A saturation starts at the first sample reaching the limit and ends at the first
+    /// sample with `|x| < limit (1 - hysteresis)`; intervals shorter than
+    /// `options.min_duration` are dropped. Returns `[start, end)` GPS segments in seconds,
+    /// where `end` is the time of the first sample back below the threshold (or the end of
+    /// the data). `limit` must be convertible to the unit of the data.
+    pub fn find_saturations(
+        &self,
+        limit: &Quantity,
+        options: SaturationOptions,
+    ) -> Result<Vec<Segment>, QuantityError> {
+        if limit.value.len() != 1 {
+            return Err(QuantityError::InvalidQuantity(
+                "limit must be a scalar quantity.".to_string(),
+            ));
+        }
+        if !(0.0..1.0).contains(&options.hysteresis) {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "hysteresis ({}) must be a fraction in [0, 1)",
+                options.hysteresis
+            )));
+        }
+        let limit = limit.to(self.unit())?.value[0].abs();
+        let release = limit * (1.0 - options.hysteresis);
+        let dt = self.dt_seconds()?;
+        let t0 = match self.get_t0() {
+            Some(t0) => t0.to(&SECOND)?.value[0],
+            None => 0.0,
+        };
+        let time = |i: usize| t0 + i as f64 * dt;
+
+        let mut segments = Vec::new();
+        let mut railed_since: Option<usize> = None;
+        for (i, x) in self.value().iter().enumerate() {
+            match railed_since {
+                None if x.abs() >= limit => railed_since = Some(i),
+                Some(start) if x.abs() < release => {
+                    segments.push(Segment::new(time(start), time(i)));
+                    railed_since = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(start) = railed_since {
+            segments.push(Segment::new(time(start), time(self.value().len())));
+        }
+        segments.retain(|segment| segment.end() - segment.start() >= options.min_duration);
+        Ok(segments)
+    }
+}
+
+// --- Tests for saturation detection ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::{CENTIMETRE, METRE};
+    use ndarray::array;
+
+    fn railed() -> TimeSeriesBase {
+        TimeSeriesBaseBuilder::new()
+            .value(array![0.0, 1.0, 0.95, 1.0, 0.2, 0.0, -1.0, 0.0, 1.0, 1.0])
+            .unit(METRE)
+            .t0(100.0)
+            .dt(Quantity::new(array![0.5], SECOND))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_find_saturations() {
+        let limit = Quantity::new(array![100.0], CENTIMETRE);
+        let segments = railed()
+            .find_saturations(&limit, SaturationOptions::default())
+            .unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                Segment::new(100.5, 101.0),
+                Segment::new(101.5, 102.0),
+                Segment::new(103.0, 103.5),
+                Segment::new(104.0, 105.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_saturations_with_hysteresis_and_min_duration() {
+        let limit = Quantity::new(array![1.0], METRE);
+        let options = SaturationOptions {
+            hysteresis: 0.1,
+            min_duration: 1.0,
+        };
+        let segments = railed().find_saturations(&limit, options).unwrap();
+        // The dip to 0.95 no longer splits the first event and the lone -1.0 is too short
+        assert_eq!(
+            segments,
+            vec![Segment::new(100.5, 102.0), Segment::new(104.0, 105.0)]
+        );
+    }
+}
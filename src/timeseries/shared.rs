@@ -0,0 +1,99 @@
+use crate::timeseries::core::TimeSeriesBase;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// An immutable, reference-counted handle to a `TimeSeriesBase`.
+///
+/// Cloning a `SharedTimeSeries` only bumps a reference count, so a single decoded stream can
+/// be handed to several analyses, possibly on different threads, without copying the data.
+/// All read-only `TimeSeriesBase` methods are available through `Deref`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedTimeSeries {
+    inner: Arc<TimeSeriesBase>,
+}
+
+impl SharedTimeSeries {
+    /// Returns true if both handles point at the same underlying data.
+    pub fn ptr_eq(&self, other: &SharedTimeSeries) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+    /// Returns the number of handles sharing the data.
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+    /// Recovers an owned, mutable `TimeSeriesBase`, cloning the data only if other handles
+    /// are still alive.
+    pub fn into_owned(self) -> TimeSeriesBase {
+        Arc::unwrap_or_clone(self.inner)
+    }
+}
+
+impl Deref for SharedTimeSeries {
+    type Target = TimeSeriesBase;
+
+    fn deref(&self) -> &TimeSeriesBase {
+        &self.inner
+    }
+}
+
+impl From<TimeSeriesBase> for SharedTimeSeries {
+    fn from(ts: TimeSeriesBase) -> Self {
+        ts.freeze()
+    }
+}
+
+impl TimeSeriesBase {
+    /// Moves the time series behind a cheaply clonable, thread-safe `SharedTimeSeries`.
+    pub fn freeze(self) -> SharedTimeSeries {
+        SharedTimeSeries {
+            inner: Arc::new(self),
+        }
+    }
+}
+
+// --- Tests for shared time series handles ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frequencyseries::core::FrequencySeries;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::{Quantity, SECOND};
+    use ndarray::array;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_series_types_are_send_sync() {
+        assert_send_sync::<TimeSeriesBase>();
+        assert_send_sync::<FrequencySeries>();
+        assert_send_sync::<SharedTimeSeries>();
+    }
+
+    #[test]
+    fn test_shared_time_series_across_threads() {
+        let shared = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, 2.0, 3.0, 4.0])
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0], SECOND))
+            .build()
+            .unwrap()
+            .freeze();
+
+        let handles: Vec<_> = (0..3)
+            .map(|_| {
+                let handle = shared.clone();
+                thread::spawn(move || handle.value().sum())
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 10.0);
+        }
+
+        let other = shared.clone();
+        assert!(shared.ptr_eq(&other));
+        assert_eq!(shared.handle_count(), 2);
+        drop(other);
+        assert_eq!(shared.into_owned().value(), &array![1.0, 2.0, 3.0, 4.0]);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::signal::fft::{irfft, rfft};
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::units::{Quantity, QuantityError, SECOND};
+use rustfft::num_complex::Complex64;
+use std::f64::consts::PI;
+
+// -- Fractional-sample time shifts for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Delays the data by `delta`, which may be negative or a fraction of a sample, keeping
+    /// the time axis.
+    ///
+    /// Unlike [`shift`](Self::shift), which moves the time axis and leaves the samples alone,
+    /// this resamples the signal so that the value at `t` is the old value at `t - delta`.
+    /// The delay is applied as a phase ramp `exp(-2πi f delta)` on the FFT of the data, so
+    /// it is circular over the data and the data should be band-limited and tapered at the
+    /// ends. The Nyquist bin of an even-length series keeps only the real part of its ramp.
+    /// `delta` must be a scalar time quantity.
+    pub fn shift_fractional(&self, delta: &Quantity) -> Result<TimeSeriesBase, QuantityError> {
+        if delta.value.len() != 1 {
+            return Err(QuantityError::InvalidQuantity(
+                "The time shift must be a scalar quantity.".to_string(),
+            ));
+        }
+        let delta = delta.to(&SECOND)?.value[0];
+        let dt = self.dt_seconds()?;
+        let n = self.value().len();
+        let df = 1.0 / (n as f64 * dt);
+        let mut spectrum = rfft(self.value());
+        for (k, bin) in spectrum.iter_mut().enumerate() {
+            let phase = -2.0 * PI * k as f64 * df * delta;
+            *bin *= if n.is_multiple_of(2) && k == n / 2 {
+                Complex64::new(phase.cos(), 0.0)
+            } else {
+                Complex64::from_polar(1.0, phase)
+            };
+        }
+        self.with_values(irfft(&spectrum, n))
+    }
+}
+
+// --- Tests for fractional-sample time shifts ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::METRE;
+    use ndarray::{Array1, array};
+
+    #[test]
+    fn test_shift_fractional() {
+        let rate = 256.0;
+        let tone = |delay: f64| {
+            Array1::from_shape_fn(1024, |i| {
+                let t = i as f64 / rate - delay;
+                (2.0 * PI * 10.0 * t).sin() + 0.5 * (2.0 * PI * 37.0 * t).cos()
+            })
+        };
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(tone(0.0))
+            .unit(METRE)
+            .t0(1_000_000_000.0)
+            .dt(Quantity::new(array![1.0 / rate], SECOND))
+            .build()
+            .unwrap();
+
+        // A third of a sample in either direction
+        for delay in [1.0 / 768.0, -1.0 / 768.0] {
+            let shifted = ts
+                .shift_fractional(&Quantity::new(array![delay], SECOND))
+                .unwrap();
+            assert_eq!(shifted.get_t0(), ts.get_t0());
+            assert_eq!(shifted.unit(), &METRE);
+            for (a, b) in shifted.value().iter().zip(tone(delay).iter()) {
+                assert!((a - b).abs() < 1e-9, "{a} {b}");
+            }
+        }
+        assert!(
+            ts.shift_fractional(&Quantity::new(array![1.0, 2.0], SECOND))
+                .is_err()
+        );
+        assert!(
+            ts.shift_fractional(&Quantity::new(array![1.0], METRE))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,1062 @@
+use crate::frequencyseries::core::{FrequencySeries, FrequencySeriesBuilder};
+use crate::segments::core::Segment;
+use crate::signal::fft::{irfft, rfft};
+use crate::signal::window::Window;
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use crate::types::provenance::Provenance;
+use crate::types::spectrogram::{Spectrogram, SpectrogramBuilder};
+use astronomy::units::{HERTZ, Quantity, QuantityError, SECOND, Unit, UnitProduct};
+use ndarray::{Array1, Array2, ArrayView1, Axis, array, s};
+use rustfft::num_complex::Complex64;
+use std::f64::consts::PI;
+
+/// How [`spectrogram`](TimeSeriesBase::spectrogram) estimates the power in each stride.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpectrogramMethod {
+    /// Welch average of the periodograms of the stride
+    #[default]
+    Welch,
+    /// Periodograms with each bin's power moved to its reassigned time and frequency,
+    /// sharpening ridges such as chirps
+    Reassigned,
+}
+
+/// How [`psd`](TimeSeriesBase::psd) averages the periodograms of its segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PsdMethod {
+    /// Mean of the periodograms, Welch's method
+    #[default]
+    Welch,
+    /// Median of the periodograms, corrected for the bias of the median of exponentially
+    /// distributed powers, which is robust to glitches in a minority of segments
+    Median,
+    /// Mean of the bias-corrected medians of the even- and odd-numbered segments, which
+    /// halves the correlation between overlapping segments in each median
+    MedianMean,
+}
+
+// -- Spectral methods for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Computes the one-sided Fourier transform of the data as a complex `FrequencySeries`.
+    ///
+    /// The normalization follows gwpy: the DFT is divided by the number of samples and every
+    /// bin above zero frequency is doubled, so a sinusoid of amplitude `A` appears with
+    /// magnitude `A`. The frequency axis starts at 0 Hz with spacing `df = 1 / (n dt)`.
+    /// Unit, name, channel and epoch are carried over from the time series.
+    pub fn fft(&self) -> Result<FrequencySeries, QuantityError> {
+        let n = self.value().len();
+        if n == 0 {
+            return Err(QuantityError::InvalidQuantity(
+                "Cannot compute the FFT of an empty TimeSeriesBase".to_string(),
+            ));
+        }
+        let df = self.frequency_resolution(n)?;
+
+        let spectrum = rfft(self.value());
+        let scale = |k: usize| if k == 0 { 1.0 } else { 2.0 } / n as f64;
+        let real = Array1::from_shape_fn(spectrum.len(), |k| spectrum[k].re * scale(k));
+        let imag = Array1::from_shape_fn(spectrum.len(), |k| spectrum[k].im * scale(k));
+
+        let mut builder = FrequencySeriesBuilder::new()
+            .value(real)
+            .imag(imag)
+            .unit(self.unit().clone())
+            .f0(Quantity::new(array![0.0], HERTZ))
+            .df(df);
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        if let Some(epoch) = self.get_epoch() {
+            builder = builder.epoch(epoch);
+        }
+        builder.build()
+    }
+
+    /// Estimates the one-sided power spectral density using Welch's method.
+    ///
+    /// The data are split into segments of `fftlength` overlapping by `overlap` (both time
+    /// quantities), each segment has its mean removed and is multiplied by `window`, and the
+    /// periodograms `2 |X(f)|² / (fs Σw²)` are averaged as chosen by `method`. The
+    /// zero-frequency bin (and the Nyquist bin for even segment lengths) is not doubled. The
+    /// result has units of `unit²/Hz`, `f0 = 0` and `df = 1 / fftlength`.
+    ///
+    /// The median methods divide by the bias of the median, as in scipy and GWpy, so all
+    /// three agree for stationary Gaussian noise. [`PsdMethod::MedianMean`] needs at least two
+    /// segments.
+    pub fn psd(
+        &self,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+        window: Window,
+        method: PsdMethod,
+    ) -> Result<FrequencySeries, QuantityError> {
+        let (periodograms, nfft) = self.periodograms(fftlength, overlap, window)?;
+        let average = match method {
+            PsdMethod::Welch => {
+                let mut average = Array1::zeros(nfft / 2 + 1);
+                for periodogram in periodograms.iter() {
+                    average += periodogram;
+                }
+                average / periodograms.len() as f64
+            }
+            PsdMethod::Median => median_periodogram(&periodograms.iter().collect::<Vec<_>>()),
+            PsdMethod::MedianMean => {
+                if periodograms.len() < 2 {
+                    return Err(QuantityError::InvalidQuantity(
+                        "The median-mean PSD needs at least two segments".to_string(),
+                    ));
+                }
+                let (even, odd): (Vec<_>, Vec<_>) = periodograms
+                    .iter()
+                    .enumerate()
+                    .partition(|(k, _)| k.is_multiple_of(2));
+                let half = |segments: Vec<(usize, &Array1<f64>)>| {
+                    median_periodogram(&segments.into_iter().map(|(_, p)| p).collect::<Vec<_>>())
+                };
+                (half(even) + half(odd)) / 2.0
+            }
+        };
+        let provenance = self
+            .provenance()?
+            .parameter("fftlength", seconds(fftlength)?)
+            .parameter("overlap", seconds(overlap)?)
+            .parameter("window", format!("{window:?}"))
+            .parameter("method", format!("{method:?}"))
+            .step("psd");
+        Ok(self
+            .spectrum_from_values(average, nfft, self.psd_unit()?)?
+            .with_provenance(provenance))
+    }
+
+    /// Estimates the amplitude spectral density, the square root of [`psd`](Self::psd).
+    ///
+    /// The result is in the square root of the PSD unit, `unit/Hz^(1/2)`: its unit is that of
+    /// these data and it is marked as a density per square-root hertz, whose
+    /// [`power_unit`](FrequencySeries::power_unit) is the PSD unit.
+    pub fn asd(
+        &self,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+        window: Window,
+        method: PsdMethod,
+    ) -> Result<FrequencySeries, QuantityError> {
+        let (nfft, _) = self.welch_segmentation(fftlength, overlap)?;
+        let psd = self.psd(fftlength, overlap, window, method)?;
+        let provenance = psd
+            .get_provenance()
+            .cloned()
+            .unwrap_or_default()
+            .step("asd");
+        self.spectrum_builder(psd.value().mapv(f64::sqrt), nfft, self.unit().clone())?
+            .per_root_hertz()
+            .provenance(provenance)
+            .build()
+    }
+
+    /// Computes the Rayleigh statistic, the standard deviation over the mean of the Hann-window
+    /// periodograms of each `fftlength` segment, as in GWpy's `rayleigh_spectrum`.
+    ///
+    /// For stationary Gaussian noise each periodogram bin is exponentially distributed and
+    /// the statistic is close to one; bins well above one flag non-Gaussian noise such as
+    /// glitches, and bins near zero a stable line. The population standard deviation is used
+    /// and at least two segments are required. The result is dimensionless.
+    pub fn rayleigh_spectrum(
+        &self,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+    ) -> Result<FrequencySeries, QuantityError> {
+        let (periodograms, nfft) = self.periodograms(fftlength, overlap, Window::Hann)?;
+        if periodograms.len() < 2 {
+            return Err(QuantityError::InvalidQuantity(
+                "The Rayleigh statistic needs at least two segments".to_string(),
+            ));
+        }
+        let mut stacked = Array2::zeros((periodograms.len(), nfft / 2 + 1));
+        for (mut row, periodogram) in stacked.rows_mut().into_iter().zip(periodograms.iter()) {
+            row.assign(periodogram);
+        }
+        let mean = stacked.mean_axis(Axis(0)).unwrap();
+        let std = stacked.std_axis(Axis(0), 0.0);
+        let rayleigh = Array1::from_shape_fn(mean.len(), |k| {
+            if mean[k] > 0.0 { std[k] / mean[k] } else { 0.0 }
+        });
+        let provenance = self
+            .provenance()?
+            .parameter("fftlength", seconds(fftlength)?)
+            .parameter("overlap", seconds(overlap)?)
+            .step("rayleigh");
+        Ok(self
+            .spectrum_from_values(rayleigh, nfft, Unit::new("", 1.0, UnitProduct::zero()))?
+            .with_provenance(provenance))
+    }
+
+    /// Whitens the data by dividing by an amplitude spectral density in the frequency domain.
+    ///
+    /// If `asd` is `None`, the ASD is estimated from these data as the square root of a Welch
+    /// [`psd`](Self::psd) using `fftlength`, `overlap` and a Hann window; a provided ASD must
+    /// have a [`power_unit`](FrequencySeries::power_unit) that converts to the PSD unit
+    /// `unit²/Hz`, as the output of [`asd`](Self::asd) does, and is linearly interpolated
+    /// onto the FFT frequencies. The data have their mean removed and the first and last
+    /// `fftlength / 2` are Hann-tapered before the transform. The result is dimensionless and
+    /// normalized so that Gaussian noise described by the ASD has unit variance.
+    pub fn whiten(
+        &self,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+        asd: Option<&FrequencySeries>,
+    ) -> Result<TimeSeriesBase, QuantityError> {
+        let (nfft, _) = self.welch_segmentation(fftlength, overlap)?;
+        let dt = self.dt_seconds()?;
+        let (asd_frequencies, asd_values) = match asd {
+            Some(asd) => {
+                let frequencies = asd.get_frequencies().ok_or_else(|| {
+                    QuantityError::InvalidQuantity("ASD must have a frequency axis".to_string())
+                })?;
+                // The unit of the squared ASD fixes the scale to the PSD unit
+                let one = Quantity::new(array![1.0], asd.power_unit()?);
+                let scale = one.to(&self.psd_unit()?)?.value[0].sqrt();
+                (
+                    frequencies.to(&HERTZ)?.value.to_vec(),
+                    (asd.value() * scale).to_vec(),
+                )
+            }
+            None => {
+                let psd = self.psd(fftlength, overlap, Window::Hann, PsdMethod::Welch)?;
+                let frequencies = psd.get_frequencies().ok_or_else(|| {
+                    QuantityError::InvalidQuantity("PSD must have a frequency axis".to_string())
+                })?;
+                (
+                    frequencies.to(&HERTZ)?.value.to_vec(),
+                    psd.value().mapv(f64::sqrt).to_vec(),
+                )
+            }
+        };
+
+        let values = self.value();
+        let n = values.len();
+        let mean = values.mean().unwrap_or(0.0);
+        let ntaper = (nfft / 2).min(n / 2);
+        let taper = Window::Hann.generate(2 * ntaper);
+        let tapered = Array1::from_shape_fn(n, |i| {
+            let weight = if i < ntaper {
+                taper[i]
+            } else if i >= n - ntaper {
+                taper[2 * ntaper - (n - i)]
+            } else {
+                1.0
+            };
+            (values[i] - mean) * weight
+        });
+
+        let df = 1.0 / (n as f64 * dt);
+        let normalization = (2.0 * dt).sqrt();
+        let mut spectrum = rfft(&tapered);
+        for (k, bin) in spectrum.iter_mut().enumerate() {
+            let amplitude = interpolate(k as f64 * df, &asd_frequencies, &asd_values);
+            *bin = if k == 0 || amplitude <= 0.0 {
+                Complex64::new(0.0, 0.0)
+            } else {
+                *bin * (normalization / amplitude)
+            };
+        }
+
+        let mut builder = TimeSeriesBaseBuilder::new()
+            .value(irfft(&spectrum, n))
+            .dt(Quantity::new(array![dt], SECOND));
+        if let Some(t0) = self.get_t0() {
+            builder = builder.t0(t0.to(&SECOND)?.value[0]);
+        }
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        builder.build()
+    }
+
+    // Splits the data into Welch segments and returns the density-scaled one-sided
+    // periodogram of each, together with the segment length in samples
+    pub(crate) fn periodograms(
+        &self,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+        window: Window,
+    ) -> Result<(Vec<Array1<f64>>, usize), QuantityError> {
+        let (nfft, step) = self.welch_segmentation(fftlength, overlap)?;
+        let sample_rate = 1.0 / self.dt_seconds()?;
+        let periodograms = welch_periodograms(self.value().view(), nfft, step, window, sample_rate);
+        Ok((periodograms, nfft))
+    }
+
+    /// Computes a spectrogram by estimating a Welch [`psd`](Self::psd) over each consecutive
+    /// `stride` of data, using `fftlength` segments overlapping by `overlap` and a Hann window.
+    ///
+    /// Each row of the result is the PSD of one stride, in `unit²/Hz`, labelled by the GPS
+    /// start time of the stride. Incomplete trailing strides are discarded.
+    ///
+    /// With [`SpectrogramMethod::Reassigned`] the power of every bin of every segment is
+    /// instead moved to the nearest bin and stride of its reassigned time and frequency,
+    /// the centre of gravity of its energy estimated from transforms with time-weighted and
+    /// differentiated windows (Auger & Flandrin 1995). Power is conserved, apart from any
+    /// reassigned outside the spectrogram, but ridges of tones and chirps narrow to about
+    /// one bin.
+    pub fn spectrogram(
+        &self,
+        stride: &Quantity,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+        method: SpectrogramMethod,
+    ) -> Result<Spectrogram, QuantityError> {
+        if stride.value.len() != 1 {
+            return Err(QuantityError::InvalidQuantity(
+                "stride must be a scalar quantity.".to_string(),
+            ));
+        }
+        let (nfft, step) = self.welch_segmentation(fftlength, overlap)?;
+        let dt = self.dt_seconds()?;
+        let nstride = (stride.to(&SECOND)?.value[0] / dt).round();
+        if nstride < nfft as f64 || nstride as usize > self.value().len() {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "stride ({nstride} samples) must be between fftlength ({nfft} samples) and the \
+                 data length ({} samples)",
+                self.value().len()
+            )));
+        }
+        let nstride = nstride as usize;
+        let window = Window::default();
+
+        let n_strides = self.value().len() / nstride;
+        let mut values = Array2::zeros((n_strides, nfft / 2 + 1));
+        match method {
+            SpectrogramMethod::Welch => {
+                for (k, mut row) in values.rows_mut().into_iter().enumerate() {
+                    let chunk = self.value().slice(s![k * nstride..(k + 1) * nstride]);
+                    let periodograms = welch_periodograms(chunk, nfft, step, window, 1.0 / dt);
+                    for periodogram in periodograms.iter() {
+                        row += periodogram;
+                    }
+                    row /= periodograms.len() as f64;
+                }
+            }
+            SpectrogramMethod::Reassigned => {
+                reassign_periodograms(&mut values, self.value(), nstride, nfft, step, window, dt);
+            }
+        }
+
+        let t0 = match self.get_t0() {
+            Some(t0) => t0.to(&SECOND)?.value[0],
+            None => 0.0,
+        };
+        let df = self.frequency_resolution(nfft)?.value[0];
+        let mut builder = SpectrogramBuilder::new()
+            .value(values)
+            .unit(self.psd_unit()?)
+            .times(Quantity::new(
+                Array1::from_shape_fn(n_strides, |k| t0 + (k * nstride) as f64 * dt),
+                SECOND,
+            ))
+            .frequencies(Quantity::new(
+                Array1::from_shape_fn(nfft / 2 + 1, |k| k as f64 * df),
+                HERTZ,
+            ));
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.channel(channel.clone());
+        }
+        if let Some(epoch) = self.get_epoch() {
+            builder = builder.epoch(epoch);
+        }
+        let provenance = self
+            .provenance()?
+            .parameter("stride", seconds(stride)?)
+            .parameter("fftlength", seconds(fftlength)?)
+            .parameter("overlap", seconds(overlap)?)
+            .parameter("window", format!("{window:?}"))
+            .parameter("method", format!("{method:?}"))
+            .step("spectrogram");
+        builder.provenance(provenance).build()
+    }
+
+    // Starts a provenance record naming these data (by channel, else by name) and their span
+    pub(crate) fn provenance(&self) -> Result<Provenance, QuantityError> {
+        let mut provenance = Provenance::new();
+        if let Some(channel) = self.get_channel() {
+            provenance = provenance.source(channel.get_name());
+        } else if let Some(name) = self.get_name() {
+            provenance = provenance.source(name);
+        }
+        if let Some(t0) = self.get_t0() {
+            let start = t0.to(&SECOND)?.value[0];
+            let end = start + self.value().len() as f64 * self.dt_seconds()?;
+            provenance = provenance.span(Segment::new(start, end));
+        }
+        Ok(provenance)
+    }
+
+    // Converts `fftlength` and `overlap` into a segment length and step in samples
+    pub(crate) fn welch_segmentation(
+        &self,
+        fftlength: &Quantity,
+        overlap: &Quantity,
+    ) -> Result<(usize, usize), QuantityError> {
+        if fftlength.value.len() != 1 || overlap.value.len() != 1 {
+            return Err(QuantityError::InvalidQuantity(
+                "fftlength and overlap must be scalar quantities.".to_string(),
+            ));
+        }
+        let dt = self.dt_seconds()?;
+        let nfft = (fftlength.to(&SECOND)?.value[0] / dt).round();
+        let noverlap = (overlap.to(&SECOND)?.value[0] / dt).round();
+        if nfft < 1.0 || nfft as usize > self.value().len() {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "fftlength ({} samples) must be between 1 and the data length ({} samples)",
+                nfft,
+                self.value().len()
+            )));
+        }
+        if noverlap < 0.0 || noverlap >= nfft {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "overlap ({noverlap} samples) must be non-negative and less than fftlength ({nfft} samples)"
+            )));
+        }
+        Ok((nfft as usize, (nfft - noverlap) as usize))
+    }
+
+    // Sample spacing in seconds, required by all spectral estimators
+    pub(crate) fn dt_seconds(&self) -> Result<f64, QuantityError> {
+        let dt_quantity = self.get_dt().ok_or_else(|| {
+            QuantityError::InvalidQuantity("dt is required to compute a spectrum".to_string())
+        })?;
+        Ok(dt_quantity.to(&SECOND)?.value[0])
+    }
+
+    // Unit of the power spectral density of these data, `unit²/Hz`
+    pub(crate) fn psd_unit(&self) -> Result<Unit, QuantityError> {
+        let amplitude = Quantity::new(array![1.0], self.unit().clone());
+        let bandwidth = Quantity::new(array![1.0], HERTZ);
+        Ok(((amplitude.clone() * amplitude) / bandwidth)?.unit)
+    }
+
+    // Wraps one-sided spectral values from `nfft`-sample transforms into a FrequencySeries
+    // carrying the name, channel and epoch of these data
+    pub(crate) fn spectrum_from_values(
+        &self,
+        values: Array1<f64>,
+        nfft: usize,
+        unit: Unit,
+    ) -> Result<FrequencySeries, QuantityError> {
+        self.spectrum_builder(values, nfft, unit)?.build()
+    }
+
+    // A builder for such a spectrum, for products that set more than its values and unit
+    fn spectrum_builder(
+        &self,
+        values: Array1<f64>,
+        nfft: usize,
+        unit: Unit,
+    ) -> Result<FrequencySeriesBuilder, QuantityError> {
+        let mut builder = FrequencySeriesBuilder::new()
+            .value(values)
+            .unit(unit)
+            .f0(Quantity::new(array![0.0], HERTZ))
+            .df(self.frequency_resolution(nfft)?);
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        if let Some(epoch) = self.get_epoch() {
+            builder = builder.epoch(epoch);
+        }
+        Ok(builder)
+    }
+
+    // Frequency spacing `1 / (n dt)` in Hz of an `n`-sample transform of these data
+    pub(crate) fn frequency_resolution(&self, n: usize) -> Result<Quantity, QuantityError> {
+        let dt_quantity = self.get_dt().ok_or_else(|| {
+            QuantityError::InvalidQuantity("dt is required to compute a spectrum".to_string())
+        })?;
+        let duration = Quantity::new(
+            array![n as f64 * dt_quantity.value[0]],
+            dt_quantity.unit.clone(),
+        );
+        let one_quantity = Quantity::new(array![1.0], Unit::new("", 1.0, UnitProduct::zero()));
+        (one_quantity / duration)?.to(&HERTZ)
+    }
+}
+
+// Formats a scalar duration in seconds for a provenance record
+pub(crate) fn seconds(duration: &Quantity) -> Result<String, QuantityError> {
+    Ok(format!("{} s", duration.to(&SECOND)?.value[0]))
+}
+
+// Linear interpolation of `(x, y)` at `at`, clamped to the end values outside the range
+pub(crate) fn interpolate(at: f64, x: &[f64], y: &[f64]) -> f64 {
+    let upper = x.partition_point(|&xi| xi < at);
+    if upper == 0 {
+        return y[0];
+    }
+    if upper == x.len() {
+        return y[x.len() - 1];
+    }
+    let fraction = (at - x[upper - 1]) / (x[upper] - x[upper - 1]);
+    y[upper - 1] + fraction * (y[upper] - y[upper - 1])
+}
+
+// Median over the periodograms of each frequency bin, divided by the bias of the median of
+// that many exponentially distributed powers (scipy's `_median_bias`)
+fn median_periodogram(periodograms: &[&Array1<f64>]) -> Array1<f64> {
+    let n = periodograms.len();
+    let bias = 1.0
+        + (1..=(n.saturating_sub(1)) / 2)
+            .map(|i| 1.0 / (2 * i + 1) as f64 - 1.0 / (2 * i) as f64)
+            .sum::<f64>();
+    Array1::from_shape_fn(periodograms[0].len(), |bin| {
+        let mut powers: Vec<f64> = periodograms.iter().map(|p| p[bin]).collect();
+        powers.sort_by(f64::total_cmp);
+        let median = if n.is_multiple_of(2) {
+            0.5 * (powers[n / 2 - 1] + powers[n / 2])
+        } else {
+            powers[n / 2]
+        };
+        median / bias
+    })
+}
+
+// Density-scaled one-sided periodograms `2 |X(f)|² / (fs Σw²)` of the mean-removed,
+// windowed `nfft`-sample segments of `values` starting every `step` samples
+fn welch_periodograms(
+    values: ArrayView1<f64>,
+    nfft: usize,
+    step: usize,
+    window: Window,
+    sample_rate: f64,
+) -> Vec<Array1<f64>> {
+    welch_transforms(values, nfft, step, window, sample_rate)
+        .iter()
+        .map(|transform| transform.iter().map(|x| x.norm_sqr()).collect())
+        .collect()
+}
+
+// One-sided transforms of the same segments as `welch_periodograms`, each bin scaled by
+// `sqrt(c / (fs Σw²))` with `c` two except at zero and Nyquist frequency, so that `|X|²` is
+// the periodogram and `conj(X) Y` the cross-periodogram of two series
+pub(crate) fn welch_transforms(
+    values: ArrayView1<f64>,
+    nfft: usize,
+    step: usize,
+    window: Window,
+    sample_rate: f64,
+) -> Vec<Vec<Complex64>> {
+    let taper = window.generate(nfft);
+    let normalization = sample_rate * taper.mapv(|w| w * w).sum();
+    let n_segments = (values.len() - nfft) / step + 1;
+    (0..n_segments)
+        .map(|k| {
+            let segment = values.slice(s![k * step..k * step + nfft]);
+            let mean = segment.mean().unwrap_or(0.0);
+            let windowed = Array1::from_shape_fn(nfft, |i| (segment[i] - mean) * taper[i]);
+            rfft(&windowed)
+                .iter()
+                .enumerate()
+                .map(|(bin, x)| {
+                    let one_sided = if bin == 0 || 2 * bin == nfft {
+                        1.0
+                    } else {
+                        2.0
+                    };
+                    x * (one_sided / normalization).sqrt()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Accumulates the density-scaled periodogram power of every `nfft`-sample segment of each
+// `nstride`-sample stride of `data` into the row and column of its reassigned time and
+// frequency in `spectrogram`, normalized by the number of segments per stride
+fn reassign_periodograms(
+    spectrogram: &mut Array2<f64>,
+    data: &Array1<f64>,
+    nstride: usize,
+    nfft: usize,
+    step: usize,
+    window: Window,
+    dt: f64,
+) {
+    let (n_strides, bins) = spectrogram.dim();
+    let taper = window.generate(nfft);
+    let centre = (nfft as f64 - 1.0) / 2.0;
+    // Window weighted by time from the segment centre, and its time derivative by central
+    // differences of the periodic window
+    let time_taper = Array1::from_shape_fn(nfft, |i| (i as f64 - centre) * dt * taper[i]);
+    let derivative_taper = Array1::from_shape_fn(nfft, |i| {
+        (taper[(i + 1) % nfft] - taper[(i + nfft - 1) % nfft]) / (2.0 * dt)
+    });
+    let segments = (nstride - nfft) / step + 1;
+    let normalization = taper.mapv(|w| w * w).sum() / dt * segments as f64;
+    let df = 1.0 / (nfft as f64 * dt);
+
+    for start in (0..n_strides).flat_map(|k| (0..segments).map(move |j| k * nstride + j * step)) {
+        let segment = data.slice(s![start..start + nfft]);
+        let mean = segment.mean().unwrap_or(0.0);
+        let transform = |weights: &Array1<f64>| {
+            rfft(&Array1::from_shape_fn(nfft, |i| {
+                (segment[i] - mean) * weights[i]
+            }))
+        };
+        let (x, x_time, x_derivative) = (
+            transform(&taper),
+            transform(&time_taper),
+            transform(&derivative_taper),
+        );
+        for bin in 0..bins {
+            let power = x[bin].norm_sqr();
+            if power == 0.0 {
+                continue;
+            }
+            let time = (start as f64 + centre) * dt + (x_time[bin] * x[bin].conj()).re / power;
+            let frequency =
+                bin as f64 * df - (x_derivative[bin] * x[bin].conj()).im / (2.0 * PI * power);
+            let row = (time / (nstride as f64 * dt)).floor();
+            let column = (frequency / df).round();
+            if row < 0.0 || row >= n_strides as f64 || column < 0.0 || column >= bins as f64 {
+                continue;
+            }
+            let one_sided = if bin == 0 || 2 * bin == nfft {
+                1.0
+            } else {
+                2.0
+            };
+            spectrogram[[row as usize, column as usize]] += one_sided * power / normalization;
+        }
+    }
+}
+
+// --- Tests for the spectral methods ---
+#[cfg(test)]
+mod tests {
+    use super::{PsdMethod, SpectrogramMethod};
+    use crate::detector::channel::Channel;
+    use crate::frequencyseries::core::FrequencySeriesBuilder;
+    use crate::segments::core::Segment;
+    use crate::signal::window::Window;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use crate::timeseries::testing::gaussian_noise;
+    use astronomy::time::Time;
+    use astronomy::units::{Dimension, HERTZ, METRE, Quantity, QuantityError, SECOND, UnitProduct};
+    use ndarray::{Array1, array, s};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_fft_of_sinusoid() {
+        // 2 m amplitude sinusoid at 8 Hz sampled at 64 Hz for 1 s
+        let values = Array1::from_shape_fn(64, |i| 2.0 * (2.0 * PI * 8.0 * i as f64 / 64.0).sin());
+        let channel = Channel::new("H1:TEST", Some(64.0), None, None, None, None, None).unwrap();
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(values)
+            .unit(METRE)
+            .epoch(Time::from_gps_seconds(1000.0))
+            .dt(Quantity::new(array![1.0 / 64.0], SECOND))
+            .name("Sine")
+            .channel(channel)
+            .build()
+            .unwrap();
+
+        let fs = ts.fft().unwrap();
+
+        assert_eq!(fs.value().len(), 33);
+        assert!(fs.is_complex());
+        assert_eq!(fs.unit(), &METRE);
+        assert_eq!(fs.get_name(), Some("Sine"));
+        assert_eq!(fs.get_channel().unwrap().get_name(), "H1:TEST");
+        assert_eq!(fs.get_epoch(), Some(Time::from_gps_seconds(1000.0)));
+        assert_eq!(fs.get_f0().unwrap().value[0], 0.0);
+        assert_eq!(fs.get_df().unwrap().value[0], 1.0);
+        assert_eq!(fs.get_df().unwrap().unit, HERTZ);
+
+        let magnitude = fs.abs();
+        assert!((magnitude[8] - 2.0).abs() < 1e-12);
+        assert!(
+            magnitude
+                .iter()
+                .enumerate()
+                .all(|(k, m)| k == 8 || *m < 1e-12)
+        );
+    }
+
+    #[test]
+    fn test_psd_of_sinusoid() {
+        // 3 m amplitude sinusoid at 32 Hz sampled at 256 Hz for 16 s
+        let rate = 256.0;
+        let values =
+            Array1::from_shape_fn(4096, |i| 3.0 * (2.0 * PI * 32.0 * i as f64 / rate).sin());
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(values)
+            .unit(METRE)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / rate], SECOND))
+            .name("Sine")
+            .build()
+            .unwrap();
+
+        let psd = ts
+            .psd(
+                &Quantity::new(array![2.0], SECOND),
+                &Quantity::new(array![1.0], SECOND),
+                Window::Hann,
+                PsdMethod::Welch,
+            )
+            .unwrap();
+
+        assert_eq!(psd.value().len(), 257);
+        assert_eq!(psd.get_df().unwrap().value[0], 0.5);
+        assert_eq!(psd.get_name(), Some("Sine"));
+        assert!(!psd.is_complex());
+        assert_eq!(
+            psd.unit().dimensions,
+            UnitProduct::from_components(&[(Dimension::Length, 2), (Dimension::Time, 1)])
+        );
+        let provenance = psd.get_provenance().unwrap();
+        assert_eq!(provenance.get_sources(), ["Sine"]);
+        assert_eq!(provenance.get_span(), Some(Segment::new(0.0, 16.0)));
+        assert_eq!(provenance.get_parameter("fftlength"), Some("2 s"));
+        assert_eq!(provenance.get_parameter("window"), Some("Hann"));
+        assert_eq!(provenance.get_processing(), ["psd"]);
+        // Parseval: the PSD integrates to the mean square, A² / 2
+        let power: f64 = psd.value().sum() * 0.5;
+        assert!((power - 4.5).abs() < 1e-6);
+        // The peak sits in the 32 Hz bin
+        let peak = psd
+            .value()
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0;
+        assert_eq!(peak, 64);
+    }
+
+    #[test]
+    fn test_asd_is_root_of_psd() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(1024, |i| {
+                ((i * 7919) % 101) as f64 - 50.0
+            }))
+            .unit(METRE)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / 64.0], SECOND))
+            .build()
+            .unwrap();
+        let fftlength = Quantity::new(array![4.0], SECOND);
+        let overlap = Quantity::new(array![2.0], SECOND);
+        let psd = ts
+            .psd(&fftlength, &overlap, Window::Hann, PsdMethod::Welch)
+            .unwrap();
+        let asd = ts
+            .asd(&fftlength, &overlap, Window::Hann, PsdMethod::Welch)
+            .unwrap();
+
+        assert_eq!(asd.value().len(), psd.value().len());
+        for (a, p) in asd.value().iter().zip(psd.value().iter()) {
+            assert!((a * a - p).abs() <= 1e-12 * p.abs(), "{a} {p}");
+        }
+        // m/Hz^(1/2): the unit of the data, per square-root hertz, squaring to m²/Hz
+        assert_eq!(asd.unit(), &METRE);
+        assert!(asd.is_per_root_hertz() && !psd.is_per_root_hertz());
+        assert_eq!(asd.power_unit().unwrap().dimensions, psd.unit().dimensions);
+        assert_eq!(asd.get_df(), psd.get_df());
+        assert_eq!(
+            asd.get_provenance()
+                .unwrap()
+                .get_processing()
+                .last()
+                .unwrap(),
+            "asd"
+        );
+    }
+
+    #[test]
+    fn test_spectrogram_tracks_chirp() {
+        // 8 s of a tone stepping from 16 Hz to 48 Hz halfway through, sampled at 128 Hz
+        let rate = 128.0;
+        let values = Array1::from_shape_fn(1024, |i| {
+            let frequency = if i < 512 { 16.0 } else { 48.0 };
+            (2.0 * PI * frequency * i as f64 / rate).sin()
+        });
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(values)
+            .unit(METRE)
+            .t0(1000.0)
+            .dt(Quantity::new(array![1.0 / rate], SECOND))
+            .build()
+            .unwrap();
+
+        let spectrogram = ts
+            .spectrogram(
+                &Quantity::new(array![2.0], SECOND),
+                &Quantity::new(array![1.0], SECOND),
+                &Quantity::new(array![0.5], SECOND),
+                SpectrogramMethod::Welch,
+            )
+            .unwrap();
+
+        assert_eq!(spectrogram.value().dim(), (4, 65));
+        assert_eq!(
+            spectrogram.get_times().value,
+            array![1000.0, 1002.0, 1004.0, 1006.0]
+        );
+        assert_eq!(spectrogram.get_frequencies().value[1], 1.0);
+        assert_eq!(spectrogram.unit(), &ts.psd_unit().unwrap());
+        for (row, expected) in spectrogram.value().rows().into_iter().zip([16, 16, 48, 48]) {
+            let peak = row
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .unwrap()
+                .0;
+            assert_eq!(peak, expected);
+        }
+    }
+
+    #[test]
+    fn test_reassigned_spectrogram_sharpens_tone() {
+        // A tone between bins leaks into its neighbours in a Welch estimate
+        let rate = 64.0;
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(1024, |i| {
+                (2.0 * PI * 10.3 * i as f64 / rate).sin()
+            }))
+            .unit(METRE)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / rate], SECOND))
+            .build()
+            .unwrap();
+        let spectrogram = |method| {
+            ts.spectrogram(
+                &Quantity::new(array![4.0], SECOND),
+                &Quantity::new(array![1.0], SECOND),
+                &Quantity::new(array![0.5], SECOND),
+                method,
+            )
+            .unwrap()
+        };
+        let welch = spectrogram(SpectrogramMethod::Welch);
+        let reassigned = spectrogram(SpectrogramMethod::Reassigned);
+        assert_eq!(reassigned.value().dim(), welch.value().dim());
+        assert_eq!(
+            reassigned.get_provenance().unwrap().get_parameter("method"),
+            Some("Reassigned")
+        );
+
+        for (welch_row, row) in welch
+            .value()
+            .rows()
+            .into_iter()
+            .zip(reassigned.value().rows())
+        {
+            let total = row.sum();
+            assert!((total - welch_row.sum()).abs() < 1e-6 * total);
+            assert!(welch_row[10] / total < 0.7);
+            assert!(row[10] / total > 0.95, "{row}");
+        }
+    }
+
+    // Deterministic Gaussian noise from a linear congruential generator and Box-Muller
+    #[test]
+    fn test_whiten_white_noise_has_unit_variance() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(gaussian_noise(8192, 3.0))
+            .unit(METRE)
+            .t0(1000.0)
+            .dt(Quantity::new(array![1.0 / 256.0], SECOND))
+            .name("Noise")
+            .build()
+            .unwrap();
+
+        let whitened = ts
+            .whiten(
+                &Quantity::new(array![2.0], SECOND),
+                &Quantity::new(array![1.0], SECOND),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(whitened.value().len(), 8192);
+        assert_eq!(whitened.get_t0(), ts.get_t0());
+        assert_eq!(whitened.get_name(), Some("Noise"));
+        assert_eq!(whitened.unit().dimensions, UnitProduct::zero());
+        // Exclude the tapered edges
+        let interior = whitened.value().slice(s![512..7680]);
+        let variance = interior.mapv(|x| x * x).mean().unwrap();
+        assert!((variance - 1.0).abs() < 0.1, "variance = {variance}");
+
+        // The ASD of the data whitens them the same way
+        let (fftlength, overlap) = (
+            Quantity::new(array![2.0], SECOND),
+            Quantity::new(array![1.0], SECOND),
+        );
+        let asd = ts
+            .asd(&fftlength, &overlap, Window::Hann, PsdMethod::Welch)
+            .unwrap();
+        let with_asd = ts.whiten(&fftlength, &overlap, Some(&asd)).unwrap();
+        for (x, y) in with_asd.value().iter().zip(whitened.value().iter()) {
+            assert!((x - y).abs() < 1e-9, "{x} {y}");
+        }
+    }
+
+    #[test]
+    fn test_median_psd_rejects_glitch() {
+        let rate = 256.0;
+        let clean = gaussian_noise(16384, 1.0);
+        let mut glitched = clean.clone();
+        // A loud burst confined to one of the 64 one-second segments
+        for i in 4096..4352 {
+            glitched[i] += 50.0 * (2.0 * PI * 40.0 * i as f64 / rate).sin();
+        }
+        let psd = |values: &Array1<f64>, method| {
+            TimeSeriesBaseBuilder::new()
+                .value(values.clone())
+                .unit(METRE)
+                .dt(Quantity::new(array![1.0 / rate], SECOND))
+                .build()
+                .unwrap()
+                .psd(
+                    &Quantity::new(array![1.0], SECOND),
+                    &Quantity::new(array![0.0], SECOND),
+                    Window::Hann,
+                    method,
+                )
+                .unwrap()
+        };
+        // White noise of unit variance has the one-sided PSD 2 / rate away from 0 Hz
+        let level = |psd: &crate::frequencyseries::core::FrequencySeries, bins: &[usize]| {
+            bins.iter().map(|&k| psd.value()[k]).sum::<f64>() / bins.len() as f64 * rate / 2.0
+        };
+        let noise_bins: Vec<usize> = (60..120).collect();
+        for method in [PsdMethod::Welch, PsdMethod::Median, PsdMethod::MedianMean] {
+            let estimate = level(&psd(&clean, method), &noise_bins);
+            assert!((estimate - 1.0).abs() < 0.05, "{method:?} {estimate}");
+        }
+
+        // The glitch dominates the mean at 40 Hz but barely moves the medians
+        let welch = level(&psd(&glitched, PsdMethod::Welch), &[40]);
+        assert!(welch > 10.0, "{welch}");
+        for method in [PsdMethod::Median, PsdMethod::MedianMean] {
+            let glitched = psd(&glitched, method);
+            assert!(level(&glitched, &[40]) < 2.0, "{method:?}");
+            assert_eq!(
+                glitched.get_provenance().unwrap().get_parameter("method"),
+                Some(format!("{method:?}").as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn test_rayleigh_spectrum() {
+        let tone =
+            Array1::from_shape_fn(16384, |i| 10.0 * (2.0 * PI * 32.0 * i as f64 / 256.0).sin());
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(gaussian_noise(16384, 1.0) + tone)
+            .unit(METRE)
+            .dt(Quantity::new(array![1.0 / 256.0], SECOND))
+            .build()
+            .unwrap();
+        let rayleigh = ts
+            .rayleigh_spectrum(
+                &Quantity::new(array![1.0], SECOND),
+                &Quantity::new(array![0.0], SECOND),
+            )
+            .unwrap();
+
+        assert_eq!(rayleigh.value().len(), 129);
+        assert_eq!(rayleigh.unit().dimensions, UnitProduct::zero());
+        // Gaussian noise averages to one away from the line, which is nearly constant
+        let noise: Vec<f64> = (1..128)
+            .filter(|k| (k - 32_i32).abs() > 2)
+            .map(|k| rayleigh.value()[k as usize])
+            .collect();
+        let mean = noise.iter().sum::<f64>() / noise.len() as f64;
+        assert!((mean - 1.0).abs() < 0.1, "mean = {mean}");
+        assert!(rayleigh.value()[32] < 0.1, "{}", rayleigh.value()[32]);
+
+        let short = TimeSeriesBaseBuilder::new()
+            .value(gaussian_noise(256, 1.0))
+            .dt(Quantity::new(array![1.0 / 256.0], SECOND))
+            .build()
+            .unwrap();
+        assert!(
+            short
+                .rayleigh_spectrum(
+                    &Quantity::new(array![1.0], SECOND),
+                    &Quantity::new(array![0.0], SECOND)
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_whiten_rejects_incompatible_asd() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(gaussian_noise(256, 1.0))
+            .unit(METRE)
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0 / 16.0], SECOND))
+            .build()
+            .unwrap();
+        let asd = FrequencySeriesBuilder::new()
+            .value(array![1.0, 1.0])
+            .unit(SECOND)
+            .f0(Quantity::new(array![0.0], HERTZ))
+            .df(Quantity::new(array![8.0], HERTZ))
+            .build()
+            .unwrap();
+        let fftlength = Quantity::new(array![4.0], SECOND);
+        let overlap = Quantity::new(array![2.0], SECOND);
+        assert!(ts.whiten(&fftlength, &overlap, Some(&asd)).is_err());
+    }
+
+    #[test]
+    fn test_psd_invalid_segmentation() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::zeros(16))
+            .t0(0.0)
+            .dt(Quantity::new(array![1.0], SECOND))
+            .build()
+            .unwrap();
+        let too_long = ts.psd(
+            &Quantity::new(array![32.0], SECOND),
+            &Quantity::new(array![0.0], SECOND),
+            Window::Hann,
+            PsdMethod::Welch,
+        );
+        assert!(matches!(too_long, Err(QuantityError::InvalidQuantity(_))));
+        let full_overlap = ts.psd(
+            &Quantity::new(array![4.0], SECOND),
+            &Quantity::new(array![4.0], SECOND),
+            Window::Hann,
+            PsdMethod::Welch,
+        );
+        assert!(matches!(
+            full_overlap,
+            Err(QuantityError::InvalidQuantity(_))
+        ));
+    }
+
+    #[test]
+    fn test_fft_requires_dt() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![1.0, 2.0])
+            .times(Quantity::new(array![0.0, 1.0], SECOND))
+            .build()
+            .unwrap();
+        assert!(matches!(ts.fft(), Err(QuantityError::InvalidQuantity(_))));
+    }
+}
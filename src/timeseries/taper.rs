@@ -0,0 +1,180 @@
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::units::{Quantity, QuantityError, SECOND};
+use ndarray::Array1;
+use std::f64::consts::PI;
+
+/// The ends of a series to which a [`taper`](TimeSeriesBase::taper) is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaperSide {
+    Left,
+    Right,
+    #[default]
+    Both,
+}
+
+/// The shape of the ramp with which a [`taper`](TimeSeriesBase::taper) rises from zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaperShape {
+    /// Planck-taper ramp, smooth to all orders at both ends
+    #[default]
+    Planck,
+    /// Raised-cosine ramp of a Tukey window
+    Tukey,
+}
+
+/// The length of a [`taper`](TimeSeriesBase::taper) at each tapered end.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaperLength {
+    /// An absolute duration
+    Duration(Quantity),
+    /// A fraction of the length of the series
+    Fraction(f64),
+}
+
+// -- Tapering for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Smoothly brings the data to zero at one or both ends, suppressing the edge artefacts
+    /// of Fourier transforms after cropping or gating.
+    ///
+    /// Over `length` at each tapered end the data are multiplied by a ramp rising from zero
+    /// to one with the given `shape`; the rest of the series is unchanged. When both ends are
+    /// tapered the two ramps may not overlap.
+    pub fn taper(
+        &self,
+        length: TaperLength,
+        side: TaperSide,
+        shape: TaperShape,
+    ) -> Result<TimeSeriesBase, QuantityError> {
+        let n = self.value().len();
+        let ramp_length = match length {
+            TaperLength::Duration(duration) => {
+                if duration.value.len() != 1 || duration.value[0] < 0.0 {
+                    return Err(QuantityError::InvalidQuantity(
+                        "The taper duration must be a non-negative scalar quantity.".to_string(),
+                    ));
+                }
+                (duration.to(&SECOND)?.value[0] / self.dt_seconds()?).round()
+            }
+            TaperLength::Fraction(fraction) => {
+                if !(0.0..=1.0).contains(&fraction) {
+                    return Err(QuantityError::InvalidQuantity(format!(
+                        "The taper fraction ({fraction}) must lie in [0, 1]"
+                    )));
+                }
+                (fraction * n as f64).round()
+            }
+        } as usize;
+        let ends = if side == TaperSide::Both { 2 } else { 1 };
+        if ends * ramp_length > n {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "A taper of {ramp_length} samples per end does not fit in {n} samples"
+            )));
+        }
+
+        let ramp = Array1::from_shape_fn(ramp_length, |i| match shape {
+            TaperShape::Planck => planck_ramp(i, ramp_length),
+            TaperShape::Tukey => 0.5 - 0.5 * (PI * i as f64 / ramp_length as f64).cos(),
+        });
+        let mut values = self.value().clone();
+        if side != TaperSide::Right {
+            values.iter_mut().zip(&ramp).for_each(|(x, w)| *x *= w);
+        }
+        if side != TaperSide::Left {
+            values
+                .iter_mut()
+                .rev()
+                .zip(&ramp)
+                .for_each(|(x, w)| *x *= w);
+        }
+        self.with_values(values)
+    }
+}
+
+// Planck-taper rise 1 / (1 + exp(m / i - m / (m - i))) at sample i of an m-sample ramp,
+// zero at i = 0 and one half at the midpoint
+fn planck_ramp(i: usize, m: usize) -> f64 {
+    if i == 0 {
+        return 0.0;
+    }
+    let (i, m) = (i as f64, m as f64);
+    1.0 / (1.0 + (m / i - m / (m - i)).exp())
+}
+
+// --- Tests for tapering ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use ndarray::array;
+
+    fn ones(n: usize) -> TimeSeriesBase {
+        TimeSeriesBaseBuilder::new()
+            .value(Array1::ones(n))
+            .t0(0.0)
+            .dt(Quantity::new(array![0.5], SECOND))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_tukey_taper_both_sides() {
+        let tapered = ones(10)
+            .taper(
+                TaperLength::Fraction(0.2),
+                TaperSide::Both,
+                TaperShape::Tukey,
+            )
+            .unwrap();
+        let expected = [0.0, 0.5, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.5, 0.0];
+        for (x, e) in tapered.value().iter().zip(expected) {
+            assert!((x - e).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_planck_taper_one_side() {
+        let duration = Quantity::new(array![2.0], SECOND);
+        let tapered = ones(12)
+            .taper(
+                TaperLength::Duration(duration),
+                TaperSide::Left,
+                TaperShape::Planck,
+            )
+            .unwrap();
+        let values = tapered.value();
+        assert_eq!(values[0], 0.0);
+        assert!((values[2] - 0.5).abs() < 1e-12);
+        assert!(values[1] < values[2] && values[2] < values[3] && values[3] < 1.0);
+        assert!(values.iter().skip(4).all(|&x| x == 1.0));
+        assert_eq!(tapered.get_t0(), ones(12).get_t0());
+    }
+
+    #[test]
+    fn test_taper_must_fit() {
+        let ts = ones(10);
+        assert!(
+            ts.taper(
+                TaperLength::Fraction(0.6),
+                TaperSide::Both,
+                TaperShape::Tukey
+            )
+            .is_err()
+        );
+        assert!(
+            ts.taper(
+                TaperLength::Fraction(0.6),
+                TaperSide::Right,
+                TaperShape::Tukey
+            )
+            .is_ok()
+        );
+        assert!(
+            ts.taper(
+                TaperLength::Fraction(1.5),
+                TaperSide::Left,
+                TaperShape::Planck
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! Fixtures shared by the tests of time-series methods and filters.
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use astronomy::units::{METRE, Quantity, SECOND};
+use ndarray::{Array1, array};
+use std::f64::consts::PI;
+
+/// Starts a builder for `values` in metres, sampled every `dt` seconds from GPS time `t0`,
+/// for tests that also set a name or unit.
+pub(crate) fn series_builder(values: Array1<f64>, t0: f64, dt: f64) -> TimeSeriesBaseBuilder {
+    TimeSeriesBaseBuilder::new()
+        .value(values)
+        .unit(METRE)
+        .t0(t0)
+        .dt(Quantity::new(array![dt], SECOND))
+}
+
+/// Builds a series of `values` in metres, sampled every `dt` seconds from GPS time `t0`.
+pub(crate) fn series(values: Array1<f64>, t0: f64, dt: f64) -> TimeSeriesBase {
+    series_builder(values, t0, dt).build().unwrap()
+}
+
+/// Generates `n` reproducible samples uniform in `[-1, 1)` from a 64-bit LCG seeded with
+/// `seed`.
+pub(crate) fn uniform_noise(n: usize, seed: u64) -> Array1<f64> {
+    let mut state = seed;
+    Array1::from_shape_fn(n, |_| {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        2.0 * (state >> 11) as f64 / (1u64 << 53) as f64 - 1.0
+    })
+}
+
+/// Generates `n` reproducible Gaussian samples of standard deviation `sigma` by the
+/// Box-Muller transform of the same LCG.
+pub(crate) fn gaussian_noise(n: usize, sigma: f64) -> Array1<f64> {
+    let mut state: u64 = 12345;
+    let mut uniform = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    };
+    Array1::from_shape_fn(n, |_| {
+        let (u1, u2) = (uniform(), uniform());
+        sigma * (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    })
+}
@@ -0,0 +1,113 @@
+use crate::timeseries::core::TimeSeriesBase;
+use astronomy::units::{QuantityError, SECOND};
+
+// Offsets smaller than this fraction of a sample are attributed to floating-point rounding
+const SAMPLE_TOLERANCE: f64 = 1e-3;
+
+/// A break in the sample bookkeeping between two consecutive chunks of data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimingDiscontinuity {
+    // Index of the chunk whose start does not follow on from the previous chunk
+    pub chunk: usize,
+
+    // GPS time, in seconds, at which the previous chunk should have been continued
+    pub gps: f64,
+
+    // Offset of the chunk start from the expected time, in samples: positive values are
+    // dropped samples, negative values duplicated ones
+    pub sample_offset: f64,
+}
+
+impl TimingDiscontinuity {
+    /// Returns true if samples were lost between the chunks.
+    pub fn is_gap(&self) -> bool {
+        self.sample_offset > 0.0
+    }
+    /// Returns true if the chunk repeats samples already covered by its predecessor.
+    pub fn is_overlap(&self) -> bool {
+        self.sample_offset < 0.0
+    }
+}
+
+/// Compares the expected and actual sample counts across consecutive chunks of a stream.
+///
+/// Each chunk is expected to start exactly where the previous one ended, i.e. at
+/// `t0 + n dt` of its predecessor. Every chunk that does not is reported with its offset in
+/// samples, which exposes samples dropped or duplicated by the frame builder as well as
+/// sub-sample timing shifts. All chunks must have the same `dt`.
+pub fn find_timing_discontinuities(
+    chunks: &[TimeSeriesBase],
+) -> Result<Vec<TimingDiscontinuity>, QuantityError> {
+    let mut discontinuities = Vec::new();
+    let mut previous: Option<(f64, f64)> = None;
+    for (index, chunk) in chunks.iter().enumerate() {
+        let dt = chunk.dt_seconds()?;
+        let t0 = chunk
+            .get_t0()
+            .ok_or_else(|| QuantityError::InvalidQuantity(format!("Chunk {index} has no t0")))?
+            .to(&SECOND)?
+            .value[0];
+        if let Some((expected, previous_dt)) = previous {
+            if (dt - previous_dt).abs() > SAMPLE_TOLERANCE * dt {
+                return Err(QuantityError::MismatchError(format!(
+                    "Chunk {index} has dt = {dt} s but the previous chunk has dt = {previous_dt} s"
+                )));
+            }
+            let sample_offset = (t0 - expected) / dt;
+            if sample_offset.abs() > SAMPLE_TOLERANCE {
+                discontinuities.push(TimingDiscontinuity {
+                    chunk: index,
+                    gps: expected,
+                    sample_offset,
+                });
+            }
+        }
+        previous = Some((t0 + chunk.value().len() as f64 * dt, dt));
+    }
+    Ok(discontinuities)
+}
+
+// --- Tests for timing checks ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeseries::core::TimeSeriesBaseBuilder;
+    use astronomy::units::Quantity;
+    use ndarray::{Array1, array};
+
+    fn chunk(t0: f64, n: usize, dt: f64) -> TimeSeriesBase {
+        TimeSeriesBaseBuilder::new()
+            .value(Array1::zeros(n))
+            .t0(t0)
+            .dt(Quantity::new(array![dt], SECOND))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_find_timing_discontinuities() {
+        let chunks = [
+            chunk(100.0, 16, 0.0625),
+            chunk(101.0, 16, 0.0625),   // contiguous
+            chunk(102.25, 16, 0.0625),  // four samples dropped
+            chunk(103.125, 16, 0.0625), // two samples duplicated
+        ];
+        let discontinuities = find_timing_discontinuities(&chunks).unwrap();
+        assert_eq!(discontinuities.len(), 2);
+        assert_eq!(discontinuities[0].chunk, 2);
+        assert_eq!(discontinuities[0].gps, 102.0);
+        assert_eq!(discontinuities[0].sample_offset, 4.0);
+        assert!(discontinuities[0].is_gap());
+        assert_eq!(discontinuities[1].sample_offset, -2.0);
+        assert!(discontinuities[1].is_overlap());
+    }
+
+    #[test]
+    fn test_find_timing_discontinuities_requires_common_dt() {
+        let chunks = [chunk(0.0, 4, 1.0), chunk(4.0, 8, 0.5)];
+        assert!(matches!(
+            find_timing_discontinuities(&chunks),
+            Err(QuantityError::MismatchError(_))
+        ));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::timeseries::core::{TimeSeriesBase, TimeSeriesBaseBuilder};
+use astronomy::units::{Quantity, QuantityError, SECOND, Unit, UnitProduct};
+use ndarray::{Array1, array};
+
+/// Stride of second-trend data, in seconds.
+pub const SECOND_TREND_STRIDE: f64 = 1.0;
+
+/// Stride of minute-trend data, in seconds.
+pub const MINUTE_TREND_STRIDE: f64 = 60.0;
+
+// Fraction of a stride by which sample times may fall short of a bin boundary
+// and still be assigned to the next bin, absorbing rounding in `t0 + i dt`
+const BIN_TOLERANCE: f64 = 1e-9;
+
+/// Summary statistics of raw data over consecutive GPS-aligned strides, the reduced
+/// products stored in second- and minute-trend frames.
+///
+/// Each statistic is a `TimeSeriesBase` sampled once per stride, starting at the stride
+/// boundary at or before the first raw sample. Strides only partly covered by the data are
+/// kept, with `n` recording how many samples contributed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend {
+    pub mean: TimeSeriesBase,
+    pub min: TimeSeriesBase,
+    pub max: TimeSeriesBase,
+    pub rms: TimeSeriesBase,
+    pub n: TimeSeriesBase,
+}
+
+// -- Trend generation for TimeSeriesBase
+impl TimeSeriesBase {
+    /// Computes mean, minimum, maximum, RMS and sample count over consecutive `stride`s
+    /// aligned to integer multiples of `stride` in GPS time.
+    ///
+    /// Each statistic series is named `"<name>.<statistic>"` and keeps the channel; `n` is
+    /// dimensionless, the others carry the unit of the data.
+    pub fn trend(&self, stride: &Quantity) -> Result<Trend, QuantityError> {
+        if stride.value.len() != 1 || stride.value[0] <= 0.0 {
+            return Err(QuantityError::InvalidQuantity(
+                "stride must be a positive scalar quantity.".to_string(),
+            ));
+        }
+        if self.value().is_empty() {
+            return Err(QuantityError::InvalidQuantity(
+                "Cannot compute trends of an empty TimeSeriesBase".to_string(),
+            ));
+        }
+        let stride = stride.to(&SECOND)?.value[0];
+        let dt = self.dt_seconds()?;
+        let t0 = match self.get_t0() {
+            Some(t0) => t0.to(&SECOND)?.value[0],
+            None => 0.0,
+        };
+        let bin_of = |i: usize| ((t0 + i as f64 * dt) / stride + BIN_TOLERANCE).floor();
+        let first_bin = bin_of(0);
+        let n_bins = (bin_of(self.value().len() - 1) - first_bin) as usize + 1;
+
+        let mut sum = Array1::<f64>::zeros(n_bins);
+        let mut sum_squares = Array1::<f64>::zeros(n_bins);
+        let mut min = Array1::from_elem(n_bins, f64::INFINITY);
+        let mut max = Array1::from_elem(n_bins, f64::NEG_INFINITY);
+        let mut count = Array1::<f64>::zeros(n_bins);
+        for (i, &x) in self.value().iter().enumerate() {
+            let bin = (bin_of(i) - first_bin) as usize;
+            sum[bin] += x;
+            sum_squares[bin] += x * x;
+            min[bin] = min[bin].min(x);
+            max[bin] = max[bin].max(x);
+            count[bin] += 1.0;
+        }
+        // Strides shorter than dt can leave empty bins, which get n = 0 and a NaN mean and RMS
+        let mean = Array1::from_shape_fn(n_bins, |k| sum[k] / count[k]);
+        let rms = Array1::from_shape_fn(n_bins, |k| (sum_squares[k] / count[k]).sqrt());
+
+        let build = |values: Array1<f64>, statistic: &str, unit: Unit| {
+            let mut builder = TimeSeriesBaseBuilder::new()
+                .value(values)
+                .unit(unit)
+                .t0(first_bin * stride)
+                .dt(Quantity::new(array![stride], SECOND));
+            if let Some(name) = self.get_name() {
+                builder = builder.name(format!("{name}.{statistic}"));
+            }
+            if let Some(channel) = self.get_channel() {
+                builder = builder.source_channel(channel.clone());
+            }
+            builder.build()
+        };
+        let unit = self.unit();
+        Ok(Trend {
+            mean: build(mean, "mean", unit.clone())?,
+            min: build(min, "min", unit.clone())?,
+            max: build(max, "max", unit.clone())?,
+            rms: build(rms, "rms", unit.clone())?,
+            n: build(count, "n", Unit::new("", 1.0, UnitProduct::zero()))?,
+        })
+    }
+
+    /// Computes the root-mean-square over consecutive `stride`s from the start of the data,
+    /// e.g. a 1 Hz band-limited RMS after bandpassing.
+    ///
+    /// Unlike [`trend`](Self::trend) the strides are not aligned to GPS multiples: the result
+    /// starts at `t0`, has `dt = stride` and drops an incomplete trailing stride. It keeps the
+    /// unit, name and channel of the data.
+    pub fn rms(&self, stride: &Quantity) -> Result<TimeSeriesBase, QuantityError> {
+        if stride.value.len() != 1 || stride.value[0] <= 0.0 {
+            return Err(QuantityError::InvalidQuantity(
+                "stride must be a positive scalar quantity.".to_string(),
+            ));
+        }
+        let stride = stride.to(&SECOND)?.value[0];
+        let nstride = (stride / self.dt_seconds()?).round() as usize;
+        if nstride == 0 || nstride > self.value().len() {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "stride ({nstride} samples) must be between one sample and the data length ({} \
+                 samples)",
+                self.value().len()
+            )));
+        }
+        let values = Array1::from_iter(
+            self.value()
+                .exact_chunks(nstride)
+                .into_iter()
+                .map(|chunk| chunk.mapv(|x| x * x).mean().unwrap_or(0.0).sqrt()),
+        );
+        let mut builder = TimeSeriesBaseBuilder::new()
+            .value(values)
+            .unit(self.unit().clone())
+            .dt(Quantity::new(array![stride], SECOND));
+        if let Some(t0) = self.get_t0() {
+            builder = builder.t0(t0.to(&SECOND)?.value[0]);
+        }
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        builder.build()
+    }
+
+    /// Computes second-trend statistics, see [`trend`](Self::trend).
+    pub fn second_trend(&self) -> Result<Trend, QuantityError> {
+        self.trend(&Quantity::new(array![SECOND_TREND_STRIDE], SECOND))
+    }
+
+    /// Computes minute-trend statistics, see [`trend`](Self::trend).
+    pub fn minute_trend(&self) -> Result<Trend, QuantityError> {
+        self.trend(&Quantity::new(array![MINUTE_TREND_STRIDE], SECOND))
+    }
+}
+
+// --- Tests for trend generation ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use astronomy::units::METRE;
+
+    #[test]
+    fn test_second_trend_with_partial_strides() {
+        // 4 Hz samples 0, 1, ..., 9 starting half way through GPS second 10
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::from_shape_fn(10, |i| i as f64))
+            .unit(METRE)
+            .t0(10.5)
+            .dt(Quantity::new(array![0.25], SECOND))
+            .name("X1:RAW")
+            .build()
+            .unwrap();
+
+        let trend = ts.second_trend().unwrap();
+
+        assert_eq!(trend.mean.get_t0().unwrap().value[0], 10.0);
+        assert_eq!(trend.mean.get_dt().unwrap().value[0], 1.0);
+        assert_eq!(trend.mean.get_name(), Some("X1:RAW.mean"));
+        assert_eq!(trend.mean.unit(), &METRE);
+        assert_eq!(trend.n.value(), &array![2.0, 4.0, 4.0]);
+        assert_eq!(trend.mean.value(), &array![0.5, 3.5, 7.5]);
+        assert_eq!(trend.min.value(), &array![0.0, 2.0, 6.0]);
+        assert_eq!(trend.max.value(), &array![1.0, 5.0, 9.0]);
+        assert!((trend.rms.value()[0] - 0.5_f64.sqrt()).abs() < 1e-12);
+        assert!((trend.rms.value()[1] - (54.0_f64 / 4.0).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_minute_trend_single_stride() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(Array1::from_elem(60, -2.0))
+            .t0(1_200_000_000.0)
+            .dt(Quantity::new(array![1.0], SECOND))
+            .build()
+            .unwrap();
+        let trend = ts.minute_trend().unwrap();
+        assert_eq!(trend.n.value(), &array![60.0]);
+        assert_eq!(trend.rms.value(), &array![2.0]);
+        assert_eq!(trend.mean.get_t0().unwrap().value[0], 1_200_000_000.0);
+    }
+
+    #[test]
+    fn test_rms_over_stride() {
+        let ts = TimeSeriesBaseBuilder::new()
+            .value(array![3.0, -3.0, 1.0, 1.0, 1.0, -1.0, 2.0, 2.0, 5.0])
+            .unit(METRE)
+            .t0(100.25)
+            .dt(Quantity::new(array![0.25], SECOND))
+            .name("X1:BLRMS")
+            .build()
+            .unwrap();
+        let rms = ts.rms(&Quantity::new(array![0.5], SECOND)).unwrap();
+        assert_eq!(rms.value(), &array![3.0, 1.0, 1.0, 2.0]);
+        assert_eq!(rms.get_t0().unwrap().value[0], 100.25);
+        assert_eq!(rms.get_dt().unwrap().value[0], 0.5);
+        assert_eq!(rms.unit(), &METRE);
+        assert_eq!(rms.get_name(), Some("X1:BLRMS"));
+        assert!(ts.rms(&Quantity::new(array![10.0], SECOND)).is_err());
+    }
+}
@@ -1,7 +1,13 @@
 use crate::detector::channel::Channel;
 use astronomy::time::Time;
-use astronomy::units::{Quantity, QuantityError, Unit, UnitProduct};
-use ndarray::Array1;
+use astronomy::units::{
+    CENTIMETRE, Dimension, HERTZ, JOULE, METRE, Quantity, QuantityError, SECOND, Unit, UnitProduct,
+};
+use ndarray::{Array1, Array2, Zip, array};
+use rustfft::num_complex::Complex64;
+use std::collections::HashSet;
+use std::mem::size_of;
+use std::sync::{LazyLock, Mutex, PoisonError};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct GWArray {
@@ -9,6 +15,8 @@ pub struct GWArray {
     pub name: Option<String>,
     pub epoch: Option<Time>,
     pub channel: Option<Channel>,
+    // Imaginary part of complex data, in the same unit; `None` for real data
+    imag: Option<Array1<f64>>,
 }
 
 impl GWArray {
@@ -29,13 +37,70 @@ impl GWArray {
             name,
             epoch,
             channel,
+            imag: None,
         }
     }
 
+    /// Creates complex data from its real and imaginary parts, which must have the same
+    /// length and share `unit`.
+    pub fn new_complex(
+        real: Array1<f64>,
+        imag: Array1<f64>,
+        unit: Option<Unit>,
+        name: Option<String>,
+        epoch: Option<Time>,
+        channel: Option<Channel>,
+    ) -> Result<Self, QuantityError> {
+        if imag.len() != real.len() {
+            return Err(QuantityError::MismatchError(format!(
+                "Imaginary part length ({}) must match value length ({})",
+                imag.len(),
+                real.len()
+            )));
+        }
+        let mut array = GWArray::new(real, unit, name, epoch, channel);
+        array.imag = Some(imag);
+        Ok(array)
+    }
+
+    /// Creates complex data from complex values, such as the output of an FFT.
+    pub fn from_complex(
+        values: &Array1<Complex64>,
+        unit: Option<Unit>,
+        name: Option<String>,
+        epoch: Option<Time>,
+        channel: Option<Channel>,
+    ) -> Self {
+        let mut array = GWArray::new(values.mapv(|z| z.re), unit, name, epoch, channel);
+        array.imag = Some(values.mapv(|z| z.im));
+        array
+    }
+
+    /// Returns the values, or their real part for complex data.
     pub fn value(&self) -> &Array1<f64> {
         &self.quantity.value
     }
 
+    /// Returns the imaginary part of complex data.
+    pub fn imag(&self) -> Option<&Array1<f64>> {
+        self.imag.as_ref()
+    }
+
+    /// Returns true if the data carry an imaginary part.
+    pub fn is_complex(&self) -> bool {
+        self.imag.is_some()
+    }
+
+    /// Returns the values as complex numbers, with zero imaginary part for real data.
+    pub fn to_complex(&self) -> Array1<Complex64> {
+        match &self.imag {
+            Some(imag) => Zip::from(self.value())
+                .and(imag)
+                .map_collect(|&re, &im| Complex64::new(re, im)),
+            None => self.value().mapv(|re| Complex64::new(re, 0.0)),
+        }
+    }
+
     pub fn unit(&self) -> &Unit {
         &self.quantity.unit
     }
@@ -52,71 +117,499 @@ impl GWArray {
         self.channel.as_ref()
     }
 
+    /// Returns the approximate memory footprint in bytes, counting the struct itself, the
+    /// sample buffers and the name.
+    pub fn mem_size(&self) -> usize {
+        size_of::<Self>()
+            + quantity_heap_size(&self.quantity)
+            + self
+                .imag
+                .as_ref()
+                .map_or(0, |imag| imag.len() * size_of::<f64>())
+            + self.name.as_ref().map_or(0, String::capacity)
+    }
+
+    /// Returns the arithmetic mean of the values, in the unit of the array.
+    pub fn mean(&self) -> Result<Quantity, QuantityError> {
+        let values = self.nonempty_values("mean")?;
+        Ok(self.scalar(values.sum() / values.len() as f64))
+    }
+
+    /// Returns the median of the values, the mean of the two middle values for an even
+    /// count, in the unit of the array.
+    pub fn median(&self) -> Result<Quantity, QuantityError> {
+        let mut sorted = self.nonempty_values("median")?.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let middle = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 0 {
+            0.5 * (sorted[middle - 1] + sorted[middle])
+        } else {
+            sorted[middle]
+        };
+        Ok(self.scalar(median))
+    }
+
+    /// Returns the population standard deviation of the values (normalised by their count,
+    /// as `numpy.std`), in the unit of the array.
+    pub fn std(&self) -> Result<Quantity, QuantityError> {
+        let values = self.nonempty_values("standard deviation")?;
+        Ok(self.scalar(values.std(0.0)))
+    }
+
+    /// Returns the smallest value, in the unit of the array.
+    pub fn min(&self) -> Result<Quantity, QuantityError> {
+        let values = self.nonempty_values("minimum")?;
+        Ok(self.scalar(values.fold(f64::INFINITY, |a, &b| a.min(b))))
+    }
+
+    /// Returns the largest value, in the unit of the array.
+    pub fn max(&self) -> Result<Quantity, QuantityError> {
+        let values = self.nonempty_values("maximum")?;
+        Ok(self.scalar(values.fold(f64::NEG_INFINITY, |a, &b| a.max(b))))
+    }
+
+    /// Returns the largest absolute value, the largest magnitude for complex data, in the
+    /// unit of the array.
+    pub fn abs_max(&self) -> Result<Quantity, QuantityError> {
+        if self.value().is_empty() {
+            return Err(QuantityError::InvalidQuantity(
+                "Cannot take the absolute maximum of an empty array".to_string(),
+            ));
+        }
+        Ok(self.scalar(self.abs().value().fold(0.0, |a, &b| a.max(b))))
+    }
+
+    // The values, or an error naming the statistic if there are none or they are complex
+    fn nonempty_values(&self, statistic: &str) -> Result<&Array1<f64>, QuantityError> {
+        if self.is_complex() {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "Cannot take the {statistic} of complex data; take its magnitude first"
+            )));
+        }
+        if self.value().is_empty() {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "Cannot take the {statistic} of an empty array"
+            )));
+        }
+        Ok(self.value())
+    }
+
+    // A scalar quantity in the unit of the array
+    fn scalar(&self, value: f64) -> Quantity {
+        Quantity::new(Array1::from_elem(1, value), self.unit().clone())
+    }
+
+    /// Returns the absolute values, or the magnitudes of complex data, in the unit of the
+    /// array.
+    pub fn abs(&self) -> Self {
+        let values = match &self.imag {
+            Some(imag) => Zip::from(self.value())
+                .and(imag)
+                .map_collect(|re, im| re.hypot(*im)),
+            None => self.value().mapv(f64::abs),
+        };
+        self.with_quantity(Quantity::new(values, self.unit().clone()))
+    }
+
+    /// Returns the phase of each value in radians, in `(-pi, pi]`; real data have phase 0
+    /// or pi.
+    pub fn phase(&self) -> Self {
+        let values = self.to_complex().mapv(|z| z.im.atan2(z.re));
+        self.with_quantity(Quantity::new(
+            values,
+            Unit::new("rad", 1.0, UnitProduct::zero()),
+        ))
+    }
+
+    /// Returns the complex conjugate, which leaves real data unchanged.
+    pub fn conj(&self) -> Self {
+        let mut conjugate = self.clone();
+        if let Some(imag) = &mut conjugate.imag {
+            imag.mapv_inplace(|im| -im);
+        }
+        conjugate
+    }
+
+    /// Returns the square roots of the values, in the square root of the unit, whose
+    /// dimension exponents are halved. Complex data take the principal root.
+    ///
+    /// The root unit is looked up among the powers of base units and of common units of
+    /// detector data. Units with an odd exponent, such as `m²/Hz`, or with no root there are
+    /// an error.
+    pub fn sqrt(&self) -> Result<Self, QuantityError> {
+        let unit = unit_sqrt(self.unit())?;
+        Ok(match self.imag {
+            Some(_) => self.with_complex(self.to_complex().mapv(|z| z.sqrt()), unit),
+            None => self.with_quantity(Quantity::new(self.value().mapv(f64::sqrt), unit)),
+        })
+    }
+
+    /// Returns the base-10 logarithms of the values, which must be dimensionless; a scaled
+    /// dimensionless unit is applied before taking the logarithm. Complex data take the
+    /// principal logarithm.
+    pub fn log10(&self) -> Result<Self, QuantityError> {
+        let dimensionless = Unit::new("", 1.0, UnitProduct::zero());
+        if self.unit().dimensions != dimensionless.dimensions {
+            return Err(QuantityError::IncompatibleUnits {
+                from: self.unit().name.to_string(),
+                to: "dimensionless".to_string(),
+            });
+        }
+        let converted = self.to(&dimensionless)?;
+        Ok(match self.imag {
+            Some(_) => {
+                converted.with_complex(converted.to_complex().mapv(|z| z.log10()), dimensionless)
+            }
+            None => self.with_quantity(Quantity::new(
+                converted.value().mapv(f64::log10),
+                dimensionless,
+            )),
+        })
+    }
+
+    /// Returns the values raised to the integer power `n`, in the unit to the `n`.
+    pub fn powi(&self, n: i32) -> Result<Self, QuantityError> {
+        let unit = unit_powi(self.unit(), n);
+        Ok(match self.imag {
+            Some(_) => self.with_complex(self.to_complex().mapv(|z| z.powi(n)), unit),
+            None => self.with_quantity(Quantity::new(self.value().mapv(|x| x.powi(n)), unit)),
+        })
+    }
+
+    // A copy of the metadata around new real values
+    fn with_quantity(&self, quantity: Quantity) -> Self {
+        GWArray {
+            quantity,
+            name: self.name.clone(),
+            epoch: self.epoch,
+            channel: self.channel.clone(),
+            imag: None,
+        }
+    }
+
+    // A copy of the metadata around new complex values
+    fn with_complex(&self, values: Array1<Complex64>, unit: Unit) -> Self {
+        GWArray::from_complex(
+            &values,
+            Some(unit),
+            self.name.clone(),
+            self.epoch,
+            self.channel.clone(),
+        )
+    }
+
+    // The real and imaginary parts as quantities, the latter zero for real data
+    fn parts(&self) -> (Quantity, Quantity) {
+        let imag = match &self.imag {
+            Some(imag) => imag.clone(),
+            None => Array1::zeros(self.value().len()),
+        };
+        (
+            self.quantity.clone(),
+            Quantity::new(imag, self.unit().clone()),
+        )
+    }
+
+    // A copy of the metadata around the result of an operation, complex if either operand is
+    fn with_parts(&self, real: Quantity, imag: Option<Quantity>) -> Self {
+        let mut array = self.with_quantity(real);
+        array.imag = imag.map(|imag| imag.value);
+        array
+    }
+
     pub fn to(&self, target_unit: &Unit) -> Result<Self, QuantityError> {
         let converted_quantity = self.quantity.to(target_unit)?;
-        Ok(GWArray::new(
+        let imag = match &self.imag {
+            Some(imag) => Some(
+                Quantity::new(imag.clone(), self.unit().clone())
+                    .to(target_unit)?
+                    .value,
+            ),
+            None => None,
+        };
+        let mut converted = GWArray::new(
             converted_quantity.value,
             Some(converted_quantity.unit),
             self.name.clone(),
             self.epoch,
             self.channel.clone(),
-        ))
+        );
+        converted.imag = imag;
+        Ok(converted)
+    }
+}
+
+// Largest power of a probe unit tried as the square root of a unit
+const MAX_ROOT_POWER: i32 = 3;
+
+// Names of derived units, each leaked once since `Unit` names are `&'static str`
+static DERIVED_NAMES: LazyLock<Mutex<HashSet<&'static str>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+// The units whose powers are probed for the square root of a unit: dimensionless, the base
+// units of the mechanical and electrical dimensions and common units of detector data
+fn root_probes() -> [Unit; 9] {
+    let base = |name, dimension| Unit::new(name, 1.0, UnitProduct::new(dimension));
+    [
+        Unit::new("", 1.0, UnitProduct::zero()),
+        METRE,
+        CENTIMETRE,
+        SECOND,
+        HERTZ,
+        JOULE,
+        base("kg", Dimension::Mass),
+        base("A", Dimension::ElectricCurrent),
+        Unit::new(
+            "V",
+            1.0,
+            UnitProduct::from_components(&[
+                (Dimension::Mass, 1),
+                (Dimension::Length, 2),
+                (Dimension::Time, -3),
+                (Dimension::ElectricCurrent, -1),
+            ]),
+        ),
+    ]
+}
+
+// A `&'static str` for a derived unit name, reusing the one leaked for an earlier equal name
+fn derived_name(name: String) -> &'static str {
+    let mut names = DERIVED_NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(&existing) = names.get(name.as_str()) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(name.into_boxed_str());
+    names.insert(leaked);
+    leaked
+}
+
+// The name of a unit called `name` raised to `power`, e.g. `m^2` or `(m/s)^-1`; unnamed
+// units stay unnamed
+fn power_name(name: &str, power: &str) -> &'static str {
+    if name.is_empty() {
+        ""
+    } else if name.chars().all(char::is_alphanumeric) {
+        derived_name(format!("{name}^{power}"))
+    } else {
+        derived_name(format!("({name})^{power}"))
+    }
+}
+
+// The unit whose square is `unit`. `astronomy` units expose their dimensions only through
+// comparison, so the root is looked up among the powers up to ±MAX_ROOT_POWER of a fixed
+// set of probe units. It takes the name of a probe power of the same scale if there is one,
+// and is otherwise named after `unit`.
+pub(crate) fn unit_sqrt(unit: &Unit) -> Result<Unit, QuantityError> {
+    // Lowest powers first, so that a probe is preferred to a power of another one
+    let probe_powers = || {
+        (1..=MAX_ROOT_POWER)
+            .flat_map(|power| [power, -power])
+            .flat_map(|power| root_probes().map(|probe| (probe, power)))
+    };
+    let (probe, power) = probe_powers()
+        .find(|(probe, power)| unit_powi(probe, 2 * power).dimensions == unit.dimensions)
+        .ok_or_else(|| {
+            QuantityError::InvalidQuantity(format!(
+                "The unit '{}' has no square root among the powers of known units",
+                unit.name
+            ))
+        })?;
+    let dimensions = unit_powi(&probe, power).dimensions;
+    let scale = unit.scale.sqrt();
+    let name = probe_powers()
+        .map(|(probe, power)| unit_powi(&probe, power))
+        .find(|root| root.dimensions == dimensions && (root.scale - scale).abs() <= 1e-12 * scale)
+        .map_or_else(|| power_name(unit.name, "(1/2)"), |root| root.name);
+    Ok(Unit::new(name, scale, dimensions))
+}
+
+// The unit raised to the integer power `n`, built by repeated squaring of quantities
+fn unit_powi(unit: &Unit, n: i32) -> Unit {
+    let base = if n < 0 {
+        Unit::new("", 1.0 / unit.scale, unit.dimensions.inverse())
+    } else {
+        unit.clone()
+    };
+    let one = |unit: Unit| Quantity::new(array![1.0], unit);
+    let mut power = one(Unit::new("", 1.0, UnitProduct::zero()));
+    let mut square = one(base);
+    let mut remaining = n.unsigned_abs();
+    while remaining > 0 {
+        if remaining & 1 == 1 {
+            power = power * square.clone();
+        }
+        square = square.clone() * square;
+        remaining >>= 1;
     }
+    let name = match n {
+        0 => "",
+        1 => unit.name,
+        _ => power_name(unit.name, &n.to_string()),
+    };
+    Unit::new(name, power.unit.scale, power.unit.dimensions)
+}
+
+// Heap bytes held by the values of a quantity
+pub(crate) fn quantity_heap_size(quantity: &Quantity) -> usize {
+    quantity.value.len() * size_of::<f64>()
 }
 
 use std::ops::{Add, Div, Mul, Sub};
 
+// Complex data combine as complex numbers, with a real operand taken to have zero
+// imaginary part; the metadata of the left operand are kept.
+
 impl Add for GWArray {
     type Output = Result<Self, QuantityError>;
     fn add(self, rhs: Self) -> Self::Output {
-        let added_quantity = (self.quantity + rhs.quantity)?;
-        Ok(GWArray::new(
-            added_quantity.value,
-            Some(added_quantity.unit),
-            self.name.clone(),
-            self.epoch,
-            self.channel.clone(),
-        ))
+        let imag = if self.is_complex() || rhs.is_complex() {
+            Some((self.parts().1 + rhs.parts().1)?)
+        } else {
+            None
+        };
+        let added_quantity = (self.quantity.clone() + rhs.quantity)?;
+        Ok(self.with_parts(added_quantity, imag))
     }
 }
 
 impl Sub for GWArray {
     type Output = Result<Self, QuantityError>;
     fn sub(self, rhs: Self) -> Self::Output {
-        let subtracted_quantity = (self.quantity - rhs.quantity)?;
-        Ok(GWArray::new(
-            subtracted_quantity.value,
-            Some(subtracted_quantity.unit),
-            self.name.clone(),
-            self.epoch,
-            self.channel.clone(),
-        ))
+        let imag = if self.is_complex() || rhs.is_complex() {
+            Some((self.parts().1 - rhs.parts().1)?)
+        } else {
+            None
+        };
+        let subtracted_quantity = (self.quantity.clone() - rhs.quantity)?;
+        Ok(self.with_parts(subtracted_quantity, imag))
     }
 }
 impl Mul for GWArray {
     type Output = Result<Self, QuantityError>;
     fn mul(self, rhs: Self) -> Self::Output {
-        let multiplied_quantity = self.quantity * rhs.quantity;
-        Ok(GWArray::new(
-            multiplied_quantity.value,
-            Some(multiplied_quantity.unit),
-            self.name.clone(),
-            self.epoch,
-            self.channel.clone(),
-        ))
+        if !self.is_complex() && !rhs.is_complex() {
+            let multiplied_quantity = self.quantity.clone() * rhs.quantity;
+            return Ok(self.with_parts(multiplied_quantity, None));
+        }
+        // (a + ib)(c + id) = (ac - bd) + i(ad + bc)
+        let ((a, b), (c, d)) = (self.parts(), rhs.parts());
+        let real = (a.clone() * c.clone() - b.clone() * d.clone())?;
+        let imag = (a * d + b * c)?;
+        Ok(self.with_parts(real, Some(imag)))
     }
 }
 impl Div for GWArray {
     type Output = Result<Self, QuantityError>;
     fn div(self, rhs: Self) -> Self::Output {
-        let divided_quantity = (self.quantity / rhs.quantity)?;
-        Ok(GWArray::new(
-            divided_quantity.value,
-            Some(divided_quantity.unit),
+        if !self.is_complex() && !rhs.is_complex() {
+            let divided_quantity = (self.quantity.clone() / rhs.quantity)?;
+            return Ok(self.with_parts(divided_quantity, None));
+        }
+        // (a + ib) / (c + id) = ((ac + bd) + i(bc - ad)) / (c² + d²)
+        let ((a, b), (c, d)) = (self.parts(), rhs.parts());
+        let norm = (c.clone() * c.clone() + d.clone() * d.clone())?;
+        let real = ((a.clone() * c.clone() + b.clone() * d.clone())? / norm.clone())?;
+        let imag = ((b * c - a * d)? / norm)?;
+        Ok(self.with_parts(real, Some(imag)))
+    }
+}
+
+/// A matrix of values sharing one unit, with the same metadata as [`GWArray`], e.g. the
+/// value matrix of a spectrogram indexed by `[time, frequency]` or a coherence matrix
+/// indexed by `[channel, channel]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GWArray2 {
+    pub value: Array2<f64>,
+    pub unit: Unit,
+    pub name: Option<String>,
+    pub epoch: Option<Time>,
+    pub channel: Option<Channel>,
+}
+
+impl GWArray2 {
+    pub fn new(
+        value: Array2<f64>,
+        unit: Option<Unit>,
+        name: Option<String>,
+        epoch: Option<Time>,
+        channel: Option<Channel>,
+    ) -> Self {
+        GWArray2 {
+            value,
+            unit: unit.unwrap_or_else(|| Unit::new("", 1.0, UnitProduct::zero())),
+            name,
+            epoch,
+            channel,
+        }
+    }
+
+    pub fn value(&self) -> &Array2<f64> {
+        &self.value
+    }
+
+    pub fn unit(&self) -> &Unit {
+        &self.unit
+    }
+
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn get_epoch(&self) -> Option<Time> {
+        self.epoch
+    }
+
+    pub fn get_channel(&self) -> Option<&Channel> {
+        self.channel.as_ref()
+    }
+
+    /// Returns the number of rows and columns.
+    pub fn dim(&self) -> (usize, usize) {
+        self.value.dim()
+    }
+
+    /// Returns the approximate memory footprint in bytes, counting the struct itself, the
+    /// value matrix and the name.
+    pub fn mem_size(&self) -> usize {
+        size_of::<Self>()
+            + self.value.len() * size_of::<f64>()
+            + self.name.as_ref().map_or(0, String::capacity)
+    }
+
+    /// Returns row `index` as a [`GWArray`] with the same unit and metadata, or `None` if
+    /// it is out of bounds.
+    pub fn row(&self, index: usize) -> Option<GWArray> {
+        (index < self.value.nrows()).then(|| self.lane(self.value.row(index).to_owned()))
+    }
+
+    /// Returns column `index` as a [`GWArray`] with the same unit and metadata, or `None`
+    /// if it is out of bounds.
+    pub fn column(&self, index: usize) -> Option<GWArray> {
+        (index < self.value.ncols()).then(|| self.lane(self.value.column(index).to_owned()))
+    }
+
+    // A copy of the metadata around one row or column
+    fn lane(&self, values: Array1<f64>) -> GWArray {
+        GWArray::new(
+            values,
+            Some(self.unit.clone()),
             self.name.clone(),
             self.epoch,
             self.channel.clone(),
-        ))
+        )
+    }
+
+    pub fn to(&self, target_unit: &Unit) -> Result<Self, QuantityError> {
+        let factor = Quantity::new(array![1.0], self.unit.clone())
+            .to(target_unit)?
+            .value[0];
+        Ok(GWArray2 {
+            value: &self.value * factor,
+            unit: target_unit.clone(),
+            name: self.name.clone(),
+            epoch: self.epoch,
+            channel: self.channel.clone(),
+        })
     }
 }
 
@@ -124,8 +617,8 @@ impl Div for GWArray {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use astronomy::units::{CENTIMETRE, METRE, SECOND};
     use ndarray::array;
+    use std::f64::consts::PI;
 
     #[test]
     fn test_gwarray_creation() {
@@ -172,6 +665,127 @@ mod tests {
         assert_eq!(converted_array.name, Some("Test Array".to_string()));
     }
 
+    #[test]
+    fn test_gw_array_statistics() {
+        let gw_array = GWArray::new(array![3.0, -5.0, 1.0, 2.0], Some(METRE), None, None, None);
+        assert_eq!(gw_array.mean().unwrap(), Quantity::new(array![0.25], METRE));
+        assert_eq!(gw_array.median().unwrap().value[0], 1.5);
+        assert_eq!(gw_array.min().unwrap().value[0], -5.0);
+        assert_eq!(gw_array.max().unwrap().value[0], 3.0);
+        assert_eq!(gw_array.abs_max().unwrap().value[0], 5.0);
+        let std = gw_array.std().unwrap();
+        assert!((std.value[0] - 38.75_f64.sqrt() / 2.0).abs() < 1e-12);
+        assert_eq!(std.unit, METRE);
+
+        let odd = GWArray::new(array![3.0, -5.0, 1.0], Some(SECOND), None, None, None);
+        assert_eq!(odd.median().unwrap(), Quantity::new(array![1.0], SECOND));
+        let empty = GWArray::new(array![], Some(METRE), None, None, None);
+        assert!(empty.mean().is_err());
+        assert!(empty.abs_max().is_err());
+    }
+
+    #[test]
+    fn test_gw_array_math() {
+        let area = (Quantity::new(array![1.0], METRE) * Quantity::new(array![1.0], METRE)).unit;
+        let gw_array = GWArray::new(
+            array![4.0, 9.0],
+            Some(area.clone()),
+            Some("Area".to_string()),
+            None,
+            None,
+        );
+        let root = gw_array.sqrt().unwrap();
+        assert_eq!(root.value(), &array![2.0, 3.0]);
+        assert_eq!(root.unit().dimensions, METRE.dimensions);
+        assert_eq!(root.name, Some("Area".to_string()));
+        let square = root.powi(2).unwrap();
+        assert_eq!(square.unit().dimensions, area.dimensions);
+        let inverse = root.powi(-1).unwrap();
+        assert_eq!(inverse.value(), &array![0.5, 1.0 / 3.0]);
+        assert_eq!(
+            (inverse.quantity * Quantity::new(array![1.0], METRE))
+                .unit
+                .dimensions,
+            UnitProduct::zero()
+        );
+        assert!(
+            GWArray::new(array![1.0], Some(METRE), None, None, None)
+                .sqrt()
+                .is_err()
+        );
+        // The scale is rooted with the exponents, and roots take the names of known units
+        assert_eq!(root.unit().name, "m");
+        let metre_sixth = root.powi(6).unwrap();
+        assert_eq!(metre_sixth.unit().name, "m^6");
+        let metre_cubed = metre_sixth.sqrt().unwrap();
+        assert_eq!(
+            metre_cubed.unit().dimensions,
+            root.powi(3).unwrap().unit().dimensions
+        );
+        assert_eq!(metre_cubed.unit().name, "m^3");
+        let square_centimetres = GWArray::new(array![4.0], Some(CENTIMETRE), None, None, None)
+            .powi(2)
+            .unwrap();
+        let centimetres = square_centimetres.sqrt().unwrap();
+        assert_eq!(centimetres.value(), &array![4.0]);
+        assert_eq!(centimetres.unit(), &CENTIMETRE);
+        let per_second = GWArray::new(array![4.0], Some(SECOND), None, None, None)
+            .powi(-2)
+            .unwrap()
+            .sqrt()
+            .unwrap();
+        assert_eq!(per_second.unit(), &HERTZ);
+
+        let signed = GWArray::new(array![-2.0, 3.0], Some(SECOND), None, None, None);
+        assert_eq!(signed.abs().value(), &array![2.0, 3.0]);
+        assert_eq!(signed.abs().unit(), &SECOND);
+        assert!(signed.log10().is_err());
+        let ratio = GWArray::new(array![1.0, 100.0], None, None, None, None);
+        assert_eq!(ratio.log10().unwrap().value(), &array![0.0, 2.0]);
+    }
+
+    #[test]
+    fn test_gw_array_complex() {
+        let z = GWArray::new_complex(
+            array![3.0, 0.0],
+            array![4.0, -2.0],
+            Some(METRE),
+            Some("Z".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(z.is_complex());
+        assert_eq!(z.abs().value(), &array![5.0, 2.0]);
+        assert!(!z.abs().is_complex());
+        assert_eq!(z.phase().value()[1], -PI / 2.0);
+        assert_eq!(z.conj().imag(), Some(&array![-4.0, 2.0]));
+        assert_eq!(z.abs_max().unwrap().value[0], 5.0);
+        assert!(z.mean().is_err());
+        let converted = z.to(&CENTIMETRE).unwrap();
+        assert_eq!(converted.imag(), Some(&array![400.0, -200.0]));
+
+        // (3 + 4i)(3 - 4i) = 25 and (-2i)(2i) = 4, with a real operand promoted
+        let product = (z.clone() * z.conj()).unwrap();
+        assert_eq!(product.value(), &array![25.0, 4.0]);
+        assert_eq!(product.imag(), Some(&array![0.0, 0.0]));
+        assert_eq!(product.get_name(), Some("Z"));
+        let real = GWArray::new(array![1.0, 1.0], Some(METRE), None, None, None);
+        let sum = (real.clone() + z.clone()).unwrap();
+        assert_eq!(sum.value(), &array![4.0, 1.0]);
+        assert_eq!(sum.imag(), Some(&array![4.0, -2.0]));
+        let quotient = (z.clone() / real).unwrap();
+        assert_eq!(quotient.to_complex(), z.to_complex());
+        assert_eq!(quotient.unit().dimensions, UnitProduct::zero());
+
+        let root =
+            GWArray::from_complex(&array![Complex64::new(-4.0, 0.0)], None, None, None, None)
+                .sqrt()
+                .unwrap();
+        assert_eq!(root.to_complex()[0], Complex64::new(0.0, 2.0));
+        assert!(GWArray::new_complex(array![1.0], array![], None, None, None, None).is_err());
+    }
+
     #[test]
     fn test_gw_array_addition() {
         let gw_array1 = GWArray::new(array![1.0, 2.0, 3.0], Some(METRE.clone()), None, None, None);
@@ -225,4 +839,30 @@ mod tests {
             panic!("Expected incompatible addition error");
         }
     }
+
+    #[test]
+    fn test_gw_array2() {
+        let matrix = GWArray2::new(
+            array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]],
+            Some(METRE),
+            Some("Matrix".to_string()),
+            None,
+            None,
+        );
+        assert_eq!(matrix.dim(), (2, 3));
+        let row = matrix.row(1).unwrap();
+        assert_eq!(row.value(), &array![4.0, 5.0, 6.0]);
+        assert_eq!(row.unit(), &METRE);
+        assert_eq!(row.get_name(), Some("Matrix"));
+        assert_eq!(matrix.column(2).unwrap().value(), &array![3.0, 6.0]);
+        assert!(matrix.row(2).is_none() && matrix.column(3).is_none());
+
+        let converted = matrix.to(&CENTIMETRE).unwrap();
+        assert_eq!(
+            converted.value(),
+            &array![[100.0, 200.0, 300.0], [400.0, 500.0, 600.0]]
+        );
+        assert_eq!(converted.unit(), &CENTIMETRE);
+        assert!(matrix.to(&SECOND).is_err());
+    }
 }
@@ -0,0 +1,250 @@
+use crate::types::series::{Series, SeriesBuilder};
+use astronomy::units::{Quantity, QuantityError, Unit};
+use ndarray::{Array1, array, s};
+
+// -- Differentiation and integration for Series
+impl Series {
+    /// Returns the `n`-th order finite difference of the data divided by the x-axis spacing,
+    /// an estimate of the `n`-th derivative, in the unit of the data per x-unit to the `n`.
+    ///
+    /// Each order divides the difference of neighbouring values by their spacing, so
+    /// irregular x-indices are supported. The result has `n` fewer samples and, as in GWpy,
+    /// is indexed by the later sample of each difference: `x0` moves forward by `n dx`.
+    pub fn diff(&self, n: usize) -> Result<Series, QuantityError> {
+        let x = self.x_values()?;
+        if n >= x.len() {
+            return Err(QuantityError::InvalidQuantity(format!(
+                "An order {n} difference needs more than {n} samples, found {}",
+                x.len()
+            )));
+        }
+        let mut values = self.value().clone();
+        for order in 1..=n {
+            // Divided differences, scaled by the order to approximate the derivative
+            let spans = &x.slice(s![order..]) - &x.slice(s![..x.len() - order]);
+            values = (&values.slice(s![1..]) - &values.slice(s![..-1])) / spans * order as f64;
+        }
+        let mut builder = self.derived_builder(values, self.per_xunit(n)?);
+        match (self.get_x0(), self.get_dx()) {
+            (Some(_), Some(dx_quantity)) => {
+                builder = builder
+                    .x0(Quantity::new(array![x[n]], self.xunit()?.clone()))
+                    .dx(dx_quantity.clone());
+            }
+            _ => {
+                builder = builder.xindex(Quantity::new(
+                    x.slice(s![n..]).to_owned(),
+                    self.xunit()?.clone(),
+                ));
+            }
+        }
+        builder.build()
+    }
+
+    /// Returns the derivative of the data along the x-axis, in the unit of the data per
+    /// x-unit, on the same x-axis.
+    ///
+    /// As `numpy.gradient`, interior samples use second-order central differences, which
+    /// account for uneven spacing, and the two end samples use one-sided differences.
+    pub fn gradient(&self) -> Result<Series, QuantityError> {
+        let x = self.x_values()?;
+        let y = self.value();
+        let n = x.len();
+        if n < 2 {
+            return Err(QuantityError::InvalidQuantity(
+                "The gradient needs at least two samples".to_string(),
+            ));
+        }
+        let values = Array1::from_shape_fn(n, |i| {
+            if i == 0 {
+                (y[1] - y[0]) / (x[1] - x[0])
+            } else if i == n - 1 {
+                (y[n - 1] - y[n - 2]) / (x[n - 1] - x[n - 2])
+            } else {
+                let (before, after) = (x[i] - x[i - 1], x[i + 1] - x[i]);
+                (before * before * y[i + 1] - after * after * y[i - 1]
+                    + (after * after - before * before) * y[i])
+                    / (before * after * (before + after))
+            }
+        });
+        let mut builder = self.derived_builder(values, self.per_xunit(1)?);
+        match (self.get_x0(), self.get_dx()) {
+            (Some(x0_quantity), Some(dx_quantity)) => {
+                builder = builder.x0(x0_quantity.clone()).dx(dx_quantity.clone());
+            }
+            _ => builder = builder.xindex(Quantity::new(x.clone(), self.xunit()?.clone())),
+        }
+        builder.build()
+    }
+
+    /// Returns the cumulative trapezoidal integral of the data along the x-axis, starting
+    /// from zero at the first sample, in the unit of the data times the x-unit.
+    ///
+    /// The result keeps the x-axis, so integrating a velocity channel over time gives the
+    /// displacement relative to the start of the data.
+    pub fn integrate(&self) -> Result<Series, QuantityError> {
+        let x = self.x_values()?;
+        let y = self.value();
+        let mut values = Array1::zeros(y.len());
+        for i in 1..y.len() {
+            values[i] = values[i - 1] + 0.5 * (y[i] + y[i - 1]) * (x[i] - x[i - 1]);
+        }
+        let unit = (Quantity::new(array![1.0], self.unit().clone())
+            * Quantity::new(array![1.0], self.xunit()?.clone()))
+        .unit;
+        let mut builder = self.derived_builder(values, unit);
+        match (self.get_x0(), self.get_dx()) {
+            (Some(x0_quantity), Some(dx_quantity)) => {
+                builder = builder.x0(x0_quantity.clone()).dx(dx_quantity.clone());
+            }
+            _ => builder = builder.xindex(Quantity::new(x.clone(), self.xunit()?.clone())),
+        }
+        builder.build()
+    }
+
+    // The x-index values, which differences and integrals need
+    fn x_values(&self) -> Result<&Array1<f64>, QuantityError> {
+        self.get_xindex()
+            .map(|xindex_quantity| &xindex_quantity.value)
+            .ok_or_else(|| {
+                QuantityError::InvalidQuantity(
+                    "Cannot differentiate or integrate a series without an x-axis".to_string(),
+                )
+            })
+    }
+
+    fn xunit(&self) -> Result<&Unit, QuantityError> {
+        self.get_xindex()
+            .map(|xindex_quantity| &xindex_quantity.unit)
+            .ok_or_else(|| QuantityError::InvalidQuantity("The series has no x-axis".to_string()))
+    }
+
+    // The unit of the data divided by the x-unit `power` times
+    fn per_xunit(&self, power: usize) -> Result<Unit, QuantityError> {
+        let mut quantity = Quantity::new(array![1.0], self.unit().clone());
+        for _ in 0..power {
+            quantity = (quantity / Quantity::new(array![1.0], self.xunit()?.clone()))?;
+        }
+        Ok(quantity.unit)
+    }
+
+    // Starts a builder for derived values in `unit`, carrying the name, epoch and channel
+    fn derived_builder(&self, values: Array1<f64>, unit: Unit) -> SeriesBuilder {
+        let mut builder = SeriesBuilder::new().value(values).unit(unit);
+        if let Some(name) = self.get_name() {
+            builder = builder.name(name);
+        }
+        if let Some(epoch) = self.get_epoch() {
+            builder = builder.epoch(epoch);
+        }
+        if let Some(channel) = self.get_channel() {
+            builder = builder.source_channel(channel.clone());
+        }
+        builder
+    }
+}
+
+// --- Tests for differentiation and integration ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use astronomy::units::{METRE, SECOND};
+
+    fn velocity_unit() -> Unit {
+        (Quantity::new(array![1.0], METRE) / Quantity::new(array![1.0], SECOND))
+            .unwrap()
+            .unit
+    }
+
+    #[test]
+    fn test_diff() {
+        // x² sampled every 0.5 s from 1 s
+        let series = SeriesBuilder::new()
+            .value(Array1::from_shape_fn(6, |i| (1.0 + 0.5 * i as f64).powi(2)))
+            .unit(METRE)
+            .name("Position")
+            .x0(Quantity::new(array![1.0], SECOND))
+            .dx(Quantity::new(array![0.5], SECOND))
+            .build()
+            .unwrap();
+
+        let first = series.diff(1).unwrap();
+        assert_eq!(first.value(), &array![2.5, 3.5, 4.5, 5.5, 6.5]);
+        assert_eq!(first.unit(), &velocity_unit());
+        assert_eq!(first.get_x0().unwrap().value[0], 1.5);
+        assert_eq!(first.get_name(), Some("Position"));
+
+        let second = series.diff(2).unwrap();
+        assert_eq!(second.value(), &array![2.0, 2.0, 2.0, 2.0]);
+        assert_eq!(second.get_x0().unwrap().value[0], 2.0);
+        assert_eq!(series.diff(0).unwrap().value(), series.value());
+        assert!(series.diff(6).is_err());
+
+        // Uneven spacing
+        let uneven = SeriesBuilder::new()
+            .value(array![0.0, 1.0, 9.0])
+            .unit(METRE)
+            .xindex(Quantity::new(array![0.0, 1.0, 3.0], SECOND))
+            .build()
+            .unwrap();
+        let first = uneven.diff(1).unwrap();
+        assert_eq!(first.value(), &array![1.0, 4.0]);
+        assert_eq!(first.get_xindex().unwrap().value, array![1.0, 3.0]);
+        assert_eq!(uneven.diff(2).unwrap().value(), &array![2.0]);
+    }
+
+    #[test]
+    fn test_gradient() {
+        // A quadratic, whose central differences are exact even on an uneven grid
+        let x = array![0.0, 1.0, 3.0, 4.0, 6.0];
+        let series = SeriesBuilder::new()
+            .value(x.mapv(|x| x * x))
+            .unit(METRE)
+            .xindex(Quantity::new(x.clone(), SECOND))
+            .build()
+            .unwrap();
+        let gradient = series.gradient().unwrap();
+        assert_eq!(gradient.value(), &array![1.0, 2.0, 6.0, 8.0, 10.0]);
+        assert_eq!(gradient.unit(), &velocity_unit());
+        assert_eq!(gradient.get_xindex(), series.get_xindex());
+
+        let regular = SeriesBuilder::new()
+            .value(array![1.0, 2.0, 4.0, 7.0])
+            .unit(METRE)
+            .x0(Quantity::new(array![0.0], SECOND))
+            .dx(Quantity::new(array![2.0], SECOND))
+            .build()
+            .unwrap();
+        let gradient = regular.gradient().unwrap();
+        assert_eq!(gradient.value(), &array![0.5, 0.75, 1.25, 1.5]);
+        assert_eq!(gradient.get_dx(), regular.get_dx());
+    }
+
+    #[test]
+    fn test_integrate() {
+        // A velocity of 2t m/s integrates to t² m
+        let velocity = SeriesBuilder::new()
+            .value(array![0.0, 1.0, 2.0, 3.0, 4.0])
+            .unit(velocity_unit())
+            .x0(Quantity::new(array![0.0], SECOND))
+            .dx(Quantity::new(array![0.5], SECOND))
+            .build()
+            .unwrap();
+        let displacement = velocity.integrate().unwrap();
+        assert_eq!(displacement.value(), &array![0.0, 0.25, 1.0, 2.25, 4.0]);
+        assert_eq!(displacement.unit().dimensions, METRE.dimensions);
+        assert_eq!(displacement.unit().scale, 1.0);
+        assert_eq!(displacement.get_dx(), velocity.get_dx());
+        // Differentiating recovers the interior of the velocity
+        let recovered = displacement.gradient().unwrap();
+        assert_eq!(recovered.value().slice(s![1..4]), array![1.0, 2.0, 3.0]);
+
+        let uneven = SeriesBuilder::new()
+            .value(array![1.0, 1.0, 3.0])
+            .unit(METRE)
+            .xindex(Quantity::new(array![0.0, 2.0, 3.0], SECOND))
+            .build()
+            .unwrap();
+        assert_eq!(uneven.integrate().unwrap().value(), &array![0.0, 2.0, 4.0]);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::segments::core::Segment;
+
+/// A record of how a derived data product was made, so that results can be traced back to
+/// their inputs.
+///
+/// It lists the input sources (channel names, files or URLs), the GPS span of the input
+/// data, the parameters used, the processing steps in the order applied and the version of
+/// this crate that produced the product.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Provenance {
+    sources: Vec<String>,
+    span: Option<Segment>,
+    parameters: Vec<(String, String)>,
+    processing: Vec<String>,
+    crate_version: String,
+}
+
+impl Provenance {
+    /// Starts an empty record stamped with the version of this crate.
+    pub fn new() -> Self {
+        Provenance {
+            sources: Vec::new(),
+            span: None,
+            parameters: Vec::new(),
+            processing: Vec::new(),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Adds an input source, such as a channel name, file path or URL.
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.sources.push(source.into());
+        self
+    }
+    /// Sets the GPS span of the input data.
+    pub fn span(mut self, span: Segment) -> Self {
+        self.span = Some(span);
+        self
+    }
+    /// Records a parameter; a parameter already recorded under `name` is replaced.
+    pub fn parameter(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        let name = name.into();
+        let value = value.to_string();
+        match self.parameters.iter_mut().find(|(key, _)| *key == name) {
+            Some(entry) => entry.1 = value,
+            None => self.parameters.push((name, value)),
+        }
+        self
+    }
+    /// Appends a processing step.
+    pub fn step(mut self, step: impl Into<String>) -> Self {
+        self.processing.push(step.into());
+        self
+    }
+
+    pub fn get_sources(&self) -> &[String] {
+        &self.sources
+    }
+    pub fn get_span(&self) -> Option<Segment> {
+        self.span
+    }
+    pub fn get_parameters(&self) -> &[(String, String)] {
+        &self.parameters
+    }
+    /// Returns the value recorded for the parameter `name`, if any.
+    pub fn get_parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+    pub fn get_processing(&self) -> &[String] {
+        &self.processing
+    }
+    pub fn get_crate_version(&self) -> &str {
+        &self.crate_version
+    }
+}
+
+impl Default for Provenance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// --- Tests for Provenance ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provenance_record() {
+        let provenance = Provenance::new()
+            .source("H1:GDS-CALIB_STRAIN")
+            .span(Segment::new(1_000_000_000.0, 1_000_000_064.0))
+            .parameter("fftlength", 4.0)
+            .parameter("window", "Hann")
+            .parameter("fftlength", 8.0)
+            .step("bandpass")
+            .step("psd");
+
+        assert_eq!(provenance.get_sources(), ["H1:GDS-CALIB_STRAIN"]);
+        assert_eq!(provenance.get_span().unwrap().end(), 1_000_000_064.0);
+        assert_eq!(provenance.get_parameter("fftlength"), Some("8"));
+        assert_eq!(provenance.get_parameters().len(), 2);
+        assert_eq!(provenance.get_processing(), ["bandpass", "psd"]);
+        assert_eq!(provenance.get_crate_version(), env!("CARGO_PKG_VERSION"));
+    }
+}
@@ -1,8 +1,9 @@
 use crate::detector::channel::Channel;
-use crate::types::array::GWArray;
+use crate::types::array::{GWArray, quantity_heap_size};
 use astronomy::time::Time;
-use astronomy::units::{Quantity, QuantityError, Unit};
+use astronomy::units::{Quantity, QuantityError, Unit, UnitProduct};
 use ndarray::Array1;
+use std::mem::size_of;
 use std::ops::{Add, Div, Mul, Sub};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -13,12 +14,25 @@ pub struct Series {
     _xindex_cache: Option<Quantity>,
 }
 
+/// Whether [`to_db`](Series::to_db) treats the data as a power or as an amplitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecibelScale {
+    /// `10 log10(x / reference)`, for powers and power spectral densities
+    #[default]
+    Power,
+    /// `20 log10(x / reference)`, for amplitudes such as transfer function magnitudes
+    Amplitude,
+}
+
 pub struct SeriesBuilder {
     value: Option<Array1<f64>>,
+    imag: Option<Array1<f64>>,
     unit: Option<Unit>,
     name: Option<String>,
     epoch: Option<Time>,
     channel: Option<Channel>,
+    // Whether the unit of the channel defaults and cross-checks that of the data
+    check_channel_unit: bool,
     x0: Option<Quantity>,
     dx: Option<Quantity>,
     xindex: Option<Quantity>,
@@ -28,10 +42,12 @@ impl SeriesBuilder {
     pub fn new() -> Self {
         SeriesBuilder {
             value: None,
+            imag: None,
             unit: None,
             name: None,
             epoch: None,
             channel: None,
+            check_channel_unit: true,
             x0: None,
             dx: None,
             xindex: None,
@@ -43,6 +59,12 @@ impl SeriesBuilder {
         self
     }
 
+    /// Sets the imaginary part of the data, making the series complex.
+    pub fn imag(mut self, imag: Array1<f64>) -> Self {
+        self.imag = Some(imag);
+        self
+    }
+
     pub fn unit(mut self, unit: Unit) -> Self {
         self.unit = Some(unit);
         self
@@ -58,8 +80,18 @@ impl SeriesBuilder {
         self
     }
 
+    /// Sets the channel the data were recorded on. Without an explicit unit the series takes
+    /// the unit of the channel; with one, the two must agree.
     pub fn channel(mut self, channel: Channel) -> Self {
         self.channel = Some(channel);
+        self.check_channel_unit = true;
+        self
+    }
+
+    // Sets the channel that derived data were computed from, leaving their unit independent
+    pub(crate) fn source_channel(mut self, channel: Channel) -> Self {
+        self.channel = Some(channel);
+        self.check_channel_unit = false;
         self
     }
 
@@ -83,7 +115,31 @@ impl SeriesBuilder {
         let value = self.value.ok_or_else(|| {
             QuantityError::InvalidQuantity("Value is required to build Series".to_string())
         })?;
-        let array_data = GWArray::new(value, self.unit, self.name, self.epoch, self.channel);
+        let channel_unit = self
+            .channel
+            .as_ref()
+            .filter(|_| self.check_channel_unit)
+            .and_then(Channel::get_unit);
+        let unit = match (self.unit, channel_unit) {
+            (Some(unit), Some(channel_unit)) if !same_unit(&unit, channel_unit) => {
+                return Err(QuantityError::MismatchError(format!(
+                    "Unit '{}' does not match the unit '{}' of channel {}",
+                    unit.name,
+                    channel_unit.name,
+                    self.channel
+                        .as_ref()
+                        .map_or("", |channel| channel.get_name())
+                )));
+            }
+            (None, Some(channel_unit)) => Some(channel_unit.clone()),
+            (unit, _) => unit,
+        };
+        let array_data = match self.imag {
+            Some(imag) => {
+                GWArray::new_complex(value, imag, unit, self.name, self.epoch, self.channel)?
+            }
+            None => GWArray::new(value, unit, self.name, self.epoch, self.channel),
+        };
         let data_len = array_data.value().len();
         let resolved_index = if let Some(index_quantity) = self.xindex {
             // If xindex is explicitly provided, use it directly
@@ -158,6 +214,12 @@ impl Series {
     pub fn value(&self) -> &Array1<f64> {
         self.array_data.value()
     }
+    pub fn imag(&self) -> Option<&Array1<f64>> {
+        self.array_data.imag()
+    }
+    pub fn is_complex(&self) -> bool {
+        self.array_data.is_complex()
+    }
     pub fn unit(&self) -> &Unit {
         self.array_data.unit()
     }
@@ -170,6 +232,90 @@ impl Series {
     pub fn get_channel(&self) -> Option<&Channel> {
         self.array_data.get_channel()
     }
+    pub fn mean(&self) -> Result<Quantity, QuantityError> {
+        self.array_data.mean()
+    }
+    pub fn median(&self) -> Result<Quantity, QuantityError> {
+        self.array_data.median()
+    }
+    pub fn std(&self) -> Result<Quantity, QuantityError> {
+        self.array_data.std()
+    }
+    pub fn min(&self) -> Result<Quantity, QuantityError> {
+        self.array_data.min()
+    }
+    pub fn max(&self) -> Result<Quantity, QuantityError> {
+        self.array_data.max()
+    }
+    pub fn abs_max(&self) -> Result<Quantity, QuantityError> {
+        self.array_data.abs_max()
+    }
+    pub fn abs(&self) -> Series {
+        self.with_array(self.array_data.abs())
+    }
+    pub fn phase(&self) -> Series {
+        self.with_array(self.array_data.phase())
+    }
+    pub fn conj(&self) -> Series {
+        self.with_array(self.array_data.conj())
+    }
+    pub fn sqrt(&self) -> Result<Series, QuantityError> {
+        Ok(self.with_array(self.array_data.sqrt()?))
+    }
+    pub fn log10(&self) -> Result<Series, QuantityError> {
+        Ok(self.with_array(self.array_data.log10()?))
+    }
+    pub fn powi(&self, n: i32) -> Result<Series, QuantityError> {
+        Ok(self.with_array(self.array_data.powi(n)?))
+    }
+    /// Returns the data in decibels relative to `reference`, as a dimensionless series with
+    /// the unit label `dB` on the same x-axis.
+    ///
+    /// The reference is converted to the unit of the data. Zero data give negative infinity
+    /// and negative data NaN, so take the magnitude of signed data first.
+    pub fn to_db(
+        &self,
+        reference: &Quantity,
+        scale: DecibelScale,
+    ) -> Result<Series, QuantityError> {
+        if reference.value.len() != 1 {
+            return Err(QuantityError::InvalidQuantity(
+                "reference must be a scalar quantity.".to_string(),
+            ));
+        }
+        let reference = reference.to(self.unit())?.value[0];
+        let factor = match scale {
+            DecibelScale::Power => 10.0,
+            DecibelScale::Amplitude => 20.0,
+        };
+        let values = self.value().mapv(|x| factor * (x / reference).log10());
+        Ok(self.with_array(GWArray::new(
+            values,
+            Some(Unit::new("dB", 1.0, UnitProduct::zero())),
+            self.array_data.name.clone(),
+            self.array_data.epoch,
+            self.array_data.channel.clone(),
+        )))
+    }
+    // The same unit and x-axis around new values
+    pub(crate) fn with_values(&self, values: Array1<f64>) -> Series {
+        self.with_array(GWArray::new(
+            values,
+            Some(self.unit().clone()),
+            self.array_data.name.clone(),
+            self.array_data.epoch,
+            self.array_data.channel.clone(),
+        ))
+    }
+    // The same x-axis around new data
+    fn with_array(&self, array_data: GWArray) -> Series {
+        Series::new_internal(
+            array_data,
+            self.x0.clone(),
+            self.dx.clone(),
+            self._xindex_cache.clone(),
+        )
+    }
     // Series specific methods
     pub fn get_x0(&self) -> Option<&Quantity> {
         self.x0.as_ref()
@@ -193,6 +339,15 @@ impl Series {
             None
         }
     }
+    /// Returns the approximate memory footprint in bytes, including the x-axis quantities.
+    pub fn mem_size(&self) -> usize {
+        let axes = [&self.x0, &self.dx, &self._xindex_cache]
+            .into_iter()
+            .flatten()
+            .map(quantity_heap_size)
+            .sum::<usize>();
+        self.array_data.mem_size() + axes + size_of::<Self>() - size_of::<GWArray>()
+    }
     pub fn get_xunit(&self) -> Option<&Unit> {
         if let Some(xindex_quantity) = self.get_xindex() {
             Some(&xindex_quantity.unit)
@@ -206,8 +361,14 @@ impl Series {
     }
 }
 
+// Whether two units have the same dimensions and scale, whatever their names; units from
+// quantity arithmetic are unnamed
+fn same_unit(a: &Unit, b: &Unit) -> bool {
+    a.dimensions == b.dimensions && (a.scale - b.scale).abs() <= 1e-12 * a.scale.abs()
+}
+
 // Helper to propagate metadata for Series after an arithmetic operation
-fn propagate_metadata_series(result_quantity: Quantity, lhs: &Series, rhs: &Series) -> Series {
+fn propagate_metadata_series(mut result_array: GWArray, lhs: &Series, rhs: &Series) -> Series {
     // Attempt to get metdata from the left-hand side (lhs) Series, falling back to the right-hand side (rhs) if necessary
     let new_name = lhs.array_data.name.clone().or(rhs.array_data.name.clone());
     let new_epoch = lhs.array_data.epoch.or(rhs.array_data.epoch);
@@ -220,7 +381,7 @@ fn propagate_metadata_series(result_quantity: Quantity, lhs: &Series, rhs: &Seri
     let dx_clone = lhs.dx.clone().or(rhs.dx.clone());
     let xindex_clone = lhs._xindex_cache.clone().or(rhs._xindex_cache.clone());
 
-    let data_len = result_quantity.value.len();
+    let data_len = result_array.value().len();
     let re_derived_xindex = if let Some(index_quantity) = xindex_clone.clone() {
         if index_quantity.value.len() == data_len {
             Some(index_quantity)
@@ -265,58 +426,38 @@ fn propagate_metadata_series(result_quantity: Quantity, lhs: &Series, rhs: &Seri
         None
     };
 
-    Series::new_internal(
-        GWArray::new(
-            result_quantity.value,
-            Some(result_quantity.unit),
-            new_name,
-            new_epoch,
-            new_channel,
-        ),
-        x0_clone,
-        dx_clone,
-        re_derived_xindex,
-    )
+    result_array.name = new_name;
+    result_array.epoch = new_epoch;
+    result_array.channel = new_channel;
+    Series::new_internal(result_array, x0_clone, dx_clone, re_derived_xindex)
 }
 // --- Implementing Traits for `Series` (Arithmetic Operations) ---
 impl Add for Series {
     type Output = Result<Self, QuantityError>;
     fn add(self, rhs: Self) -> Self::Output {
         let added_array = (self.array_data.clone() + rhs.array_data.clone())?;
-        Ok(propagate_metadata_series(added_array.quantity, &self, &rhs))
+        Ok(propagate_metadata_series(added_array, &self, &rhs))
     }
 }
 impl Div for Series {
     type Output = Result<Self, QuantityError>;
     fn div(self, rhs: Self) -> Self::Output {
         let divided_array = (self.array_data.clone() / rhs.array_data.clone())?;
-        Ok(propagate_metadata_series(
-            divided_array.quantity,
-            &self,
-            &rhs,
-        ))
+        Ok(propagate_metadata_series(divided_array, &self, &rhs))
     }
 }
 impl Mul for Series {
     type Output = Result<Self, QuantityError>;
     fn mul(self, rhs: Self) -> Self::Output {
         let multiplied_array = (self.array_data.clone() * rhs.array_data.clone())?;
-        Ok(propagate_metadata_series(
-            multiplied_array.quantity,
-            &self,
-            &rhs,
-        ))
+        Ok(propagate_metadata_series(multiplied_array, &self, &rhs))
     }
 }
 impl Sub for Series {
     type Output = Result<Self, QuantityError>;
     fn sub(self, rhs: Self) -> Self::Output {
         let subtracted_array = (self.array_data.clone() - rhs.array_data.clone())?;
-        Ok(propagate_metadata_series(
-            subtracted_array.quantity,
-            &self,
-            &rhs,
-        ))
+        Ok(propagate_metadata_series(subtracted_array, &self, &rhs))
     }
 }
 
@@ -409,6 +550,35 @@ mod tests {
         println!("Series Debug (explicit xindex): {:?}", data);
     }
 
+    #[test]
+    fn test_series_complex() {
+        let series = SeriesBuilder::new()
+            .value(array![1.0, 0.0])
+            .imag(array![1.0, 2.0])
+            .unit(METRE)
+            .x0(Quantity::new(array![0.0], SECOND))
+            .dx(Quantity::new(array![1.0], SECOND))
+            .build()
+            .unwrap();
+        assert!(series.is_complex());
+        assert_eq!(series.imag(), Some(&array![1.0, 2.0]));
+        assert_eq!(series.abs().value(), &array![2.0_f64.sqrt(), 2.0]);
+        assert_eq!(series.phase().value()[0], std::f64::consts::FRAC_PI_4);
+
+        // |z|² is real, on the same axis
+        let power = (series.clone() * series.conj()).unwrap();
+        assert_eq!(power.value(), &array![2.0, 4.0]);
+        assert_eq!(power.imag(), Some(&array![0.0, 0.0]));
+        assert_eq!(power.get_xindex(), series.get_xindex());
+        assert!(
+            SeriesBuilder::new()
+                .value(array![1.0, 2.0])
+                .imag(array![1.0])
+                .build()
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_series_add_propagation() {
         let unit_s = SECOND.clone();
@@ -0,0 +1,212 @@
+use crate::detector::channel::Channel;
+use crate::types::array::{GWArray2, quantity_heap_size};
+use crate::types::provenance::Provenance;
+use astronomy::time::Time;
+use astronomy::units::{HERTZ, Quantity, QuantityError, SECOND, Unit};
+use ndarray::Array2;
+use std::mem::size_of;
+
+/// A time-frequency representation of data, such as a sequence of power spectral densities.
+///
+/// Rows of the value matrix correspond to the `times` axis (GPS seconds) and columns to the
+/// `frequencies` axis (Hz).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrogram {
+    array_data: GWArray2,
+    times: Quantity,
+    frequencies: Quantity,
+    provenance: Option<Provenance>,
+}
+
+/// Builder for Spectrogram
+pub struct SpectrogramBuilder {
+    value: Option<Array2<f64>>,
+    unit: Option<Unit>,
+    name: Option<String>,
+    epoch: Option<Time>,
+    channel: Option<Channel>,
+    times: Option<Quantity>,       // start time of each row
+    frequencies: Option<Quantity>, // frequency of each column
+    provenance: Option<Provenance>,
+}
+
+impl SpectrogramBuilder {
+    pub fn new() -> Self {
+        SpectrogramBuilder {
+            value: None,
+            unit: None,
+            name: None,
+            epoch: None,
+            channel: None,
+            times: None,
+            frequencies: None,
+            provenance: None,
+        }
+    }
+
+    // Builder setters for the SpectrogramBuilder
+    pub fn value(mut self, value: Array2<f64>) -> Self {
+        self.value = Some(value);
+        self
+    }
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+    pub fn epoch(mut self, epoch: Time) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+    pub fn channel(mut self, channel: Channel) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+    /// Sets the time of each row as a `Quantity`.
+    pub fn times(mut self, times: Quantity) -> Self {
+        self.times = Some(times);
+        self
+    }
+    /// Sets the frequency of each column as a `Quantity`.
+    pub fn frequencies(mut self, frequencies: Quantity) -> Self {
+        self.frequencies = Some(frequencies);
+        self
+    }
+    /// Sets the record of how this spectrogram was derived.
+    pub fn provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+    /// Builds the `Spectrogram`, converting the axes to seconds and Hz and checking that
+    /// their lengths match the shape of the value matrix.
+    pub fn build(self) -> Result<Spectrogram, QuantityError> {
+        let value = self.value.ok_or_else(|| {
+            QuantityError::InvalidQuantity("Value is required to build Spectrogram".to_string())
+        })?;
+        let times = self
+            .times
+            .ok_or_else(|| {
+                QuantityError::InvalidQuantity(
+                    "Times are required to build Spectrogram".to_string(),
+                )
+            })?
+            .to(&SECOND)?;
+        let frequencies = self
+            .frequencies
+            .ok_or_else(|| {
+                QuantityError::InvalidQuantity(
+                    "Frequencies are required to build Spectrogram".to_string(),
+                )
+            })?
+            .to(&HERTZ)?;
+        if value.dim() != (times.value.len(), frequencies.value.len()) {
+            return Err(QuantityError::MismatchError(format!(
+                "Value shape {:?} does not match {} times by {} frequencies",
+                value.dim(),
+                times.value.len(),
+                frequencies.value.len()
+            )));
+        }
+        Ok(Spectrogram {
+            array_data: GWArray2::new(value, self.unit, self.name, self.epoch, self.channel),
+            times,
+            frequencies,
+            provenance: self.provenance,
+        })
+    }
+}
+
+impl Default for SpectrogramBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spectrogram {
+    /// Returns the value matrix, indexed by `[time, frequency]`.
+    pub fn value(&self) -> &Array2<f64> {
+        self.array_data.value()
+    }
+    /// Returns the value matrix with its unit and metadata.
+    pub fn array(&self) -> &GWArray2 {
+        &self.array_data
+    }
+    pub fn unit(&self) -> &Unit {
+        self.array_data.unit()
+    }
+    pub fn get_name(&self) -> Option<&str> {
+        self.array_data.get_name()
+    }
+    pub fn get_epoch(&self) -> Option<Time> {
+        self.array_data.get_epoch()
+    }
+    pub fn get_channel(&self) -> Option<&Channel> {
+        self.array_data.get_channel()
+    }
+    /// Returns the approximate memory footprint in bytes of the value matrix, axes and name.
+    pub fn mem_size(&self) -> usize {
+        self.array_data.mem_size()
+            + quantity_heap_size(&self.times)
+            + quantity_heap_size(&self.frequencies)
+            + size_of::<Self>()
+            - size_of::<GWArray2>()
+    }
+    pub fn get_times(&self) -> &Quantity {
+        &self.times
+    }
+    pub fn get_frequencies(&self) -> &Quantity {
+        &self.frequencies
+    }
+    /// Returns the record of how this spectrogram was derived, if one is attached.
+    pub fn get_provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+    /// Attaches a record of how this spectrogram was derived, replacing any previous one.
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+}
+
+// --- Test Module for Spectrogram ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use astronomy::units::METRE;
+    use ndarray::array;
+
+    #[test]
+    fn test_spectrogram_creation() {
+        let spectrogram = SpectrogramBuilder::new()
+            .value(array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])
+            .unit(METRE)
+            .name("Spectrogram")
+            .times(Quantity::new(array![100.0, 104.0], SECOND))
+            .frequencies(Quantity::new(array![0.0, 0.25, 0.5], HERTZ))
+            .build()
+            .unwrap();
+
+        assert_eq!(spectrogram.value().dim(), (2, 3));
+        assert_eq!(spectrogram.unit(), &METRE);
+        assert_eq!(spectrogram.get_name(), Some("Spectrogram"));
+        assert_eq!(spectrogram.get_times().value, array![100.0, 104.0]);
+        assert_eq!(spectrogram.get_frequencies().unit, HERTZ);
+        assert_eq!(
+            spectrogram.array().row(1).unwrap().value(),
+            &array![4.0, 5.0, 6.0]
+        );
+    }
+
+    #[test]
+    fn test_spectrogram_shape_mismatch() {
+        let result = SpectrogramBuilder::new()
+            .value(array![[1.0, 2.0], [3.0, 4.0]])
+            .times(Quantity::new(array![0.0, 1.0, 2.0], SECOND))
+            .frequencies(Quantity::new(array![0.0, 1.0], HERTZ))
+            .build();
+        assert!(matches!(result, Err(QuantityError::MismatchError(_))));
+    }
+}